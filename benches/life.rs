@@ -0,0 +1,73 @@
+//! Compares render modes on Game of Life simulation.
+//!
+//! Every generation the whole field is sent to the board, but only some cells change. Run with
+//! `cargo bench --bench life`.
+
+use std::time::{Duration, Instant};
+
+use gameboard::{Board, Cell, Position, RenderMode};
+
+const FIELD_WIDTH: usize = 80;
+const FIELD_HEIGHT: usize = 40;
+const GENERATIONS: usize = 200;
+const ALIVE: char = '█';
+const NEIGHBOURS: [(isize, isize); 8] =
+    [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+// Create initial field with pseudo-random cells.
+fn create_field() -> Vec<bool> {
+    let mut seed: u32 = 12345;
+    (0..FIELD_WIDTH * FIELD_HEIGHT).map(|_| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (seed >> 16) % 3 == 1
+    }).collect()
+}
+
+fn next_generation(field: &[bool]) -> Vec<bool> {
+    let mut next = vec![false; field.len()];
+    for y in 0..FIELD_HEIGHT {
+        for x in 0..FIELD_WIDTH {
+            let mut neighbours = 0;
+            for (dx, dy) in &NEIGHBOURS {
+                let nx = (x as isize + dx).rem_euclid(FIELD_WIDTH as isize) as usize;
+                let ny = (y as isize + dy).rem_euclid(FIELD_HEIGHT as isize) as usize;
+                if field[ny * FIELD_WIDTH + nx] {
+                    neighbours += 1;
+                }
+            }
+            let idx = y * FIELD_WIDTH + x;
+            next[idx] = neighbours == 3 || (field[idx] && neighbours == 2);
+        }
+    }
+    next
+}
+
+// Run simulation and return (time spent in board, bytes written).
+fn run(mode: RenderMode) -> (Duration, usize) {
+    let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    board.set_render_mode(mode);
+    board.init_from_vec(&vec![Cell::Empty; FIELD_WIDTH * FIELD_HEIGHT], None);
+    board.render();
+
+    let mut field = create_field();
+    let mut elapsed = Duration::from_secs(0);
+    let mut bytes = 0;
+    for _ in 0..GENERATIONS {
+        let start = Instant::now();
+        for (i, &alive) in field.iter().enumerate() {
+            let cell = if alive { Cell::Char(ALIVE) } else { Cell::Empty };
+            board.fill_region(Position(i % FIELD_WIDTH, i / FIELD_WIDTH), 1, 1, cell);
+        }
+        bytes += board.render_updates().map_or(0, |s| s.len());
+        elapsed += start.elapsed();
+        field = next_generation(&field);
+    }
+    (elapsed, bytes)
+}
+
+fn main() {
+    for &(name, mode) in &[("Dirty", RenderMode::Dirty), ("FullDiff", RenderMode::FullDiff)] {
+        let (elapsed, bytes) = run(mode);
+        println!("{:>8}: {} generations in {:?}, {} bytes", name, GENERATIONS, elapsed, bytes);
+    }
+}
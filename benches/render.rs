@@ -0,0 +1,203 @@
+//! Measures output written to the terminal for a full-board minesweeper reveal.
+//!
+//! Incremental update, which skips cells looking the same as on screen, is compared with full
+//! redraw, which writes every cell.
+//!
+//! Frame writes are measured as well. Game renders each frame (board and information area
+//! updates) into a buffer and writes it at once. Before that, each part was written to the
+//! terminal separately, so terminal could show a partially drawn screen, for example, a cleared
+//! screen without the board. Parts are written to `LineWriter`, the same as locked stdout, and
+//! write calls which reach the terminal are counted. Results on 50x20 board with information
+//! area:
+//!
+//! | Frame                | Per part writes | Single frame writes | Bytes |
+//! |----------------------|-----------------|---------------------|-------|
+//! | Reveal + info update | 1               | 1                   | 892   |
+//! | Redraw (clear + all) | 3               | 1                   | 3953  |
+//!
+//! Number of bytes doesn't change. Small frames fit into `LineWriter` buffer (1 KiB), so they
+//! were written at once before as well. Larger frames, like redraw after screen clear, were
+//! split, now `Game::redraw` reaches the terminal in a single write. Run with
+//! `cargo bench --bench render`.
+
+use std::cell::RefCell;
+use std::io::{self, LineWriter, Read, Write};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gameboard::{Board, Game, Info, InfoLayout, InputListener, Key, Terminal};
+
+const FIELD_WIDTH: usize = 50;
+const FIELD_HEIGHT: usize = 20;
+const CONCEALED: char = '▒';
+const MINE: char = '*';
+const CLEAR_SCREEN: &str = "\x1b[2J";
+
+// Get revealed field content. Cells are mines, bomb numbers or empty.
+fn revealed_cell(x: usize, y: usize) -> char {
+    match (x * 7 + y * 13) % 10 {
+        0 => MINE,
+        1 => '1',
+        2 => '2',
+        _ => ' '
+    }
+}
+
+fn create_field(opened_columns: usize) -> String {
+    let mut field = String::with_capacity(FIELD_WIDTH * FIELD_HEIGHT * 3);
+    for y in 0..FIELD_HEIGHT {
+        for x in 0..FIELD_WIDTH {
+            field.push(if x < opened_columns { revealed_cell(x, y) } else { CONCEALED });
+        }
+    }
+    field
+}
+
+// Reveal the whole field after player opened its left half. Return (time spent in board, bytes
+// written).
+fn reveal(full_redraw: bool) -> (Duration, usize) {
+    let mut revealed = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    revealed.init_from_str(&create_field(FIELD_WIDTH), None);
+    let state = revealed.snapshot();
+
+    let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    board.init_from_str(&create_field(FIELD_WIDTH / 2), None);
+    board.render();
+
+    let start = Instant::now();
+    board.restore(&state);
+    if full_redraw {
+        board.request_full_redraw();
+    }
+    let bytes = board.render_updates().map_or(0, |s| s.len());
+    (start.elapsed(), bytes)
+}
+
+// Number of write calls and bytes which reached the terminal.
+#[derive(Clone, Copy, Default)]
+struct Stats {
+    writes: usize,
+    bytes: usize,
+}
+
+// Terminal output which counts writes.
+struct Counter {
+    stats: Rc<RefCell<Stats>>,
+}
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut stats = self.stats.borrow_mut();
+        stats.writes += 1;
+        stats.bytes += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Terminal with line buffered output, the same as locked stdout.
+struct CountingTerminal {
+    output: LineWriter<Counter>,
+}
+
+impl Write for CountingTerminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl Terminal for CountingTerminal {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok((120, 40))
+    }
+}
+
+struct App {}
+
+impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    type Action = ();
+
+    fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+}
+
+fn create_info() -> Info {
+    Info::new(20, InfoLayout::Right, &["Bombs left", "100"])
+}
+
+// Write frame parts separately or as a single frame and flush. Return writes which reached the
+// terminal.
+fn write_frame(parts: &[String], single_frame: bool) -> Stats {
+    let stats = Rc::new(RefCell::new(Stats::default()));
+    let mut output = LineWriter::new(Counter { stats: Rc::clone(&stats) });
+    if single_frame {
+        output.write_all(parts.concat().as_bytes()).unwrap();
+    } else {
+        for part in parts {
+            output.write_all(part.as_bytes()).unwrap();
+        }
+    }
+    output.flush().unwrap();
+    let stats = *stats.borrow();
+    stats
+}
+
+// Parts of the frame which reveals the whole field and updates information area.
+fn reveal_frame() -> Vec<String> {
+    let mut revealed = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    revealed.init_from_str(&create_field(FIELD_WIDTH), None);
+    let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    board.init_from_str(&create_field(FIELD_WIDTH / 2), None);
+    board.render();
+    board.restore(&revealed.snapshot());
+    vec![board.render_updates().unwrap_or_default(), create_info().render()]
+}
+
+// Parts of the frame which clears the screen and draws everything again.
+fn redraw_frame() -> Vec<String> {
+    let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    board.init_from_str(&create_field(FIELD_WIDTH / 2), None);
+    vec![CLEAR_SCREEN.to_string(), board.render(), create_info().render()]
+}
+
+// Redraw the screen of the game. Return writes which reached the terminal.
+fn game_redraw() -> Stats {
+    let stats = Rc::new(RefCell::new(Stats::default()));
+    let terminal = CountingTerminal {
+        output: LineWriter::new(Counter { stats: Rc::clone(&stats) }),
+    };
+    let app = Rc::new(RefCell::new(App {}));
+    let mut game = Game::with_terminal(io::empty(), terminal, Rc::clone(&app));
+    let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
+    board.init_from_str(&create_field(FIELD_WIDTH / 2), None);
+    game.init(board, vec![create_info()]);
+    *stats.borrow_mut() = Stats::default();
+    game.redraw();
+    let stats = *stats.borrow();
+    stats
+}
+
+fn main() {
+    let changed = (FIELD_WIDTH - FIELD_WIDTH / 2) * FIELD_HEIGHT;
+    println!("Reveal of {} cells ({} changed)", FIELD_WIDTH * FIELD_HEIGHT, changed);
+    for &(name, full_redraw) in &[("Incremental", false), ("FullRedraw", true)] {
+        let (elapsed, bytes) = reveal(full_redraw);
+        println!("{:>11}: {} bytes in {:?}", name, bytes, elapsed);
+    }
+
+    println!("Frame writes (per part / single frame)");
+    for &(name, ref parts) in &[("Reveal", reveal_frame()), ("Redraw", redraw_frame())] {
+        let per_part = write_frame(parts, false);
+        let single = write_frame(parts, true);
+        println!("{:>11}: {} / {} writes, {} / {} bytes", name, per_part.writes, single.writes,
+                 per_part.bytes, single.bytes);
+    }
+    let game = game_redraw();
+    println!("{:>11}: {} writes, {} bytes", "GameRedraw", game.writes, game.bytes);
+}
@@ -1,11 +1,11 @@
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use termion::event::Key;
+use gameboard::Key;
 use termion::{style, color};
 
-use gameboard::{Board, Game, InputListener, Cursor, Cell, Position, ResourceTable};
+use gameboard::{Board, Game, InputListener, Terminal, Cursor, Cell, Position, ResourceTable};
 
 fn create_resources() -> ResourceTable {
     let mut res = ResourceTable::new();
@@ -16,11 +16,12 @@ fn create_resources() -> ResourceTable {
 
 struct App {}
 
-impl<R: Read, W: Write> InputListener<R, W> for App {
+impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    type Action = ();
+
     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
-        match key {
-            Key::Char('q') => game.stop(),
-            _ => {}
+        if let Key::Char('q') = key {
+            game.stop();
         }
     }
 }
@@ -36,7 +37,7 @@ fn main() {
     let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), true, None);
     let mut board = Board::new(3, 3, 6, 3, true, Some(create_resources()));
     board.init_from_vec(
-        &vec![
+        &[
             Cell::Empty,
             Cell::ResourceId(0),
             Cell::ResourceId(1),
@@ -1,14 +1,16 @@
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use termion::event::Key;
+use gameboard::Key;
 
-use gameboard::{Board, Info, InfoLayout, Game, GameState, InputListener};
+use gameboard::{Board, Info, InfoLayout, Game, GameState, InputListener, Terminal};
 
 struct App {}
 
-impl<R: Read, W: Write> InputListener<R, W> for App {
+impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    type Action = ();
+
     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
         match key {
             Key::Char('q') => game.stop(),
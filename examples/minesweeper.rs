@@ -1,13 +1,14 @@
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use termion::event::Key;
+use gameboard::Key;
 use termion::color;
 use rand::{thread_rng, Rng};
 use rand::distributions::Uniform;
 
-use gameboard::{Board, Info, InfoLayout, Game, InputListener, Cell, Cursor, Position, CellUpdates};
+use gameboard::{Board, Info, InfoLayout, Game, InputListener, Terminal, Cell, Cursor, Position,
+                CellUpdates};
 
 const FIELD_WIDTH: usize = 50;
 const FIELD_HEIGHT: usize = 20;
@@ -22,11 +23,11 @@ const MINE: char = '*';
 const FLAG: char = 'F';
 const CONCEALED: char = '▒';
 
-const TEXT_WIN: &'static str = "You WIN";
-const TEXT_LOSE: &'static str = "You LOSE";
-const TEXT_BOMBS_LEFT: &'static str = "Bombs left";
-const TEXT_KEYS: &'static str = "Move: asdw/arrows. Open: j. Flag: i. Exit: q.";
-const TEXT_REPLAY: &'static str = "Press r to replay. Press q to exit game.";
+const TEXT_WIN: &str = "You WIN";
+const TEXT_LOSE: &str = "You LOSE";
+const TEXT_BOMBS_LEFT: &str = "Bombs left";
+const TEXT_KEYS: &str = "Move: asdw/arrows. Open: j. Flag: i. Exit: q.";
+const TEXT_REPLAY: &str = "Press r to replay. Press q to exit game.";
 
 #[derive(PartialEq, Eq)]
 enum GameResult {
@@ -39,62 +40,49 @@ struct App {
     cursor_position: Position,
     board: [u8; FIELD_WIDTH * FIELD_HEIGHT],
     result: GameResult,
-    exit: bool,
     concealed: usize,
     flags: usize,
 }
 
-impl<R: Read, W: Write> InputListener<R, W> for App {
+impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    type Action = ();
+
     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
         match key {
-            Key::Char('q') => {
+            Key::Char('r') if self.result != GameResult::Unknown => {
                 game.stop();
-                self.exit = true;
             },
-            Key::Char('r') => {
-                if self.result != GameResult::Unknown {
-                    game.stop();
+            Key::Char('i') if self.result == GameResult::Unknown => {
+                if let Some(updates) = self.set_flag() {
+                    game.update_cells(updates);
+
+                    let bomb_left = BOMB_TOTAL.saturating_sub(self.flags);
+                    game.update_info(&[
+                        "",
+                        &format!("{:^width$}",
+                                 &format!("{} {}", TEXT_BOMBS_LEFT, bomb_left),
+                                 width = FIELD_WIDTH),
+                        "",
+                        &format!("{:^width$}", TEXT_KEYS, width = FIELD_WIDTH),
+                    ]);
                 }
             },
-            Key::Char('i') => {
-                if self.result == GameResult::Unknown {
-                    if let Some(updates) = self.set_flag() {
-                        game.update_cells(updates);
-
-                        let bomb_left = if self.flags <= BOMB_TOTAL {
-                            BOMB_TOTAL - self.flags
-                        } else {
-                            0
-                        };
-                        game.update_info(&[
-                            "",
-                            &format!("{:^width$}",
-                                     &format!("{} {}", TEXT_BOMBS_LEFT, bomb_left),
-                                     width = FIELD_WIDTH),
-                            "",
-                            &format!("{:^width$}", TEXT_KEYS, width = FIELD_WIDTH),
-                        ]);
-                    }
+            Key::Char('j') if self.result == GameResult::Unknown => {
+                if let Some(updates) = game.get_board().and_then(|board| self.reveal(board)) {
+                    game.update_cells(updates);
                 }
-            },
-            Key::Char('j') => {
-                if self.result == GameResult::Unknown {
-                    if let Some(updates) = self.reveal() {
-                        game.update_cells(updates);
-                    }
-                    if self.result != GameResult::Unknown {
-                        let s = if self.result == GameResult::Win {
-                            TEXT_WIN
-                        } else {
-                            TEXT_LOSE
-                        };
-                        game.update_info(&[
-                            "",
-                            &format!("{:^width$}", &s, width = FIELD_WIDTH),
-                            "",
-                            &format!("{:^width$}", TEXT_REPLAY, width = FIELD_WIDTH),
-                        ]);
-                    }
+                if self.result != GameResult::Unknown {
+                    let s = if self.result == GameResult::Win {
+                        TEXT_WIN
+                    } else {
+                        TEXT_LOSE
+                    };
+                    game.update_info(&[
+                        "",
+                        &format!("{:^width$}", &s, width = FIELD_WIDTH),
+                        "",
+                        &format!("{:^width$}", TEXT_REPLAY, width = FIELD_WIDTH),
+                    ]);
                 }
             },
             _ => {}
@@ -112,7 +100,6 @@ impl App {
             cursor_position: START_POSITION,
             board: [0; FIELD_WIDTH * FIELD_HEIGHT],
             result: GameResult::Unknown,
-            exit: false,
             concealed: FIELD_WIDTH * FIELD_HEIGHT,
             flags: 0,
         };
@@ -151,12 +138,10 @@ impl App {
             Cell::Char(FLAG)
         };
         self.toggle_flag(x, y);
-        let mut updates = CellUpdates::with_capacity(1);
-        updates.push((new_cell, Position(x, y)));
-        Some(updates)
+        Some(vec![(new_cell, Position(x, y))])
     }
 
-    fn reveal(&mut self) -> Option<CellUpdates> {
+    fn reveal(&mut self, board: &Board) -> Option<CellUpdates> {
         let Position(x, y) = self.cursor_position;
         if self.is_open(x, y) {
             return None
@@ -167,7 +152,7 @@ impl App {
             self.result = GameResult::Lose;
             self.show_all_bombs(&mut updates);
         } else {
-            self.open_free_cells(x, y, &mut updates);
+            self.open_free_cells(board, x, y, &mut updates);
             if self.concealed == BOMB_TOTAL {
                 self.result = GameResult::Win;
             }
@@ -175,18 +160,19 @@ impl App {
         Some(updates)
     }
 
-    fn open_free_cells(&mut self, x: usize, y: usize, updates: &mut CellUpdates) {
+    fn open_free_cells(&mut self, board: &Board, x: usize, y: usize,
+                       updates: &mut CellUpdates) {
         self.set_open(x, y);
         self.concealed -= 1;
-        let val = self.get_bomb_num(x, y);
+        let val = self.get_bomb_num(board, x, y);
         if val == 0 {
             // Cell is free, no bombs around.
             updates.push((Cell::Empty, Position(x, y)));
 
             // Recursively open cells around until non-free cell is reached.
-            for &(i, j) in self.cells_around(x, y).iter() {
+            for Position(i, j) in board.neighbors(Position(x, y), true) {
                 if !self.is_open(i, j) && !self.is_bomb(i, j) {
-                    self.open_free_cells(i, j, updates);
+                    self.open_free_cells(board, i, j, updates);
                 }
             }
         } else {
@@ -205,45 +191,9 @@ impl App {
         }
     }
 
-    fn get_bomb_num(&self, x: usize, y: usize) -> u8 {
-        self.cells_around(x, y).iter().map(|&(i, j)| if self.is_bomb(i, j) { 1 } else { 0 }).sum()
-    }
-
-    fn cells_around(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
-        let mut v = Vec::with_capacity(8);
-        if y > 0 {
-            // up
-            v.push((x, y - 1));
-            if x > 0 {
-                // left up
-                v.push((x - 1, y - 1));
-            }
-            if x < FIELD_WIDTH - 1 {
-                // right up
-                v.push((x + 1, y - 1));
-            }
-        }
-        if x > 0 {
-            // left
-            v.push((x - 1, y));
-        }
-        if x < FIELD_WIDTH - 1 {
-            // right
-            v.push((x + 1, y));
-        }
-        if y < FIELD_HEIGHT - 1 {
-            // down
-            v.push((x, y + 1));
-            if x > 0 {
-                // left down
-                v.push((x - 1, y + 1));
-            }
-            if x < FIELD_WIDTH - 1 {
-                // right down
-                v.push((x + 1, y + 1));
-            }
-        }
-        v
+    fn get_bomb_num(&self, board: &Board, x: usize, y: usize) -> u8 {
+        board.neighbors(Position(x, y), true).filter(|&Position(i, j)| self.is_bomb(i, j)).count()
+            as u8
     }
 
     fn is_bomb(&self, x: usize, y: usize) -> bool {
@@ -274,9 +224,10 @@ fn main() {
     let stdout = stdout.lock();
 
     let app = Rc::new(RefCell::new(App::new()));
-    let game = Rc::new(RefCell::new(Game::new(stdin, stdout, Rc::clone(&app))));
+    let mut game = Game::new(stdin, stdout, Rc::clone(&app));
+    game.set_quit_key(Some(Key::Char('q')));
 
-    while !app.borrow().exit {
+    game.run(|| {
         app.borrow_mut().reset();
         let cursor = Cursor::new(color::Rgb(0, 0, 255), START_POSITION, false, None);
         let mut board = Board::new(FIELD_WIDTH, FIELD_HEIGHT, 1, 1, false, None);
@@ -289,7 +240,6 @@ fn main() {
         ]);
         board.init_from_str(&CONCEALED.to_string().repeat(FIELD_WIDTH * FIELD_HEIGHT),
                             Some(cursor));
-        game.borrow_mut().init(board, Some(info));
-        game.borrow_mut().start();
-    }
+        (board, vec![info])
+    });
 }
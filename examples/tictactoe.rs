@@ -1,24 +1,29 @@
-use std::io::{self, Read, Write};
+// Backend is selected by features: termion (default) or crossterm.
+#![cfg_attr(not(any(feature = "termion", feature = "crossterm")), allow(dead_code, unused_imports))]
+
+#[cfg(feature = "termion")]
+use std::io;
+use std::io::Read;
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use termion::event::Key;
-use termion::color;
+use gameboard::Key;
 
-use gameboard::{Board, ResourceTable, Cell, Game, InputListener, Cursor, Position,
-                CellUpdates};
+use gameboard::{Board, ResourceTable, Cell, Color, Game, InputListener, Terminal, Cursor,
+                Position, CellUpdates};
 
 const START_POSITION: Position = Position(1, 1);
 
-const CELL_EMPTY: u8 = 0;
-const CELL_X: u8 = 1;
-const CELL_O: u8 = 2;
+const CELL_X: Cell = Cell::ResourceId(1);
+const CELL_O: Cell = Cell::ResourceId(0);
+
+const CORNERS: [Position; 4] = [Position(0, 0), Position(0, 2), Position(2, 0), Position(2, 2)];
 
-const TEXT_GAME_RESULT_WIN: &'static str = "|^|You win.";
-const TEXT_GAME_RESULT_LOSE: &'static str = "|^|You lose.";
-const TEXT_GAME_RESULT_DRAW: &'static str = "|^|Draw.";
-const TEXT_REPLAY: &'static str = "|^|Press 'r' to replay.";
-const TEXT_QUIT: &'static str = "|^|Press 'q' to quit.";
+const TEXT_GAME_RESULT_WIN: &str = "|^|You win.";
+const TEXT_GAME_RESULT_LOSE: &str = "|^|You lose.";
+const TEXT_GAME_RESULT_DRAW: &str = "|^|Draw.";
+const TEXT_REPLAY: &str = "|^|Press 'r' to replay.";
+const TEXT_QUIT: &str = "|^|Press 'q' to quit.";
 
 fn create_resources() -> ResourceTable {
     let mut res = ResourceTable::new();
@@ -37,31 +42,35 @@ enum GameResult {
 
 struct App {
     cursor_position: Position,
-    board: [u8; 9],
+    // Copy of the game board cells. Computer tries moves on it to find winning lines.
+    board: Board,
     turn_num: u8,
     result: GameResult,
-    exit: bool
 }
 
-impl<R: Read, W: Write> InputListener<R, W> for App {
+impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    type Action = ();
+
     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
         match key {
-            Key::Char('q') => {
+            Key::Char('r') if self.result != GameResult::Unknown => {
+                // No need to call game.hide_message(), because after game stop
+                // board will be recreated and redrawn anyway.
                 game.stop();
-                self.exit = true;
-            },
-            Key::Char('r') => {
-                if self.result != GameResult::Unknown {
-                    // No need to call game.hide_message(), because after game stop
-                    // board will be recreated and redrawn anyway.
-                    game.stop();
-                }
             },
             Key::Char('j') => {
                 if let Some(updates) = self.process_user_turn() {
                     game.update_cells(updates);
                 }
                 if self.result != GameResult::Unknown {
+                    // Show the winning line.
+                    let line = game.get_board().and_then(|board| {
+                        board.find_line(3, |c| *c == CELL_X)
+                            .or_else(|| board.find_line(3, |c| *c == CELL_O))
+                    });
+                    if let Some(line) = line {
+                        game.highlight_line(&line, Color::Rgb(0, 128, 0));
+                    }
                     let game_res = if self.result == GameResult::HumanWin {
                         TEXT_GAME_RESULT_WIN
                     } else if self.result == GameResult::ComputerWin {
@@ -90,29 +99,34 @@ impl App {
     fn new() -> Self {
         App {
             cursor_position: START_POSITION,
-            board: [CELL_EMPTY; 9],
+            board: App::create_board(),
             turn_num: 0,
             result: GameResult::Unknown,
-            exit: false,
         }
     }
 
+    fn create_board() -> Board {
+        let mut board = Board::new(3, 3, 1, 1, false, None);
+        board.init_from_vec(&vec![Cell::Empty; 9], None);
+        board
+    }
+
     fn reset(&mut self) {
         self.cursor_position = START_POSITION;
-        self.board = [CELL_EMPTY; 9];
+        self.board = App::create_board();
         self.turn_num = 0;
         self.result = GameResult::Unknown;
     }
 
     fn process_user_turn(&mut self) -> Option<CellUpdates> {
         let Position(x, y) = self.cursor_position;
-        if self.get(x, y) == CELL_EMPTY {
+        if self.is(x, y, &Cell::Empty) {
             // Add X to the cell. This is user's turn.
-            self.set(x, y, CELL_X);
+            self.board.set_cell(Position(x, y), CELL_X);
             let mut updates = CellUpdates::with_capacity(2);
-            updates.push((Cell::ResourceId(1), Position(x, y)));
+            updates.push((CELL_X, Position(x, y)));
 
-            if self.is_user_win() {
+            if self.board.find_line(3, |c| *c == CELL_X).is_some() {
                 self.result = GameResult::HumanWin;
             } else if !self.is_empty_cells() {
                 self.result = GameResult::Draw;
@@ -128,19 +142,19 @@ impl App {
 
     fn make_turn(&mut self, updates: &mut CellUpdates) {
         let mut new_pos = Position(1, 1); // this value will never be set
-        if let Some(pos) = self.find_two_in_line(CELL_O) {
+        if let Some(&pos) = self.find_winning_cells(&CELL_O).first() {
             // Check if we can win. Finish game if we can.
             new_pos = pos;
             self.result = GameResult::ComputerWin;
-        } else if let Some(pos) = self.find_two_in_line(CELL_X) {
+        } else if let Some(&pos) = self.find_winning_cells(&CELL_X).first() {
             // Check if user can win. Don't let user win.
             new_pos = pos;
-        } else if self.get(1, 1) == CELL_EMPTY {
+        } else if self.is(1, 1, &Cell::Empty) {
             // If center cell is empty, put 'O' in it.
             new_pos = Position(1, 1);
-        } else if self.turn_num == 1 && self.get(1, 1) == CELL_O &&
-                  ((self.get(0, 0) == CELL_X && self.get(2, 2) == CELL_X) ||
-                   (self.get(2, 0) == CELL_X && self.get(0, 2) == CELL_X)) {
+        } else if self.turn_num == 1 && self.is(1, 1, &CELL_O) &&
+                  ((self.is(0, 0, &CELL_X) && self.is(2, 2, &CELL_X)) ||
+                   (self.is(2, 0, &CELL_X) && self.is(0, 2, &CELL_X))) {
             // Handle special cases:
             //  ..x      x..
             //  .o.  or  .o.
@@ -151,170 +165,80 @@ impl App {
             new_pos = pos;
         } else {
             // Put 'O' in any corner, otherwise in any cell.
-            let indexes = [Position(0, 0), Position(0, 2), Position(2, 0), Position(2, 2),
-                           Position(0, 1), Position(1, 0), Position(1, 2), Position(2, 1)];
-            for &Position(x, y) in &indexes {
-                if self.get(x, y) == CELL_EMPTY {
+            let indexes = CORNERS.iter()
+                .chain(&[Position(0, 1), Position(1, 0), Position(1, 2), Position(2, 1)]);
+            for &Position(x, y) in indexes {
+                if self.is(x, y, &Cell::Empty) {
                     new_pos = Position(x, y);
                     break;
                 }
             }
         }
-        self.set(new_pos.0, new_pos.1, CELL_O);
+        self.board.set_cell(new_pos, CELL_O);
         self.turn_num += 1;
-        updates.push((Cell::ResourceId(0), new_pos));
-    }
-
-    // Find 2 X's or O's in line and return position of 3rd cell to complete the line.
-    fn find_two_in_line(&self, value: u8) -> Option<Position> {
-        // Check columns
-        for x in 0..3 {
-            let (val_num, empty_num, empty_pos) =
-                self.check_line(value, [Position(x, 0), Position(x, 1), Position(x, 2)]);
-            if val_num == 2 && empty_num == 1 {
-                return Some(empty_pos)
-            }
-        }
-        // Check rows
-        for y in 0..3 {
-            let (val_num, empty_num, empty_pos) =
-                self.check_line(value, [Position(0, y), Position(1, y), Position(2, y)]);
-            if val_num == 2 && empty_num == 1 {
-                return Some(empty_pos)
-            }
-        }
-        // Check diagonals
-        let (val_num, empty_num, empty_pos) =
-            self.check_line(value, [Position(0, 0), Position(1, 1), Position(2, 2)]);
-        if val_num == 2 && empty_num == 1 {
-            return Some(empty_pos)
-        }
-        let (val_num, empty_num, empty_pos) =
-            self.check_line(value, [Position(0, 2), Position(1, 1), Position(2, 0)]);
-        if val_num == 2 && empty_num == 1 {
-            return Some(empty_pos)
-        }
-        None
-    }
-
-    fn find_fork(&self) -> Option<Position> {
-        let (val_num, empty_num, _) =
-            self.check_line(CELL_X, [Position(0, 0), Position(0, 1), Position(0, 2)]);
-        let row0 = val_num == 1 && empty_num == 2;
-
-        let (val_num, empty_num, _) =
-            self.check_line(CELL_X, [Position(2, 0), Position(2, 1), Position(2, 2)]);
-        let row2 = val_num == 1 && empty_num == 2;
-
-        let (val_num, empty_num, _) =
-            self.check_line(CELL_X, [Position(0, 0), Position(1, 0), Position(2, 0)]);
-        let col0 = val_num == 1 && empty_num == 2;
-
-        let (val_num, empty_num, _) =
-            self.check_line(CELL_X, [Position(0, 2), Position(1, 2), Position(2, 2)]);
-        let col2 = val_num == 1 && empty_num == 2;
-
-        if row0 && col0 && self.get(0, 0) == CELL_EMPTY {
-            Some(Position(0, 0))
-        } else if row0 && col2 && self.get(0, 2) == CELL_EMPTY {
-            Some(Position(0, 2))
-        } else if row2 && col0 && self.get(2, 0) == CELL_EMPTY {
-            Some(Position(2, 0))
-        } else if row2 && col2 && self.get(2, 2) == CELL_EMPTY {
-            Some(Position(2, 2))
-        } else {
-            None
-        }
-    }
-
-    fn is_user_win(&self) -> bool {
-        // Check columns
-        for x in 0..3 {
-            let (val_num, ..) =
-                self.check_line(CELL_X, [Position(x, 0), Position(x, 1), Position(x, 2)]);
-            if val_num == 3 {
-                return true
-            }
-        }
-        // Check rows
-        for y in 0..3 {
-            let (val_num, ..) =
-                self.check_line(CELL_X, [Position(0, y), Position(1, y), Position(2, y)]);
-            if val_num == 3 {
-                return true
-            }
-        }
-        // Check diagonals
-        let (val_num, ..) =
-            self.check_line(CELL_X, [Position(0, 0), Position(1, 1), Position(2, 2)]);
-        if val_num == 3 {
-            return true
-        }
-        let (val_num, ..) =
-            self.check_line(CELL_X, [Position(0, 2), Position(1, 1), Position(2, 0)]);
-        if val_num == 3 {
-            return true
-        }
-        false
+        updates.push((CELL_O, new_pos));
     }
 
-    // Check line for values and return
-    // (number of values, number of empty cells, empty cell position).
-    // Empty cell position makes sense for 1 empty cell in line only.
-    fn check_line(&self, value: u8, indexes: [Position; 3]) -> (usize, usize, Position) {
-        let mut val_num = 0;
-        let mut empty_num = 0;
-        let mut empty_pos = Position(0, 0);
-        for &Position(x, y) in &indexes {
-            let v = self.get(x, y);
-            if v == value {
-                val_num += 1;
-            } else if v == CELL_EMPTY {
-                empty_num += 1;
-                empty_pos = Position(x, y);
-            }
-        }
-        (val_num, empty_num, empty_pos)
+    // Find empty cells which complete line of 3 `cell`s.
+    fn find_winning_cells(&mut self, cell: &Cell) -> Vec<Position> {
+        let empty: Vec<Position> = self.board.iter_cells()
+            .filter(|(_, c)| **c == Cell::Empty)
+            .map(|(pos, _)| pos)
+            .collect();
+        empty.into_iter().filter(|&pos| {
+            self.board.set_cell(pos, cell.clone());
+            let is_win = self.board.find_line(3, |c| c == cell).is_some();
+            self.board.set_cell(pos, Cell::Empty);
+            is_win
+        }).collect()
     }
 
-    fn is_empty_cells(&self) -> bool {
-        for x in 0..2 {
-            for y in 0..2 {
-                if self.get(x, y) == CELL_EMPTY {
-                    return true;
+    // Find corner where user can make fork, i.e. get two lines which can be completed.
+    fn find_fork(&mut self) -> Option<Position> {
+        for &pos in &CORNERS {
+            if self.is(pos.0, pos.1, &Cell::Empty) {
+                self.board.set_cell(pos, CELL_X);
+                let is_fork = self.find_winning_cells(&CELL_X).len() > 1;
+                self.board.set_cell(pos, Cell::Empty);
+                if is_fork {
+                    return Some(pos)
                 }
             }
         }
-        return false;
+        None
     }
 
-    fn get(&self, x: usize, y: usize) -> u8 {
-        self.board[y * 3 + x]
+    fn is_empty_cells(&self) -> bool {
+        self.board.iter_cells().any(|(_, c)| *c == Cell::Empty)
     }
 
-    fn set(&mut self, x: usize, y: usize, val: u8) {
-        self.board[y * 3 + x] = val;
+    fn is(&self, x: usize, y: usize, cell: &Cell) -> bool {
+        self.board.iter_cells().any(|(pos, c)| pos == Position(x, y) && c == cell)
     }
 }
 
+#[cfg(any(feature = "termion", feature = "crossterm"))]
 fn main() {
-    let stdin = io::stdin();
-    let stdin = stdin.lock();
-    let stdout = io::stdout();
-    let stdout = stdout.lock();
-
     let app = Rc::new(RefCell::new(App::new()));
-    let game = Rc::new(RefCell::new(Game::new(stdin, stdout, Rc::clone(&app))));
+    #[cfg(feature = "termion")]
+    let mut game = Game::new(io::stdin().lock(), io::stdout().lock(), Rc::clone(&app));
+    #[cfg(not(feature = "termion"))]
+    let mut game = Game::new_crossterm(Rc::clone(&app));
+    game.set_quit_key(Some(Key::Char('q')));
 
-    while !app.borrow().exit {
+    game.run(|| {
         app.borrow_mut().reset();
-        let cursor = Cursor::new(color::Rgb(0, 0, 200), START_POSITION, true, None);
+        let cursor = Cursor::new(Color::Rgb(0, 0, 200), START_POSITION, true, None);
         let mut board = Board::new(3, 3, 10, 5, true, Some(create_resources()));
-        board.init_from_vec(&vec![Cell::Empty, Cell::Empty, Cell::Empty,
+        board.init_from_vec(&[Cell::Empty, Cell::Empty, Cell::Empty,
                                   Cell::Empty, Cell::Empty, Cell::Empty,
                                   Cell::Empty, Cell::Empty, Cell::Empty,],
                             Some(cursor));
-        game.borrow_mut().init(board, None);
-        game.borrow_mut().start();
-    }
+        (board, Vec::new())
+    });
+}
+
+#[cfg(not(any(feature = "termion", feature = "crossterm")))]
+fn main() {
+    eprintln!("Enable `termion` or `crossterm` feature to run this example.");
 }
@@ -0,0 +1,112 @@
+//! ANSI escape sequences.
+//!
+//! Sequences are the same termion writes, so game output doesn't depend on `termion` feature.
+
+macro_rules! csi_sequence {
+    ($(#[$doc:meta])* $name:ident, $value:expr) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone)]
+        pub(crate) struct $name;
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                concat!("\x1b[", $value)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_ref())
+            }
+        }
+    };
+}
+
+pub(crate) mod color {
+    use std::fmt;
+
+    use crate::color::Color;
+
+    /// Foreground color.
+    #[derive(Copy, Clone)]
+    pub(crate) struct Fg(pub Color);
+
+    impl fmt::Display for Fg {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                Color::Rgb(r, g, b) => write!(f, "\x1b[38;2;{};{};{}m", r, g, b),
+                Color::Ansi(v) => write!(f, "\x1b[38;5;{}m", v),
+            }
+        }
+    }
+
+    /// Background color.
+    #[derive(Copy, Clone)]
+    pub(crate) struct Bg(pub Color);
+
+    impl fmt::Display for Bg {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                Color::Rgb(r, g, b) => write!(f, "\x1b[48;2;{};{};{}m", r, g, b),
+                Color::Ansi(v) => write!(f, "\x1b[48;5;{}m", v),
+            }
+        }
+    }
+}
+
+pub(crate) mod cursor {
+    use std::fmt;
+
+    /// Move cursor to (x, y) position. Position is one-based.
+    #[derive(Copy, Clone)]
+    pub(crate) struct Goto(pub u16, pub u16);
+
+    impl fmt::Display for Goto {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "\x1b[{};{}H", self.1, self.0)
+        }
+    }
+
+    csi_sequence!(
+        /// Hide cursor.
+        Hide, "?25l");
+    csi_sequence!(
+        /// Show cursor.
+        Show, "?25h");
+}
+
+pub(crate) mod style {
+    use std::fmt;
+
+    csi_sequence!(
+        /// Reset all text attributes and colors.
+        Reset, "m");
+    csi_sequence!(
+        /// Underlined text.
+        Underline, "4m");
+    csi_sequence!(
+        /// Undo underlined text.
+        NoUnderline, "24m");
+    csi_sequence!(
+        /// Inverted colors.
+        Invert, "7m");
+    csi_sequence!(
+        /// Undo inverted colors.
+        NoInvert, "27m");
+}
+
+pub(crate) mod clear {
+    use std::fmt;
+
+    csi_sequence!(
+        /// Clear entire screen.
+        All, "2J");
+}
+
+pub(crate) mod screen {
+    use std::fmt;
+
+    csi_sequence!(
+        /// Switch to the main screen buffer.
+        ToMainScreen, "?1049l");
+}
@@ -1,20 +1,33 @@
 //! Game board.
 
+use std::any::Any;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 use std::rc::Rc;
 
-use termion::cursor;
-use termion::event::Key;
+use unicode_width::UnicodeWidthChar;
 
-use crate::game::Position;
+use crate::ansi::{color, cursor, style};
+use crate::game::{Position, Rect};
 use crate::chars;
-use crate::cell::Cell;
+use crate::cell::{Cell, CellPadding};
+use crate::color::Color;
 use crate::cell_grid::CellGrid;
 use crate::cursor::{Cursor, KeyHandleResult};
+use crate::input::Key;
+use crate::dialog::{DialogPosition, DialogStyle, MessageDialog};
 use crate::str_utils;
+use crate::theme::Theme;
 
-const TEXT_ALIGN_CENTER: &'static str = "|^|";
-const TEXT_ALIGN_RIGHT: &'static str = "|>|";
+/// Default background color of the cell under mouse pointer.
+const DEFAULT_HOVER_COLOR: Color = Color::Rgb(60, 60, 60);
+/// Offsets of 4 neighbor cells sharing an edge: up, left, right, down.
+const EDGE_NEIGHBORS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Offsets of all 8 neighbor cells by rows.
+const ALL_NEIGHBORS: [(isize, isize); 8] =
+    [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
 
 /// Resources for cell content.
 ///
@@ -26,6 +39,171 @@ pub type ResourceTable = HashMap<u16, String>;
 /// Each array element is a tuple of cell content and cell position.
 pub type CellUpdates = Vec<(Cell, Position)>;
 
+/// Board cells state.
+///
+/// It contains content of all cells with their positions. It can be used to save and restore
+/// the game. With `serde` feature it implements `Serialize` and `Deserialize`.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardState {
+    cells: CellUpdates,
+}
+
+/// Method of finding board cells which need to be redrawn.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Updated cells are tracked, only they are compared with cells on the screen. This is
+    /// the default mode. It is faster when a few cells are changed between screen updates.
+    Dirty,
+    /// Updated cells are not tracked. All cells are compared with cells on the screen on every
+    /// screen update and only changed cells are written. It is faster when most of the cells
+    /// are updated between screen updates, for example, in cellular automata.
+    FullDiff,
+}
+
+/// Board initialization error.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BoardError {
+    /// Number of cells doesn't match board size.
+    InvalidCellCount {
+        /// Number of cells on the board.
+        expected: usize,
+        /// Number of provided cells.
+        actual: usize,
+    },
+    /// Board must have 1x1 cells to be initialized from string.
+    InvalidCellSize,
+    /// Character in initialization string doesn't take exactly one terminal column. Wide
+    /// characters (CJK, emoji) and zero-width characters shift the rest of the board.
+    InvalidCharWidth {
+        /// Character index in the string.
+        index: usize,
+        /// The character.
+        character: char,
+    },
+    /// Row of ASCII art has a different length than the first row.
+    InvalidRowLength {
+        /// Row index.
+        row: usize,
+        /// Length of the first row in characters.
+        expected: usize,
+        /// Length of this row in characters.
+        actual: usize,
+    },
+    /// ASCII art has no cells.
+    EmptyArt,
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::InvalidCellCount { expected, actual } =>
+                write!(f, "Invalid number of cells: expected {}, got {}.", expected, actual),
+            BoardError::InvalidCellSize =>
+                write!(f, "You can initialize cells from string for board with 1x1 cells only."),
+            BoardError::InvalidCharWidth { index, character } =>
+                write!(f, "Character '{}' at index {} is not one column wide.", character, index),
+            BoardError::InvalidRowLength { row, expected, actual } =>
+                write!(f, "Row {} has {} characters, expected {}.", row, actual, expected),
+            BoardError::EmptyArt =>
+                write!(f, "ASCII art has no cells."),
+        }
+    }
+}
+
+impl Error for BoardError {}
+
+/// Board edge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Top border line.
+    Top,
+    /// Bottom border line.
+    Bottom,
+    /// Left border line.
+    Left,
+    /// Right border line.
+    Right,
+}
+
+impl Edge {
+    // Index of the edge in edge styles array.
+    fn index(self) -> usize {
+        match self {
+            Edge::Top => 0,
+            Edge::Bottom => 1,
+            Edge::Left => 2,
+            Edge::Right => 3,
+        }
+    }
+}
+
+/// Board border line style.
+///
+/// Styles are ordered by weight: `Single` is the lightest, `Heavy` is the heaviest.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BorderStyle {
+    /// Single line: `─`.
+    Single = 0,
+    /// Double line: `═`.
+    Double,
+    /// Heavy line: `━`.
+    Heavy,
+}
+
+impl BorderStyle {
+    fn chars(self) -> &'static chars::BorderChars {
+        match self {
+            BorderStyle::Single => &chars::SINGLE_BORDER,
+            BorderStyle::Double => &chars::DOUBLE_BORDER,
+            BorderStyle::Heavy => &chars::HEAVY_BORDER,
+        }
+    }
+}
+
+/// Visible part of the board.
+#[derive(Copy, Clone)]
+struct Viewport {
+    /// Position of the top left visible cell.
+    offset: Position,
+    /// Number of visible columns.
+    width: usize,
+    /// Number of visible rows.
+    height: usize,
+}
+
+/// Non-modal message shown over the board.
+struct Toast {
+    dialog: MessageDialog,
+    /// Number of ticks before toast is hidden. 0 if toast is shown until it is hidden by user.
+    ticks_left: u32,
+    /// Toast is written to the screen.
+    drawn: bool,
+}
+
+/// Cells which background color is changed periodically.
+struct Pulse {
+    cells: Vec<Position>,
+    colors: [Color; 2],
+    /// Number of ticks between color changes.
+    period_ticks: u32,
+    /// Number of ticks passed since pulse start.
+    ticks_passed: u32,
+    /// Number of ticks before pulse is stopped. 0 if pulse is stopped by user only.
+    ticks_left: u32,
+}
+
+/// Cell moving from one board cell to another.
+struct Slide {
+    cell: Cell,
+    from: Position,
+    to: Position,
+    ticks: u32,
+    ticks_passed: u32,
+    /// Screen position where the moving cell is drawn. `None` if it is not on the screen.
+    drawn_at: Option<(u16, u16)>,
+}
+
 /// Board structure.
 pub struct Board {
     /// Board top left position.
@@ -39,10 +217,31 @@ pub struct Board {
     cell_width: usize,
     cell_height: usize,
     cell_borders: bool,
+    cell_padding: CellPadding,
     grid: CellGrid,
     resources: Rc<Option<ResourceTable>>,
     cursor: Option<Cursor>,
-    message_lines: Option<Vec<String>>,
+    message: Option<MessageDialog>,
+    toast: Option<Toast>,
+    pulse: Option<Pulse>,
+    slides: Vec<Slide>,
+    /// Cycles of cell states for cell toggling.
+    toggles: Vec<(Position, Vec<Cell>)>,
+    /// User data attached to cells.
+    user_data: HashMap<Position, Box<dyn Any>>,
+    dialog_style: DialogStyle,
+    hover_color: Option<Color>,
+    /// Cell under mouse pointer.
+    hover: Option<Position>,
+    viewport: Option<Viewport>,
+    /// Background color of the board.
+    background: Option<Color>,
+    /// Color of the board borders.
+    border_color: Option<Color>,
+    /// Title in the top border with alignment prefix.
+    title: Option<String>,
+    /// Border styles indexed by `Edge`.
+    edge_styles: [BorderStyle; 4],
     /// Need to redraw all cells and borders (for example, after message dialog was closed).
     update_all: bool,
 }
@@ -64,10 +263,25 @@ impl Board {
     ///
     /// `resources` - resource table (optional)
     ///
+    /// # Panics
+    ///
+    /// Panics if resource table contains string which doesn't fill the whole cell. Width of
+    /// resource string in terminal columns must be equal to `cell_width * cell_height`. Wide
+    /// characters take 2 columns. Escape sequences and alignment prefix are not counted.
+    ///
+    /// ```should_panic
+    /// # use gameboard::{Board, ResourceTable};
+    /// let mut res = ResourceTable::new();
+    /// res.insert(0, String::from(" X X X X "));
+    /// // Resource has 9 characters, but cell has 2x4 size.
+    /// let board = Board::new(3, 3, 2, 4, false, Some(res));
+    /// ```
+    ///
     /// # Examples
     ///
     /// A board for 3x3 tic-tac-toe game. Cell has 10x5 size to look square in terminal.
     /// ```no_run
+    /// # use gameboard::{Board, ResourceTable};
     /// fn create_resources() -> ResourceTable {
     ///     let mut res = ResourceTable::new();
     ///     res.insert(0, String::from("    OOO      O   O    O     O    O   O      OOO   "));
@@ -78,14 +292,10 @@ impl Board {
     /// ```
     pub fn new(width: usize, height: usize, cell_width: usize, cell_height: usize,
                cell_borders: bool, resources: Option<ResourceTable>) -> Self {
-        let mut w_borders = 2;
-        let mut h_borders = 2;
-        if cell_borders {
-            w_borders += width - 1;
-            h_borders += height - 1;
+        let (w, h) = get_board_size(width, height, cell_width, cell_height, cell_borders);
+        if let Some(rt) = &resources {
+            validate_resources(rt, cell_width * cell_height);
         }
-        let w = width * cell_width + w_borders;
-        let h = height * cell_height + h_borders;
 
         let res_table = Rc::new(resources);
         let grid = CellGrid::new(width, height, cell_width, cell_height, Rc::clone(&res_table));
@@ -99,88 +309,1511 @@ impl Board {
             cell_width,
             cell_height,
             cell_borders,
+            cell_padding: CellPadding::default(),
             grid,
             resources: Rc::clone(&res_table),
             cursor: None,
-            message_lines: None,
+            message: None,
+            toast: None,
+            pulse: None,
+            slides: Vec::new(),
+            toggles: Vec::new(),
+            user_data: HashMap::new(),
+            dialog_style: DialogStyle::default(),
+            hover_color: Some(DEFAULT_HOVER_COLOR),
+            hover: None,
+            viewport: None,
+            background: None,
+            border_color: None,
+            title: None,
+            edge_styles: [BorderStyle::Double; 4],
             update_all: false,
         }
     }
 
-    /// Initializes board with cells and cursor (optional).
+    /// Initializes board with cells and cursor (optional).
+    ///
+    /// The cells will be filled from vector by rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` contain wrong number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use gameboard::{Board, Cell};
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// board.init_from_vec(&vec![Cell::Empty, Cell::Char('x'), Cell::Empty, Cell::Char('o')],
+    ///                     None);
+    /// ```
+    pub fn init_from_vec(&mut self, cells: &[Cell], cursor: Option<Cursor>) {
+        if let Err(e) = self.try_init_from_vec(cells, cursor) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Initializes board with cells and cursor (optional).
+    ///
+    /// The same as [`init_from_vec`](#method.init_from_vec), but returns error instead of
+    /// panicking. Board is not changed on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, BoardError, Cell};
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// let res = board.try_init_from_vec(&[Cell::Empty, Cell::Char('x')], None);
+    /// assert!(res == Err(BoardError::InvalidCellCount { expected: 4, actual: 2 }));
+    /// ```
+    pub fn try_init_from_vec(&mut self, cells: &[Cell], cursor: Option<Cursor>)
+            -> Result<(), BoardError> {
+        self.check_cell_count(cells.len())?;
+        self.grid.init_from_vec(cells);
+        self.add_cursor(cursor);
+        Ok(())
+    }
+
+    /// Initializes board with cells and cursor (optional).
+    ///
+    /// The cells will be filled from string by rows.
+    ///
+    /// This is an additional initialization method. It might be useful if board has 1x1 cells and
+    /// all cells contain 1 character without styling.
+    ///
+    /// # Implementation note
+    ///
+    /// We iterate string using `chars()` method, it iterates through Unicode code points. Do not
+    /// use Unicode symbols which consist of more than 1 code points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` contain wrong number of elements.
+    ///
+    /// Panics if cell size is not 1x1.
+    ///
+    /// Panics if any character doesn't take exactly one terminal column (for example, CJK
+    /// characters, emoji or combining marks).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use gameboard::Board;
+    /// let mut board = Board::new(4, 4, 1, 1, false, None);
+    /// board.init_from_str(&"x    o    x    o", None);
+    /// ```
+    /// The following code does the same.
+    /// ```no_run
+    /// # use gameboard::{Board, Cell};
+    /// let mut board = Board::new(4, 4, 1, 1, false, None);
+    /// board.init_from_vec(&vec![Cell::Char('x'), Cell::Empty, Cell::Empty, Cell::Empty,
+    ///                           Cell::Empty, Cell::Char('o'), Cell::Empty, Cell::Empty,
+    ///                           Cell::Empty, Cell::Empty, Cell::Char('x'), Cell::Empty,
+    ///                           Cell::Empty, Cell::Empty, Cell::Empty, Cell::Char('o')],
+    ///                     None);
+    /// ```
+    pub fn init_from_str(&mut self, cells: &str, cursor: Option<Cursor>) {
+        if let Err(e) = self.try_init_from_str(cells, cursor) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Initializes board with cells and cursor (optional).
+    ///
+    /// The same as [`init_from_str`](#method.init_from_str), but returns error instead of
+    /// panicking. Board is not changed on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, BoardError};
+    /// let mut board = Board::new(2, 2, 3, 1, false, None);
+    /// assert!(board.try_init_from_str("x  o", None) == Err(BoardError::InvalidCellSize));
+    ///
+    /// // Emoji takes 2 columns and would shift the rest of the row.
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// assert!(board.try_init_from_str("x  😀", None) ==
+    ///         Err(BoardError::InvalidCharWidth { index: 3, character: '😀' }));
+    /// ```
+    pub fn try_init_from_str(&mut self, cells: &str, cursor: Option<Cursor>)
+            -> Result<(), BoardError> {
+        self.check_cell_count(cells.chars().count())?;
+        if self.cell_width != 1 || self.cell_height != 1 {
+            return Err(BoardError::InvalidCellSize)
+        }
+        let wrong_char = cells.chars().enumerate().find(|(_, c)| c.width() != Some(1));
+        if let Some((index, character)) = wrong_char {
+            return Err(BoardError::InvalidCharWidth { index, character })
+        }
+        self.grid.init_from_str(cells);
+        self.add_cursor(cursor);
+        Ok(())
+    }
+
+    /// Creates board from ASCII art.
+    ///
+    /// Every line of `art` is a board row and every character is a 1x1 cell (see
+    /// [`init_from_str`]). Number of rows and columns is taken from the art. Board has no cell
+    /// borders and no cursor. Line breaks can be `\n` or `\r\n`, the last line break is
+    /// optional.
+    ///
+    /// [`init_from_str`]: #method.init_from_str
+    ///
+    /// # Panics
+    ///
+    /// Panics if lines have different length, art is empty or any character doesn't take
+    /// exactly one terminal column. Use [`try_from_ascii`] to get an error instead.
+    ///
+    /// [`try_from_ascii`]: #method.try_from_ascii
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let board = Board::from_ascii("\
+    /// +---+
+    /// |@ .|
+    /// +---+
+    /// ");
+    /// assert_eq!((board.columns(), board.rows()), (5, 3));
+    /// let (_, cell) = board.iter_cells().find(|(pos, _)| *pos == Position(1, 1)).unwrap();
+    /// assert_eq!(*cell, Cell::Char('@'));
+    /// ```
+    pub fn from_ascii(art: &str) -> Board {
+        match Board::try_from_ascii(art) {
+            Ok(board) => board,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates board from ASCII art.
+    ///
+    /// The same as [`from_ascii`](#method.from_ascii), but returns error instead of panicking.
+    /// Character index in `BoardError::InvalidCharWidth` is counted without line breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, BoardError};
+    /// assert!(Board::try_from_ascii("xo\nx\n").err() ==
+    ///         Some(BoardError::InvalidRowLength { row: 1, expected: 2, actual: 1 }));
+    /// assert!(Board::try_from_ascii("").err() == Some(BoardError::EmptyArt));
+    /// ```
+    pub fn try_from_ascii(art: &str) -> Result<Board, BoardError> {
+        let lines: Vec<&str> = art.lines().collect();
+        let columns = lines.first().map_or(0, |l| l.chars().count());
+        if columns == 0 {
+            return Err(BoardError::EmptyArt)
+        }
+        for (row, line) in lines.iter().enumerate() {
+            let actual = line.chars().count();
+            if actual != columns {
+                return Err(BoardError::InvalidRowLength { row, expected: columns, actual })
+            }
+        }
+        let mut board = Board::new(columns, lines.len(), 1, 1, false, None);
+        board.try_init_from_str(&lines.concat(), None)?;
+        Ok(board)
+    }
+
+    fn check_cell_count(&self, count: usize) -> Result<(), BoardError> {
+        let expected = self.rows * self.columns;
+        if count != expected {
+            return Err(BoardError::InvalidCellCount { expected, actual: count })
+        }
+        Ok(())
+    }
+
+    // Get cell content as it was set by user, without cursor and selection highlighting.
+    fn get_original_cell(&self, pos: Position) -> Cell {
+        match self.cursor.as_ref().and_then(|c| c.get_original_cell(pos)) {
+            Some(cell) => cell.clone(),
+            None => self.grid.cells()[pos.1 * self.columns + pos.0].clone(),
+        }
+    }
+
+    // Get cursor position. Return `None` if board has no cursor.
+    pub(crate) fn get_cursor_position(&self) -> Option<Position> {
+        self.cursor.as_ref().map(|c| c.get_position())
+    }
+
+    fn add_cursor(&mut self, cursor: Option<Cursor>) {
+        if let Some(mut cur) = cursor {
+            cur.init(self.rows, self.columns, &mut self.grid);
+            self.cursor = Some(cur);
+            self.scroll_to_cursor();
+        }
+    }
+
+    /// Returns number of rows (vertical number of cells).
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns number of columns (horizontal number of cells).
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns cell size in characters (width, height).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// let board = Board::new(3, 2, 10, 5, true, None);
+    /// assert_eq!((board.columns(), board.rows()), (3, 2));
+    /// assert_eq!(board.cell_size(), (10, 5));
+    ///
+    /// // All board positions.
+    /// let positions: Vec<Position> = (0..board.rows())
+    ///     .flat_map(|y| (0..board.columns()).map(move |x| Position(x, y)))
+    ///     .collect();
+    /// assert_eq!(positions.len(), 6);
+    /// ```
+    pub fn cell_size(&self) -> (usize, usize) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Returns screen position (column, row) of the cell top left corner.
+    ///
+    /// Positions start from 1 like in `termion::cursor::Goto`. Board position is set by
+    /// `Game::init`, it can be changed when terminal is resized. Returns `None` if position is
+    /// out of the board bounds or the cell isn't visible in the viewport (see
+    /// [`set_viewport`]).
+    ///
+    /// [`set_viewport`]: #method.set_viewport
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// let board = Board::new(3, 3, 2, 1, true, None);
+    /// // Board is placed at the top left corner of the screen until it is added to the game.
+    /// assert_eq!(board.cell_screen_pos(Position(0, 0)), Some((2, 2)));
+    /// assert_eq!(board.cell_screen_pos(Position(2, 1)), Some((8, 4)));
+    /// assert_eq!(board.cell_screen_pos(Position(3, 0)), None);
+    /// ```
+    pub fn cell_screen_pos(&self, pos: Position) -> Option<(u16, u16)> {
+        if pos.0 >= self.columns || pos.1 >= self.rows {
+            return None
+        }
+        let idx = pos.1 * self.columns + pos.0;
+        if !self.is_visible(idx) {
+            return None
+        }
+        Some(self.get_cell_top_left(idx))
+    }
+
+    /// Returns iterator over all cells with their positions.
+    ///
+    /// Cells are iterated by rows. Cell content is returned as it was set by user, cursor and
+    /// selection highlighting and background colors are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// board.init_from_str("x  x", None);
+    /// let crosses: Vec<Position> = board.iter_cells()
+    ///     .filter(|(_, cell)| **cell == Cell::Char('x'))
+    ///     .map(|(pos, _)| pos)
+    ///     .collect();
+    /// assert!(crosses == vec![Position(0, 0), Position(1, 1)]);
+    /// ```
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Position, &Cell)> {
+        let cursor = self.cursor.as_ref();
+        self.grid.cells().iter().enumerate().map(move |(i, cell)| {
+            let pos = self.grid.get_position(i);
+            // Cell under cursor or selected cell contains highlighting, return original one.
+            match cursor.and_then(|c| c.get_original_cell(pos)) {
+                Some(original) => (pos, original),
+                None => (pos, cell)
+            }
+        })
+    }
+
+    /// Returns board content as a string, rows are separated by newlines.
+    ///
+    /// This is the inverse of [`init_from_str`] for boards with char cells. `Cell::Char` is
+    /// written as is, `Cell::Empty` is written as space and other cells are written as `?`.
+    /// Cell content is taken as it was set by user, cursor highlighting is not included. It can
+    /// be used for debugging, logging and saving simple games.
+    ///
+    /// [`init_from_str`]: #method.init_from_str
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// board.init_from_str("x o o  xx", None);
+    /// assert_eq!(board.content_as_string(), "x o\n o \n xx");
+    /// // Remove newlines to get the string back.
+    /// assert_eq!(board.content_as_string().replace('\n', ""), "x o o  xx");
+    ///
+    /// board.set_cell(Position(2, 2), Cell::text("ab"));
+    /// assert_eq!(board.content_as_string(), "x o\n o \n x?");
+    /// ```
+    pub fn content_as_string(&self) -> String {
+        let mut res = String::with_capacity((self.columns + 1) * self.rows);
+        for (Position(x, y), cell) in self.iter_cells() {
+            if x == 0 && y > 0 {
+                res.push('\n');
+            }
+            res.push(match cell {
+                Cell::Char(c) => *c,
+                Cell::Empty => ' ',
+                _ => '?',
+            });
+        }
+        res
+    }
+
+    /// Returns iterator over neighbor cell positions.
+    ///
+    /// If `diagonal` is `false`, 4 cells sharing an edge with `pos` cell are returned (up, left,
+    /// right, down). Otherwise, diagonal cells are returned as well, 8 cells by rows. Cells
+    /// outside the board are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// let board = Board::new(3, 3, 1, 1, false, None);
+    /// let neighbors = |x, y, diagonal| -> Vec<Position> {
+    ///     board.neighbors(Position(x, y), diagonal).collect()
+    /// };
+    ///
+    /// // Corner
+    /// assert!(neighbors(0, 0, false) == vec![Position(1, 0), Position(0, 1)]);
+    /// assert!(neighbors(2, 2, true) == vec![Position(1, 1), Position(2, 1), Position(1, 2)]);
+    /// // Edge
+    /// assert!(neighbors(1, 0, false) == vec![Position(0, 0), Position(2, 0), Position(1, 1)]);
+    /// assert!(neighbors(0, 1, true) == vec![Position(0, 0), Position(1, 0), Position(1, 1),
+    ///                                       Position(0, 2), Position(1, 2)]);
+    /// // Center
+    /// assert!(neighbors(1, 1, false) == vec![Position(1, 0), Position(0, 1), Position(2, 1),
+    ///                                        Position(1, 2)]);
+    /// assert_eq!(neighbors(1, 1, true).len(), 8);
+    /// ```
+    pub fn neighbors(&self, pos: Position, diagonal: bool) -> impl Iterator<Item = Position> {
+        let (columns, rows) = (self.columns, self.rows);
+        let offsets: &'static [(isize, isize)] =
+            if diagonal { &ALL_NEIGHBORS } else { &EDGE_NEIGHBORS };
+        offsets.iter()
+            .filter_map(move |&(dx, dy)| pos.offset(dx, dy))
+            .filter(move |p| p.0 < columns && p.1 < rows)
+    }
+
+    /// Updates content of a single cell.
+    ///
+    /// If [`Cursor`] is placed at this cell, it is highlighted again.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// board.set_cell(Position(1, 1), Cell::Char('x'));
+    /// let (_, cell) = board.iter_cells().find(|(pos, _)| *pos == Position(1, 1)).unwrap();
+    /// assert_eq!(*cell, Cell::Char('x'));
+    /// ```
+    pub fn set_cell(&mut self, position: Position, cell: Cell) {
+        if position.0 >= self.columns || position.1 >= self.rows {
+            panic!("Cell position is out of the board bounds.");
+        }
+        self.update_cells(vec![(cell, position)]);
+    }
+
+    /// Exchanges content of two cells.
+    ///
+    /// Both cells are redrawn. If cursor is at one of the cells, it stays at the same position
+    /// and highlights the new content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Cursor, Position};
+    /// use termion::{color, cursor::Goto, style};
+    ///
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.init_from_vec(&[Cell::Char('a'), Cell::Empty], Some(cursor));
+    /// board.render();
+    ///
+    /// board.swap_cells(Position(0, 0), Position(1, 0));
+    /// let cells: Vec<Cell> = board.iter_cells().map(|(_, cell)| cell.clone()).collect();
+    /// assert_eq!(cells, vec![Cell::Empty, Cell::Char('a')]);
+    /// // Cursor stays at the first cell, so it is highlighted.
+    /// assert_eq!(board.render_updates().unwrap(),
+    ///            format!("{}{} {}a", Goto(2, 2), color::Bg(color::Rgb(0, 0, 200)), style::Reset));
+    /// ```
+    pub fn swap_cells(&mut self, a: Position, b: Position) {
+        if a.0 >= self.columns || a.1 >= self.rows || b.0 >= self.columns || b.1 >= self.rows {
+            panic!("Cell position is out of the board bounds.");
+        }
+        let cell_a = self.get_original_cell(a);
+        let cell_b = self.get_original_cell(b);
+        self.update_cells(vec![(cell_b, a), (cell_a, b)]);
+    }
+
+    /// Sets cycle of states for the cell toggling.
+    ///
+    /// [`toggle_cell`] changes cell content to the next state in `states`, after the last state
+    /// the first one follows. This can be used to flag cells in minesweeper or to mark cells in
+    /// puzzles. Cell content isn't changed by this method. Empty `states` removes the cycle.
+    ///
+    /// [`toggle_cell`]: #method.toggle_cell
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.init_from_str("  ", None);
+    /// let states = [Cell::Char(' '), Cell::Char('F'), Cell::Char('?')];
+    /// board.set_toggle_states(Position(0, 0), &states);
+    ///
+    /// assert_eq!(board.toggle_cell(Position(0, 0)), Some(1));
+    /// assert_eq!(board.toggle_cell(Position(0, 0)), Some(2));
+    /// assert_eq!(board.content_as_string(), "? ");
+    /// assert_eq!(board.toggle_cell(Position(0, 0)), Some(0));
+    /// // Cell without states isn't toggled.
+    /// assert_eq!(board.toggle_cell(Position(1, 0)), None);
+    /// ```
+    pub fn set_toggle_states(&mut self, position: Position, states: &[Cell]) {
+        if position.0 >= self.columns || position.1 >= self.rows {
+            panic!("Cell position is out of the board bounds.");
+        }
+        self.toggles.retain(|(pos, _)| *pos != position);
+        if !states.is_empty() {
+            self.toggles.push((position, states.to_vec()));
+        }
+    }
+
+    /// Changes cell content to the next state in the cycle set by [`set_toggle_states`].
+    ///
+    /// The next state is found by the current cell content, so the cell can be changed by
+    /// other methods as well. If cell content isn't in the cycle, the first state is set.
+    /// Returns index of the new state or `None` if cell has no states. Nothing is changed in
+    /// this case.
+    ///
+    /// [`set_toggle_states`]: #method.set_toggle_states
+    ///
+    /// # Panics
+    ///
+    /// Panics if message dialog is open.
+    pub fn toggle_cell(&mut self, position: Position) -> Option<usize> {
+        let (_, states) = self.toggles.iter().find(|(pos, _)| *pos == position)?;
+        let current = self.get_original_cell(position);
+        let index = match states.iter().position(|s| *s == current) {
+            Some(i) => (i + 1) % states.len(),
+            None => 0
+        };
+        let cell = states[index].clone();
+        self.update_cells(vec![(cell, position)]);
+        Some(index)
+    }
+
+    /// Attaches user data to the cell.
+    ///
+    /// Board can keep game state of the cells (mines, piece owners etc.) next to their content,
+    /// so there is no need to maintain a separate array indexed by position. Data of any type can
+    /// be stored, previous data of the cell is replaced. Board isn't generic over the data type,
+    /// so the type is checked when data is read: [`get_user_data`] returns `None` if the type
+    /// doesn't match. Data doesn't affect cell content and is kept until it is removed or the
+    /// cell is removed by [`resize`].
+    ///
+    /// [`get_user_data`]: #method.get_user_data
+    /// [`resize`]: #method.resize
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// board.set_user_data(Position(1, 1), 5u8);
+    /// *board.get_user_data_mut::<u8>(Position(1, 1)).unwrap() += 1;
+    ///
+    /// assert_eq!(board.get_user_data::<u8>(Position(1, 1)), Some(&6));
+    /// assert_eq!(board.get_user_data::<u32>(Position(1, 1)), None);
+    /// assert_eq!(board.get_user_data::<u8>(Position(0, 0)), None);
+    /// ```
+    pub fn set_user_data<T: Any>(&mut self, position: Position, data: T) {
+        if position.0 >= self.columns || position.1 >= self.rows {
+            panic!("Cell position is out of the board bounds.");
+        }
+        self.user_data.insert(position, Box::new(data));
+    }
+
+    /// Returns user data of the cell set by [`set_user_data`].
+    ///
+    /// Returns `None` if cell has no data or data has another type.
+    ///
+    /// [`set_user_data`]: #method.set_user_data
+    pub fn get_user_data<T: Any>(&self, position: Position) -> Option<&T> {
+        self.user_data.get(&position).and_then(|data| data.downcast_ref())
+    }
+
+    /// Returns mutable user data of the cell. See [`get_user_data`].
+    ///
+    /// [`get_user_data`]: #method.get_user_data
+    pub fn get_user_data_mut<T: Any>(&mut self, position: Position) -> Option<&mut T> {
+        self.user_data.get_mut(&position).and_then(|data| data.downcast_mut())
+    }
+
+    /// Removes user data of the cell and returns it.
+    pub fn remove_user_data(&mut self, position: Position) -> Option<Box<dyn Any>> {
+        self.user_data.remove(&position)
+    }
+
+    /// Fills rectangular region of cells with the same content.
+    ///
+    /// `top_left` is a position of the region top left cell, `width` and `height` are region
+    /// size in cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if region is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(8, 8, 1, 1, false, None);
+    /// // Fill 2x2 block in the board center.
+    /// board.fill_region(Position(3, 3), 2, 2, Cell::Char('#'));
+    /// ```
+    pub fn fill_region(&mut self, top_left: Position, width: usize, height: usize, cell: Cell) {
+        let Position(x, y) = top_left;
+        if x + width > self.columns || y + height > self.rows {
+            panic!("Region is out of the board bounds.");
+        }
+        let mut updates = CellUpdates::with_capacity(width * height);
+        for row in y..y + height {
+            for column in x..x + width {
+                updates.push((cell.clone(), Position(column, row)));
+            }
+        }
+        self.update_cells(updates);
+    }
+
+    /// Fills the whole row with the same content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if row is out of the board bounds or message dialog is open.
+    pub fn fill_row(&mut self, row: usize, cell: Cell) {
+        self.fill_region(Position(0, row), self.columns, 1, cell);
+    }
+
+    /// Fills the whole column with the same content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if column is out of the board bounds or message dialog is open.
+    pub fn fill_column(&mut self, column: usize, cell: Cell) {
+        self.fill_region(Position(column, 0), 1, self.rows, cell);
+    }
+
+    /// Returns state of all board cells.
+    ///
+    /// Cell content is saved as it was set by user, cursor highlighting and background colors
+    /// are not included.
+    ///
+    /// # Examples
+    ///
+    /// Save board to JSON and restore it (requires `serde` feature and `serde_json` crate).
+    /// ```
+    /// # #[cfg(feature = "serde")] {
+    /// # use gameboard::{Board, BoardState};
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// board.init_from_str("x o o x x", None);
+    /// let json = serde_json::to_string(&board.snapshot()).unwrap();
+    ///
+    /// let mut restored = Board::new(3, 3, 1, 1, false, None);
+    /// let state: BoardState = serde_json::from_str(&json).unwrap();
+    /// restored.restore(&state);
+    /// assert_eq!(restored.render(), board.render());
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> BoardState {
+        BoardState {
+            cells: self.iter_cells().map(|(pos, cell)| (cell.clone(), pos)).collect(),
+        }
+    }
+
+    /// Restores cells from the state.
+    ///
+    /// Cells are updated the same way as with `Game::update_cells`. Board must have enough rows
+    /// and columns for all state positions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if state positions are out of the board bounds or message dialog is open.
+    pub fn restore(&mut self, state: &BoardState) {
+        if state.cells.iter().any(|(_, Position(x, y))| *x >= self.columns || *y >= self.rows) {
+            panic!("Board state doesn't match board size.");
+        }
+        self.update_cells(state.cells.clone());
+    }
+
+    /// Changes number of rows and columns.
+    ///
+    /// Content of the cells which remain on the board is kept, new cells are empty. Background
+    /// colors and disabled state of removed cells are dropped. [`Cursor`] is moved inside the
+    /// board if it is out of the new bounds. The whole board is redrawn on the next update.
+    ///
+    /// Board size on the screen changes, so game layout must be recomputed. If board belongs to
+    /// the game, use `Game::resize_board` which does this and redraws the screen.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Board;
+    /// let mut board = Board::new(8, 8, 1, 1, false, None);
+    /// board.resize(16, 16);
+    /// assert_eq!((board.rows(), board.columns()), (16, 16));
+    /// ```
+    pub fn resize(&mut self, rows: usize, columns: usize) {
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.remove(&mut self.grid);
+        }
+        self.grid.resize(columns, rows);
+        if let Some(ref mut pulse) = self.pulse {
+            pulse.cells.retain(|&Position(x, y)| x < columns && y < rows);
+        }
+        self.slides.retain(|s| {
+            s.from.0 < columns && s.from.1 < rows && s.to.0 < columns && s.to.1 < rows
+        });
+        self.toggles.retain(|&(Position(x, y), _)| x < columns && y < rows);
+        self.user_data.retain(|&Position(x, y), _| x < columns && y < rows);
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.init(rows, columns, &mut self.grid);
+        }
+        self.rows = rows;
+        self.columns = columns;
+        self.hover = None;
+        self.update_size();
+        // Keep viewport offset within the new board size.
+        self.scroll_viewport(0, 0);
+        self.scroll_to_cursor();
+        self.update_all = true;
+    }
+
+    /// Sets size of the visible part of the board in cells.
+    ///
+    /// If board is larger than the screen, only `(columns, rows)` cells starting from the
+    /// viewport offset are shown. Use [`scroll_viewport`] to change the offset. If board has
+    /// [`Cursor`], viewport is scrolled automatically to keep cursor visible. Use `None` to show
+    /// the whole board.
+    ///
+    /// Board size on the screen changes, so game layout must be recomputed. If board belongs to
+    /// the game, use `Game::set_viewport` which does this and redraws the screen.
+    ///
+    /// [`scroll_viewport`]: #method.scroll_viewport
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if viewport width or height is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut board = Board::new(200, 200, 1, 1, false, None);
+    /// board.init_from_vec(&vec![Cell::Empty; 200 * 200], None);
+    /// board.set_viewport(Some((2, 1)));
+    /// board.fill_row(0, Cell::Char('x'));
+    /// board.render();
+    ///
+    /// board.scroll_viewport(10, 0);
+    /// assert!(board.viewport_offset() == Position(10, 0));
+    /// assert_eq!(board.render(), format!("{}╔══╗{}║  ║{}╚══╝{}xx",
+    ///                                    Goto(1, 1), Goto(1, 2), Goto(1, 3), Goto(2, 2)));
+    /// ```
+    pub fn set_viewport(&mut self, size: Option<(usize, usize)>) {
+        self.viewport = size.map(|(columns, rows)| {
+            if columns == 0 || rows == 0 {
+                panic!("Viewport can't be empty.");
+            }
+            Viewport { offset: self.viewport_offset(), width: columns, height: rows }
+        });
+        self.update_size();
+        // Keep offset within the board.
+        self.scroll_viewport(0, 0);
+        self.scroll_to_cursor();
+        self.update_all = true;
+    }
+
+    /// Scrolls viewport by `dx` columns and `dy` rows.
+    ///
+    /// Viewport doesn't go beyond the board edges. Does nothing if viewport is not set.
+    pub fn scroll_viewport(&mut self, dx: isize, dy: isize) {
+        let (max_x, max_y) = match self.viewport {
+            Some(v) => (self.columns.saturating_sub(v.width), self.rows.saturating_sub(v.height)),
+            None => return
+        };
+        let Position(x, y) = self.viewport_offset();
+        let new_offset = Position((x as isize + dx).max(0).min(max_x as isize) as usize,
+                                  (y as isize + dy).max(0).min(max_y as isize) as usize);
+        self.set_viewport_offset(new_offset);
+    }
+
+    /// Returns position of the top left visible cell.
+    ///
+    /// It is `Position(0, 0)` if viewport is not set.
+    pub fn viewport_offset(&self) -> Position {
+        self.viewport.map_or(Position(0, 0), |v| v.offset)
+    }
+
+    /// Enables or disables the cell.
+    ///
+    /// Disabled cell is drawn as a solid block and its content isn't displayed. [`Cursor`] skips
+    /// disabled cells and can't be placed at them. If cursor start position is disabled, cursor
+    /// is placed at the nearest enabled cell. If cell with cursor is disabled, cursor is moved to
+    /// the nearest enabled cell, `InputListener::cursor_moved` is not called in this case.
+    /// Disabled cells can't be clicked or highlighted with mouse.
+    ///
+    /// This can be used for non-rectangular playfields.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// // Cross-shaped playfield.
+    /// for &pos in &[Position(0, 0), Position(2, 0), Position(0, 2), Position(2, 2)] {
+    ///     board.set_disabled(pos, true);
+    /// }
+    /// assert!(board.is_disabled(Position(0, 0)));
+    /// ```
+    pub fn set_disabled(&mut self, position: Position, disabled: bool) {
+        self.grid.set_disabled(position, disabled);
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.leave_disabled(&mut self.grid);
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Returns `true` if cell is disabled.
+    pub fn is_disabled(&self, position: Position) -> bool {
+        self.grid.is_disabled(position)
+    }
+
+    /// Sets cell background color.
+    ///
+    /// Background color is kept when cell content is updated. Use `None` to remove it. If
+    /// [`Cursor`] is placed at this cell or mouse pointer is over it, their colors are shown
+    /// instead. Background colors set inside cell content take precedence as well.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// use gameboard::Color;
+    ///
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// board.set_cell_background(Position(1, 1), Some(Color::Rgb(0, 128, 0)));
+    /// ```
+    pub fn set_cell_background(&mut self, position: Position, color: Option<Color>) {
+        self.grid.set_background(position, color);
+    }
+
+    /// Highlights cells with background color.
+    ///
+    /// This can be used to show a line of cells, for example, winning row in tic-tac-toe. All
+    /// cells are redrawn on the next screen update. Highlight color is shown instead of cell
+    /// background color, which is restored when highlighting is removed. If [`Cursor`] is placed
+    /// at highlighted cell or mouse pointer is over it, their colors are shown instead.
+    /// Highlighting is kept until `clear_highlight` is called.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cursor, Position};
+    /// use termion::color;
+    ///
+    /// let cursor_bg = color::Bg(color::Rgb(0, 0, 200)).to_string();
+    /// let highlight_bg = color::Bg(color::Rgb(0, 128, 0)).to_string();
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(3, 1, 1, 1, false, None);
+    /// board.init_from_str("xxx", Some(cursor));
+    /// board.highlight_line(&[Position(0, 0), Position(1, 0), Position(2, 0)],
+    ///                      color::Rgb(0, 128, 0));
+    /// assert!(board.render().contains(&highlight_bg));
+    ///
+    /// board.clear_highlight();
+    /// let update = board.render_updates().unwrap();
+    /// assert!(!update.contains(&highlight_bg));
+    /// // Cursor is still shown.
+    /// assert!(update.contains(&cursor_bg));
+    /// ```
+    pub fn highlight_line<C: Into<Color>>(&mut self, cells: &[Position], color: C) {
+        let color = color.into();
+        for &pos in cells {
+            self.grid.set_highlight(pos, color);
+        }
+    }
+
+    /// Finds `len` consecutive cells in line which satisfy the predicate.
+    ///
+    /// Rows are checked first, then columns, diagonals from top left to bottom right and
+    /// diagonals from top right to bottom left. Positions of the first found line are returned
+    /// in order from its start, so the result can be passed to [`highlight_line`]. If a longer
+    /// line satisfies the predicate, its first `len` cells are returned. Returns `None` if there
+    /// is no such line. Cursor and highlighting don't affect cell content passed to the
+    /// predicate.
+    ///
+    /// [`highlight_line`]: #method.highlight_line
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// fn find(board: &str, len: usize) -> Option<Vec<Position>> {
+    ///     let rows: Vec<&str> = board.lines().collect();
+    ///     let mut b = Board::new(rows[0].len(), rows.len(), 1, 1, false, None);
+    ///     b.init_from_str(&rows.concat(), None);
+    ///     b.find_line(len, |c| *c == Cell::Char('x'))
+    /// }
+    ///
+    /// // Row.
+    /// assert!(find("....\n.xxx\n....", 3) == Some(vec![Position(1, 1), Position(2, 1),
+    ///                                                 Position(3, 1)]));
+    /// // Column.
+    /// assert!(find("..x.\n..x.\n....", 2) == Some(vec![Position(2, 0), Position(2, 1)]));
+    /// // Diagonal from top left to bottom right.
+    /// assert!(find("x...\n.x..\n..x.", 3) == Some(vec![Position(0, 0), Position(1, 1),
+    ///                                                 Position(2, 2)]));
+    /// // Diagonal from top right to bottom left.
+    /// assert!(find("...x\n..x.\n.x..", 3) == Some(vec![Position(3, 0), Position(2, 1),
+    ///                                                 Position(1, 2)]));
+    /// // Longer line, its start is returned.
+    /// assert!(find("xxxx", 2) == Some(vec![Position(0, 0), Position(1, 0)]));
+    /// // Lines are not continued over the board edges.
+    /// assert!(find("..xx\nx...", 3).is_none());
+    /// assert!(find("x..\n.x.\n.o.\n...", 3).is_none());
+    /// assert!(find("xx.x\n....", 3).is_none());
+    /// ```
+    pub fn find_line<F: Fn(&Cell) -> bool>(&self, len: usize, predicate: F)
+            -> Option<Vec<Position>> {
+        if len == 0 {
+            panic!("Line length must be greater than 0.");
+        }
+        let matched: Vec<bool> = self.iter_cells().map(|(_, cell)| predicate(cell)).collect();
+        let (columns, rows) = (self.columns as isize, self.rows as isize);
+        let directions = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+        for &(dx, dy) in &directions {
+            for y in 0..rows {
+                for x in 0..columns {
+                    let line: Vec<Position> = (0..len as isize)
+                        .map(|i| (x + dx * i, y + dy * i))
+                        .take_while(|&(cx, cy)| cx >= 0 && cx < columns && cy < rows &&
+                                    matched[(cy * columns + cx) as usize])
+                        .map(|(cx, cy)| Position(cx as usize, cy as usize))
+                        .collect();
+                    if line.len() == len {
+                        return Some(line)
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes highlighting added by `highlight_line` from all cells.
+    ///
+    /// Pulse started by `pulse_cells` is stopped as well.
+    pub fn clear_highlight(&mut self) {
+        self.pulse = None;
+        self.grid.clear_highlights();
+    }
+
+    /// Starts changing background color of cells between two colors.
+    ///
+    /// Cells are highlighted with `color_a` first. Color is changed every `period_ticks` ticks.
+    /// Cells are switched to `color_b` and back `count` times, then highlighting is removed and
+    /// cells are shown as before pulse. Use `count` 0 to pulse until `clear_highlight` is called.
+    ///
+    /// Pulse is advanced by game ticks, so it works only if game was started with
+    /// `Game::start_with_tick`. Only one pulse can run at a time, the previous one is stopped.
+    /// Pulse uses the same highlighting as `highlight_line` and replaces it for these cells.
+    /// Pulse is stopped when game is stopped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period_ticks` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Position};
+    /// use termion::color;
+    ///
+    /// let mut board = Board::new(3, 1, 1, 1, false, None);
+    /// board.init_from_str("xxx", None);
+    /// board.pulse_cells(&[Position(0, 0), Position(1, 0)], color::Rgb(255, 255, 0),
+    ///                   color::Rgb(0, 0, 0), 2, 3);
+    /// assert!(board.render().contains(&color::Bg(color::Rgb(255, 255, 0)).to_string()));
+    /// ```
+    pub fn pulse_cells<C: Into<Color>>(&mut self, cells: &[Position], color_a: C, color_b: C,
+                                       period_ticks: u32, count: u32) {
+        if period_ticks == 0 {
+            panic!("Pulse period must be greater than 0.");
+        }
+        let (color_a, color_b) = (color_a.into(), color_b.into());
+        self.stop_pulse();
+        for &pos in cells {
+            self.grid.set_highlight(pos, color_a);
+        }
+        self.pulse = Some(Pulse {
+            cells: cells.to_vec(),
+            colors: [color_a, color_b],
+            period_ticks,
+            ticks_passed: 0,
+            ticks_left: 2 * period_ticks * count,
+        });
+    }
+
+    /// Moves cell content from one cell to another with animation.
+    ///
+    /// Cell at `from` position is emptied, then `cell` is drawn moving in a straight line to
+    /// `to` position during `ticks` ticks. It is drawn between cells and over other cells,
+    /// which are restored when it moves away. When animation is finished, `to` cell content
+    /// is set to `cell`. If `ticks` is 0, cell is moved at once.
+    ///
+    /// Animation is advanced by game ticks, so it works only if game was started with
+    /// `Game::start_with_tick`. Several cells can move at the same time. Moving cell is drawn
+    /// only if both cells are visible. Animation is finished at once when game is stopped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// let mut board = Board::new(4, 1, 3, 1, false, None);
+    /// board.init_from_vec(&[Cell::Char('2'), Cell::Empty, Cell::Empty, Cell::Empty], None);
+    /// // Slide the tile to the right edge in 6 ticks.
+    /// board.animate_move(Position(0, 0), Position(3, 0), Cell::Char('2'), 6);
+    /// // Cell is set when animation is finished.
+    /// assert!(board.iter_cells().all(|(_, cell)| *cell == Cell::Empty));
+    /// ```
+    pub fn animate_move(&mut self, from: Position, to: Position, cell: Cell, ticks: u32) {
+        if to.0 >= self.columns || to.1 >= self.rows {
+            panic!("Cell position is out of the board bounds.");
+        }
+        self.set_cell(from, Cell::Empty);
+        if ticks == 0 {
+            self.update_cells(vec![(cell, to)]);
+            return
+        }
+        self.slides.push(Slide { cell, from, to, ticks, ticks_passed: 0, drawn_at: None });
+    }
+
+    /// Sets cursor background color.
+    ///
+    /// Cell where cursor is placed is highlighted with the new color. Does nothing if board
+    /// has no cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cursor, Position};
+    /// use termion::color;
+    ///
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// board.init_from_str("x  o", Some(cursor));
+    /// board.render();
+    ///
+    /// board.set_cursor_color(color::Rgb(200, 0, 0));
+    /// let update = board.render();
+    /// assert!(update.contains(&color::Bg(color::Rgb(200, 0, 0)).to_string()));
+    /// assert!(!update.contains(&color::Bg(color::Rgb(0, 0, 200)).to_string()));
+    /// ```
+    pub fn set_cursor_color<C: Into<Color>>(&mut self, color: C) {
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.set_background(color);
+            cursor.refresh(&mut self.grid);
+        }
+    }
+
+    /// Shows or hides cursor.
+    ///
+    /// Hidden cursor doesn't highlight the cell, cell is shown with its original content. Cursor
+    /// position isn't changed, cursor is shown at the same cell again. Hidden cursor still
+    /// handles keys and can be moved. Does nothing if board has no cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cursor, Position};
+    /// use termion::{color, cursor::Goto, style};
+    ///
+    /// let bg = color::Bg(color::Rgb(0, 0, 200)).to_string();
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.init_from_str("xo", Some(cursor));
+    /// board.render();
+    ///
+    /// board.set_cursor_visible(false);
+    /// assert_eq!(board.render_updates().unwrap(), format!("{}x", Goto(2, 2)));
+    /// board.set_cursor_visible(true);
+    /// assert_eq!(board.render_updates().unwrap(),
+    ///            format!("{}{}x{}", Goto(2, 2), bg, style::Reset));
+    /// ```
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.set_visible(visible);
+            cursor.refresh(&mut self.grid);
+        }
+    }
+
+    /// Returns cells selected by cursor in selection order.
+    ///
+    /// The first cell is the selection anchor, the last one is the cursor cell. Returns empty
+    /// vector if nothing is selected or board has no cursor. See `Cursor::set_selection`.
+    pub fn selection(&self) -> Vec<Position> {
+        match self.cursor {
+            Some(ref cursor) => cursor.get_selection(),
+            None => Vec::new()
+        }
+    }
+
+    /// Clears cursor selection. Cursor position isn't changed.
+    pub fn clear_selection(&mut self) {
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.clear_selection(&mut self.grid);
+        }
+    }
+
+    /// Sets background color of the whole board.
+    ///
+    /// Background is shown behind borders and cells. Cell background colors, mouse hover
+    /// highlighting, [`Cursor`] and background colors set inside cell content take precedence.
+    /// Use `None` to use default terminal background.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell};
+    /// use gameboard::Color;
+    /// use termion::{color, cursor::Goto, style};
+    ///
+    /// let bg = color::Bg(Color::Rgb(0, 0, 64));
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.set_background(Some(Color::Rgb(0, 0, 64)));
+    /// board.init_from_vec(&[Cell::Empty, Cell::Empty], None);
+    /// assert_eq!(board.render(),
+    ///            format!("{}{bg}╔══╗{r}{}{bg}║  ║{r}{}{bg}╚══╝{r}{}{bg} {r}{bg} {r}",
+    ///                    Goto(1, 1), Goto(1, 2), Goto(1, 3), Goto(2, 2),
+    ///                    bg = bg, r = style::Reset));
+    /// ```
+    pub fn set_background(&mut self, color: Option<Color>) {
+        self.background = color;
+        self.grid.set_base_background(color);
+        self.update_all = true;
+    }
+
+    /// Sets color of the board borders and title.
+    ///
+    /// Use `None` to use default terminal foreground color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Board;
+    /// use gameboard::Color;
+    /// use termion::{color, cursor::Goto, style};
+    ///
+    /// let fg = color::Fg(color::AnsiValue(8));
+    /// let mut board = Board::new(1, 1, 1, 1, false, None);
+    /// board.set_border_color(Some(Color::Ansi(8)));
+    /// assert!(board.render().starts_with(&format!("{}{fg}╔═╗{r}{}{fg}║ ║{r}",
+    ///                                             Goto(1, 1), Goto(1, 2),
+    ///                                             fg = fg, r = style::Reset)));
+    /// ```
+    pub fn set_border_color(&mut self, color: Option<Color>) {
+        self.border_color = color;
+        self.update_all = true;
+    }
+
+    /// Applies colors of the theme to the board.
+    ///
+    /// It sets cursor color, background, border color, mouse hover color and dialog style. See
+    /// [`Theme`] for details.
+    ///
+    /// [`Theme`]: ../theme/struct.Theme.html
+    pub fn set_theme(&mut self, theme: &Theme) {
+        self.set_cursor_color(theme.cursor);
+        self.set_background(theme.background);
+        self.set_border_color(theme.border);
+        self.set_hover_color(theme.hover);
+        self.set_dialog_style(theme.dialog);
+    }
+
+    /// Sets background color of the cell under mouse pointer.
+    ///
+    /// Cell under mouse pointer is highlighted if mouse is enabled (see `Game::enable_mouse`).
+    /// Use `None` to disable highlighting. Cursor color has priority over this color.
+    pub fn set_hover_color(&mut self, color: Option<Color>) {
+        self.hover_color = color;
+        if color.is_none() {
+            self.grid.set_hover(None);
+        }
+    }
+
+    /// Sets render mode. See [`RenderMode`] for details.
+    ///
+    /// Both modes produce the same screen content, they differ in performance only. In both
+    /// modes cells which look the same as on the screen are not written. Adjacent cells of the
+    /// board with 1x1 cells and no cell borders are written together.
+    ///
+    /// [`RenderMode`]: enum.RenderMode.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position, RenderMode};
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut board = Board::new(3, 1, 1, 1, false, None);
+    /// board.set_render_mode(RenderMode::FullDiff);
+    /// board.init_from_str("...", None);
+    /// board.render();
+    ///
+    /// board.fill_row(0, Cell::Char('.'));
+    /// board.fill_region(Position(2, 0), 1, 1, Cell::Char('#'));
+    /// assert_eq!(board.render_updates(), Some(format!("{}#", Goto(4, 2))));
+    /// ```
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.grid.set_track_updates(mode == RenderMode::Dirty);
+    }
+
+    /// Returns `true` if message dialog is open.
+    ///
+    /// Cells can't be updated while message dialog is open. Toast is not a message dialog.
+    pub fn is_message_open(&self) -> bool {
+        self.message.is_some()
+    }
+
+    /// Shows non-modal message over the board.
+    ///
+    /// Unlike message dialog, toast doesn't block the game: keys are handled as usual and cells
+    /// can be updated while it is shown. Toast is drawn on top of the cells, but under the
+    /// message dialog. New toast replaces the previous one.
+    ///
+    /// If game was started with `Game::start_with_tick`, toast is hidden after `duration_ticks`
+    /// ticks. If `duration_ticks` is 0 or there are no ticks, toast is shown until
+    /// [`hide_toast`] is called.
+    ///
+    /// Lines are aligned the same way as in `Game::show_message`.
+    ///
+    /// [`hide_toast`]: #method.hide_toast
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, DialogPosition, Position};
+    /// let mut board = Board::new(20, 10, 1, 1, false, None);
+    /// board.render();
+    /// board.show_toast(&["|^|Checkmate in 2"], DialogPosition::Top, 10);
+    /// assert!(board.render_updates().unwrap().contains("Checkmate in 2"));
+    ///
+    /// // Board can be updated while toast is shown.
+    /// board.set_cell(Position(0, 9), Cell::Char('x'));
+    /// board.hide_toast();
+    /// ```
+    pub fn show_toast(&mut self, lines: &[&str], position: DialogPosition, duration_ticks: u32) {
+        let dialog = MessageDialog::new(lines, &[], position, self.dialog_style);
+        if self.toast.is_some() {
+            // Wipe out the previous toast.
+            self.update_all = true;
+        }
+        self.toast = Some(Toast { dialog, ticks_left: duration_ticks, drawn: false });
+    }
+
+    /// Hides toast shown by [`show_toast`].
+    ///
+    /// [`show_toast`]: #method.show_toast
+    pub fn hide_toast(&mut self) {
+        if self.toast.take().is_some() {
+            // Redraw cells under the toast.
+            self.update_all = true;
+        }
+    }
+
+    /// Sets title shown in the top border.
+    ///
+    /// Title is aligned within the top border the same way as cell content:
+    ///
+    /// * Titles are left-aligned by default
+    /// * Titles started with *|^|* are centered
+    /// * Titles started with *|>|* are right-aligned
+    ///
+    /// Too long title is truncated. Use empty string to remove title.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Board;
+    /// let mut board = Board::new(11, 1, 1, 1, false, None);
+    /// board.set_title("|^|Mines");
+    /// assert!(board.render().contains("╔═╡ Mines ╞═╗"));
+    /// ```
+    pub fn set_title(&mut self, title: &str) {
+        self.title = if title.is_empty() { None } else { Some(title.to_string()) };
+        self.update_all = true;
+    }
+
+    /// Enables or disables borders between cells.
     ///
-    /// The cells will be filled from vector by rows.
+    /// Cells are kept, the whole board is redrawn on the next update. Board size on the screen
+    /// changes, so game layout must be recomputed. If board belongs to the game, use
+    /// `Game::set_cell_borders` which does this and redraws the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Board;
+    /// let mut board = Board::new(3, 3, 1, 1, false, None);
+    /// assert!(board.render().contains("╔═══╗"));
+    /// board.set_cell_borders(true);
+    /// assert!(board.render().contains("╔═╤═╤═╗"));
+    /// ```
+    pub fn set_cell_borders(&mut self, enabled: bool) {
+        self.cell_borders = enabled;
+        self.update_size();
+        self.update_all = true;
+    }
+
+    /// Sets space between cell edges and cell content.
+    ///
+    /// Content of all cells is shifted by padding and written into the rest of the cell. Text
+    /// which doesn't fit is cut, so resource strings lose their last characters. Padding is
+    /// filled with spaces and gets cell background color. There is no padding by default.
     ///
     /// # Panics
     ///
-    /// Panics `cells` contain wrong number of elements.
+    /// Panics if padding is not less than cell size.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// let mut board = Board::new(2, 2, 1, 1, false, None));
-    /// board.init_from_vec(&vec![Cell::Empty, Cell::Char('x'), Cell::Empty, Cell::Char('o')],
-    ///                     None);
     /// ```
-    pub fn init_from_vec(&mut self, cells: &Vec<Cell>, cursor: Option<Cursor>) {
-        if cells.len() != self.rows * self.columns {
-            panic!("Invalid number of cells.");
+    /// # use gameboard::{Board, Cell, CellPadding};
+    /// use termion::{cursor::Goto, style};
+    ///
+    /// let mut board = Board::new(1, 1, 4, 2, false, None);
+    /// board.set_cell_padding(CellPadding { top: 1, left: 1 });
+    /// board.init_from_vec(&[Cell::Content(String::from("ab"))], None);
+    /// assert!(board.render().ends_with(
+    ///     &format!("{}    {} ab {}", Goto(2, 2), Goto(2, 3), style::Reset)));
+    /// ```
+    pub fn set_cell_padding(&mut self, padding: CellPadding) {
+        if padding.top >= self.cell_height || padding.left >= self.cell_width {
+            panic!("Cell padding must be less than cell size.");
         }
-        self.grid.init_from_vec(cells);
-        self.add_cursor(cursor);
+        self.cell_padding = padding;
+        self.update_all = true;
     }
 
-    /// Initializes board with cells and cursor (optional).
+    /// Sets board border style of the edge.
     ///
-    /// The cells will be filled from string by rows.
+    /// All edges have `BorderStyle::Double` style by default. If two edges with different
+    /// styles meet in the corner, the corner has the heavier style. Cell borders always have
+    /// single line style.
     ///
-    /// This is an additional initialization method. It might be useful if board has 1x1 cells and
-    /// all cells contain 1 character without styling.
+    /// # Examples
     ///
-    /// # Implementation note
+    /// Double top border as a title bar and single side and bottom borders.
     ///
-    /// We iterate string using `chars()` method, it iterates through Unicode code points. Do not
-    /// use Unicode symbols which consist of more than 1 code points.
+    /// ```
+    /// # use gameboard::{Board, BorderStyle, Edge};
+    /// use termion::cursor::Goto;
     ///
-    /// # Panics
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.set_edge_style(Edge::Left, BorderStyle::Single);
+    /// board.set_edge_style(Edge::Right, BorderStyle::Single);
+    /// board.set_edge_style(Edge::Bottom, BorderStyle::Single);
+    /// board.init_from_str("xo", None);
+    /// assert_eq!(board.render(), format!("{}╔══╗{}│  │{}└──┘{}xo",
+    ///                                    Goto(1, 1), Goto(1, 2), Goto(1, 3), Goto(2, 2)));
+    /// ```
+    pub fn set_edge_style(&mut self, edge: Edge, style: BorderStyle) {
+        self.edge_styles[edge.index()] = style;
+        self.update_all = true;
+    }
+
+    /// Requests full board redraw.
+    ///
+    /// All cells and borders will be redrawn on the next screen update. This can be useful when
+    /// terminal content was damaged by another program.
+    pub fn request_full_redraw(&mut self) {
+        self.update_all = true;
+    }
+
+    /// Renders the whole board.
     ///
-    /// Panics `cells` contain wrong number of elements.
+    /// Returns a string with borders, cells and message dialog (if it is open) as it would be
+    /// written to the terminal, including escape sequences. Board is rendered at its current
+    /// position, it is `(1, 1)` until game layout is done. This can be used to test board
+    /// output without a terminal.
     ///
-    /// Panics if cell size is not 1x1.
+    /// All pending updates are considered written after this call.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// let mut board = Board::new(4, 4, 1, 1, false, None));
-    /// board.init_from_str(&"x    o    x    o", None);
     /// ```
-    /// The following code does the same.
-    /// ```no_run
-    /// let mut board = Board::new(4, 4, 1, 1, false, None));
-    /// board.init_from_vec(&vec![Cell::Char('x'), Cell::Empty, Cell::Empty, Cell::Empty,
-    ///                           Cell::Empty, Cell::Char('o'), Cell::Empty, Cell::Empty,
-    ///                           Cell::Empty, Cell::Empty, Cell::Char('x'), Cell::Empty,
-    ///                           Cell::Empty, Cell::Empty, Cell::Empty, Cell::Char('o')],
-    ///                     None);
+    /// # use gameboard::Board;
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// board.init_from_str("xo  ", None);
+    /// assert_eq!(board.render(), format!("{}╔══╗{}║  ║{}║  ║{}╚══╝{}xo{}  ",
+    ///                                    Goto(1, 1), Goto(1, 2), Goto(1, 3), Goto(1, 4),
+    ///                                    Goto(2, 2), Goto(2, 3)));
     /// ```
-    pub fn init_from_str(&mut self, cells: &str, cursor: Option<Cursor>) {
-        if cells.chars().count() != self.rows * self.columns {
-            panic!("Invalid number of cells.");
+    pub fn render(&mut self) -> String {
+        self.request_full_redraw();
+        self.get_updates().unwrap_or_default()
+    }
+
+    /// Renders board changes since the last render.
+    ///
+    /// Returns a string with updated cells as it would be written to the terminal. Cells which
+    /// were updated, but look the same as before, are not included. Returns `None` if there is
+    /// nothing to update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut board = Board::new(2, 2, 1, 1, false, None);
+    /// board.init_from_str("xo  ", None);
+    /// board.render();
+    ///
+    /// board.fill_row(0, Cell::Char('x'));
+    /// assert_eq!(board.render_updates(), Some(format!("{}x", Goto(3, 2))));
+    /// ```
+    ///
+    /// Adjacent cells of the board with 1x1 cells and no cell borders are written together.
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Position};
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut board = Board::new(5, 3, 1, 1, false, None);
+    /// board.init_from_str("xxxxxxxxxxxxxxx", None);
+    /// board.render();
+    ///
+    /// board.fill_region(Position(1, 1), 3, 1, Cell::Empty);
+    /// let updates = board.render_updates().unwrap();
+    /// assert_eq!(updates, format!("{}   ", Goto(3, 3)));
+    /// assert_eq!(updates.matches("\x1b[").count(), 1);
+    /// ```
+    pub fn render_updates(&mut self) -> Option<String> {
+        self.get_updates()
+    }
+
+    // Change viewport offset. Visible cells are redrawn if offset is changed.
+    fn set_viewport_offset(&mut self, offset: Position) {
+        if let Some(ref mut v) = self.viewport {
+            if v.offset != offset {
+                v.offset = offset;
+                self.update_all = true;
+                // Another cell is under mouse pointer now.
+                self.hover = None;
+                self.grid.set_hover(None);
+            }
         }
-        if self.cell_width != 1 && self.cell_height != 1 {
-            panic!("You can initialize cells from string for board with 1x1 cells only.");
+    }
+
+    // Scroll viewport, so cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        let (v, pos) = match (self.viewport, self.cursor.as_ref()) {
+            (Some(v), Some(cursor)) => (v, cursor.get_position()),
+            _ => return
+        };
+        let Position(mut x, mut y) = v.offset;
+        if pos.0 < x {
+            x = pos.0;
+        } else if pos.0 >= x + v.width {
+            x = pos.0 + 1 - v.width;
         }
-        self.grid.init_from_str(cells);
-        self.add_cursor(cursor);
+        if pos.1 < y {
+            y = pos.1;
+        } else if pos.1 >= y + v.height {
+            y = pos.1 + 1 - v.height;
+        }
+        self.set_viewport_offset(Position(x, y));
     }
 
-    fn add_cursor(&mut self, cursor: Option<Cursor>) {
-        if let Some(mut cur) = cursor {
-            cur.init(self.rows, self.columns, &mut self.grid);
-            self.cursor = Some(cur);
+    // Get visible part of the board: position of the top left cell, number of columns and rows.
+    fn get_visible_area(&self) -> (Position, usize, usize) {
+        match self.viewport {
+            Some(v) => (v.offset, v.width.min(self.columns), v.height.min(self.rows)),
+            None => (Position(0, 0), self.columns, self.rows)
         }
     }
 
+    fn is_visible(&self, idx: usize) -> bool {
+        let (Position(x, y), columns, rows) = self.get_visible_area();
+        let (column, row) = (idx % self.columns, idx / self.columns);
+        column >= x && column < x + columns && row >= y && row < y + rows
+    }
+
+    // Update board size on the screen according to visible number of cells.
+    fn update_size(&mut self) {
+        let (_, columns, rows) = self.get_visible_area();
+        let (w, h) = get_board_size(columns, rows, self.cell_width, self.cell_height,
+                                    self.cell_borders);
+        self.width = w;
+        self.height = h;
+    }
+
     pub(crate) fn get_width(&self) -> usize {
         self.width
     }
@@ -193,15 +1826,35 @@ impl Board {
         self.position = pos;
     }
 
+    pub(crate) fn get_rect(&self) -> Rect {
+        let Position(x, y) = self.position;
+        Rect { x, y, width: self.width, height: self.height }
+    }
+
     pub(crate) fn get_border(&self) -> String {
-        let mut y = self.position.1 as u16;
         // Add chars to row width for Goto sequences
         let mut res =
             String::with_capacity((self.width + str_utils::GOTO_SEQUENCE_WIDTH) * self.height);
 
+        let title = self.get_title();
         for h in 0..self.height {
+            let y = (self.position.1 + h) as u16;
             res.push_str(&format!("{}", cursor::Goto(self.position.0 as u16, y)));
+            if let Some(bg) = self.background {
+                res.push_str(&color::Bg(bg).to_string());
+            }
+            if let Some(fg) = self.border_color {
+                res.push_str(&color::Fg(fg).to_string());
+            }
             for w in 0..self.width {
+                if let Some((start, ref text, len)) = title {
+                    if h == 0 && w >= start && w < start + len {
+                        if w == start {
+                            res.push_str(text);
+                        }
+                        continue;
+                    }
+                }
                 match self.get_border_char(w, h) {
                     Some(border_ch) => {
                         res.push(border_ch);
@@ -211,62 +1864,120 @@ impl Board {
                     }
                 };
             }
-            y += 1;
+            if self.background.is_some() || self.border_color.is_some() {
+                res.push_str(style::Reset.as_ref());
+            }
         }
         res
     }
 
     pub(crate) fn get_updates(&mut self) -> Option<String> {
         let msg_dlg = self.get_message_dialog();
-        if msg_dlg.is_some() {
+        if msg_dlg.is_some() && !self.update_all {
             return msg_dlg
         }
 
-        if !self.update_all && !self.grid.has_updates() {
+        let toast_pending = self.toast.as_ref().map_or(false, |t| !t.drawn);
+        let slide_positions: Vec<Option<(u16, u16)>> =
+            self.slides.iter().map(|s| self.get_slide_position(s)).collect();
+        let slides_pending = self.slides.iter().zip(&slide_positions)
+            .any(|(s, &pos)| s.drawn_at != pos);
+        if !self.update_all && !self.grid.has_updates() && !toast_pending && !slides_pending {
             return None
         }
 
         let mut res = String::with_capacity(self.width * self.height);
-        let update_all = self.update_all || self.grid.need_update_all();
         if self.update_all {
             // We need to redraw the whole board with borders to wipe out message dialog.
             res.push_str(&self.get_border());
+            for slide in &mut self.slides {
+                slide.drawn_at = None;
+            }
+        }
+        // Wipe out moving cells drawn at the previous animation step.
+        let trails: Vec<(u16, u16)> = self.slides.iter_mut().zip(&slide_positions)
+            .filter(|(s, &pos)| s.drawn_at != pos)
+            .filter_map(|(s, _)| s.drawn_at.take())
+            .collect();
+        for (x, y) in trails {
+            res.push_str(&self.restore_area(x, y));
         }
+        self.slides.retain(|s| s.ticks_passed < s.ticks);
+        let update_all = self.update_all || self.grid.need_update_all();
 
         if update_all && self.cell_width == 1 && self.cell_height == 1 && !self.cell_borders {
             // If we need to update all cells and board has 1x1 cells and no borders,
             // we can simplify the process.
-            for (i, cell) in self.grid.iter().enumerate() {
-                if i % self.columns == 0 {
+            let first_column = self.viewport_offset().0;
+            for (i, cell) in self.grid.iter().enumerate().filter(|&(i, _)| self.is_visible(i)) {
+                if i % self.columns == first_column {
                     let (x, y) = self.get_cell_top_left(i);
                     res.push_str(&format!("{}", cursor::Goto(x, y)));
                 }
                 cell.add_value_to_str(&mut res, Rc::clone(&self.resources));
             }
         } else if update_all {
-            for (i, cell) in self.grid.iter().enumerate() {
+            for (i, cell) in self.grid.iter().enumerate().filter(|&(i, _)| self.is_visible(i)) {
                 let (x, y) = self.get_cell_top_left(i);
                 res.push_str(
                     &cell.get_content(self.cell_width, self.cell_height, x, y,
-                                      Rc::clone(&self.resources))
+                                      self.cell_padding, Rc::clone(&self.resources))
                 );
             }
+        } else if self.cell_width == 1 && self.cell_height == 1 && !self.cell_borders {
+            // Adjacent cells in the row are written after single Goto sequence.
+            let first_column = self.viewport_offset().0;
+            let mut next_pos = None;
+            for (cell, pos) in self.grid.updated_iter().filter(|&(_, pos)| self.is_visible(pos)) {
+                if next_pos != Some(pos) || pos % self.columns == first_column {
+                    let (x, y) = self.get_cell_top_left(pos);
+                    res.push_str(&format!("{}", cursor::Goto(x, y)));
+                }
+                cell.add_value_to_str(&mut res, Rc::clone(&self.resources));
+                next_pos = Some(pos + 1);
+            }
         } else {
-            for (cell, pos) in self.grid.updated_iter() {
+            for (cell, pos) in self.grid.updated_iter().filter(|&(_, pos)| self.is_visible(pos)) {
                 let (x, y) = self.get_cell_top_left(pos);
                 res.push_str(
                     &cell.get_content(self.cell_width, self.cell_height, x, y,
-                                      Rc::clone(&self.resources))
+                                      self.cell_padding, Rc::clone(&self.resources))
                 );
             }
         }
-        self.grid.update_complete();
+        // Cells could be written over moving cells. Draw moving cells over them again.
+        let redraw_slides = !res.is_empty();
+        for i in 0..self.slides.len() {
+            let pos = self.get_slide_position(&self.slides[i]);
+            if let Some((x, y)) = pos {
+                if redraw_slides || self.slides[i].drawn_at != pos {
+                    res.push_str(&self.get_slide_content(&self.slides[i], x, y));
+                }
+            }
+            self.slides[i].drawn_at = pos;
+        }
+        if let Some(ref mut toast) = self.toast {
+            if !res.is_empty() || !toast.drawn {
+                // Cells could be written over the toast. Draw toast over them again.
+                res.push_str(&toast.dialog.get_content(self.position, self.width, self.height));
+                toast.drawn = true;
+            }
+        }
+        if let Some(dlg) = msg_dlg {
+            // Board was redrawn under the message dialog. Draw dialog over it again.
+            res.push_str(&dlg);
+        }
+        self.grid.update_complete(update_all);
         self.update_all = false;
+        if res.is_empty() {
+            // All updated cells look the same as on the screen.
+            return None
+        }
         Some(res)
     }
 
     pub(crate) fn update_cells(&mut self, updates: CellUpdates) {
-        if self.message_lines.is_some() {
+        if self.message.is_some() {
             panic!("You can't update cells while message is open. Use hide_message() to close it.");
         }
         self.grid.update_cells(&updates);
@@ -275,137 +1986,325 @@ impl Board {
         }
     }
 
+    // Count down toast ticks and hide toast when time is up.
+    pub(crate) fn tick_toast(&mut self) {
+        if let Some(ref mut toast) = self.toast {
+            if toast.ticks_left > 0 {
+                toast.ticks_left -= 1;
+                if toast.ticks_left == 0 {
+                    self.hide_toast();
+                }
+            }
+        }
+    }
+
+    // Advance cell move animations. Moved cells are set when animation is finished.
+    pub(crate) fn tick_slides(&mut self) {
+        let mut updates = CellUpdates::new();
+        for slide in &mut self.slides {
+            slide.ticks_passed += 1;
+            if slide.ticks_passed == slide.ticks {
+                updates.push((slide.cell.clone(), slide.to));
+            }
+        }
+        self.set_moved_cells(updates);
+    }
+
+    // Finish all cell move animations at once.
+    pub(crate) fn finish_slides(&mut self) {
+        let mut updates = CellUpdates::new();
+        for slide in &mut self.slides {
+            if slide.ticks_passed < slide.ticks {
+                slide.ticks_passed = slide.ticks;
+                updates.push((slide.cell.clone(), slide.to));
+            }
+        }
+        self.set_moved_cells(updates);
+    }
+
+    // Set cells which finished moving. Finished animations are removed on the next screen update,
+    // when moving cells are wiped out.
+    fn set_moved_cells(&mut self, updates: CellUpdates) {
+        if updates.is_empty() {
+            return
+        }
+        // Cells are not drawn while message dialog is open, so it is safe to update them.
+        self.grid.update_cells(&updates);
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.check_updates(&updates, &mut self.grid)
+        }
+    }
+
+    // Get screen position of the moving cell top left corner. Return `None` if animation is
+    // finished or any of cells is not visible.
+    fn get_slide_position(&self, slide: &Slide) -> Option<(u16, u16)> {
+        let from = slide.from.1 * self.columns + slide.from.0;
+        let to = slide.to.1 * self.columns + slide.to.0;
+        if slide.ticks_passed >= slide.ticks || !self.is_visible(from) || !self.is_visible(to) {
+            return None
+        }
+        let (x0, y0) = self.get_cell_top_left(from);
+        let (x1, y1) = self.get_cell_top_left(to);
+        let step = |a: u16, b: u16| {
+            (a as i64 + (b as i64 - a as i64) * slide.ticks_passed as i64 / slide.ticks as i64)
+                as u16
+        };
+        Some((step(x0, x1), step(y0, y1)))
+    }
+
+    fn get_slide_content(&self, slide: &Slide, x: u16, y: u16) -> String {
+        let cell = match self.background {
+            Some(bg) => Cow::Owned(slide.cell.with_bg_color(
+                self.cell_width, self.cell_height, Rc::clone(&self.resources), bg)),
+            None => Cow::Borrowed(&slide.cell),
+        };
+        cell.get_content(self.cell_width, self.cell_height, x, y, self.cell_padding,
+                         Rc::clone(&self.resources))
+    }
+
+    // Restore cell size area at screen position. Cells are marked for redraw and cell borders are
+    // returned as string.
+    fn restore_area(&mut self, x: u16, y: u16) -> String {
+        let mut res = String::new();
+        for dy in 0..self.cell_height {
+            for dx in 0..self.cell_width {
+                let (x, y) = (x as usize + dx, y as usize + dy);
+                if let Some(pos) = self.get_cell_at(x, y) {
+                    self.grid.invalidate(pos);
+                    continue;
+                }
+                let w = x.wrapping_sub(self.position.0);
+                let h = y.wrapping_sub(self.position.1);
+                if w >= self.width || h >= self.height {
+                    continue;
+                }
+                if let Some(ch) = self.get_border_char(w, h) {
+                    res.push_str(&cursor::Goto(x as u16, y as u16).to_string());
+                    if let Some(bg) = self.background {
+                        res.push_str(&color::Bg(bg).to_string());
+                    }
+                    if let Some(fg) = self.border_color {
+                        res.push_str(&color::Fg(fg).to_string());
+                    }
+                    res.push(ch);
+                    if self.background.is_some() || self.border_color.is_some() {
+                        res.push_str(style::Reset.as_ref());
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    // Change pulse color when period is over and stop pulse when time is up.
+    pub(crate) fn tick_pulse(&mut self) {
+        let mut finished = false;
+        if let Some(ref mut pulse) = self.pulse {
+            if pulse.ticks_left > 0 {
+                pulse.ticks_left -= 1;
+                finished = pulse.ticks_left == 0;
+            }
+            pulse.ticks_passed += 1;
+            if !finished && pulse.ticks_passed % pulse.period_ticks == 0 {
+                let phase = pulse.ticks_passed / pulse.period_ticks % 2;
+                for &pos in &pulse.cells {
+                    self.grid.set_highlight(pos, pulse.colors[phase as usize]);
+                }
+            }
+        }
+        if finished {
+            self.stop_pulse();
+        }
+    }
+
+    // Stop pulse and remove highlighting of its cells.
+    pub(crate) fn stop_pulse(&mut self) {
+        if let Some(pulse) = self.pulse.take() {
+            for pos in pulse.cells {
+                self.grid.remove_highlight(pos);
+            }
+        }
+    }
+
+    // Toggle blinking cursor. Cursor doesn't blink while message dialog is open.
+    pub(crate) fn blink_cursor(&mut self) {
+        if self.message.is_some() {
+            return
+        }
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.blink(&mut self.grid);
+        }
+    }
+
     pub(crate) fn handle_key(&mut self, key: Key) -> KeyHandleResult {
-        match self.cursor {
-            Some(ref mut cursor) => cursor.handle_key(key, &mut self.grid),
+        if let Some(ref mut message) = self.message {
+            // Message dialog is modal, cursor can't be moved while it is open.
+            let res = message.handle_key(key);
+            if let KeyHandleResult::DialogResult(_) = res {
+                self.hide_message();
+            }
+            return res
+        }
+        let (_, _, page) = self.get_visible_area();
+        let res = match self.cursor {
+            Some(ref mut cursor) => cursor.handle_key(key, page, &mut self.grid),
+            None => KeyHandleResult::NotHandled
+        };
+        if let KeyHandleResult::NewPosition(_) = res {
+            self.scroll_to_cursor();
+        }
+        res
+    }
+
+    // Handle mouse click at screen position. Return clicked cell position and cursor move result.
+    pub(crate) fn handle_click(&mut self, x: u16, y: u16) -> Option<(Position, KeyHandleResult)> {
+        if self.message.is_some() {
+            // Message dialog is modal.
+            return None
+        }
+        let pos = self.get_cell_at(x as usize, y as usize)?;
+        if self.grid.is_disabled(pos) {
+            return None
+        }
+        let res = match self.cursor {
+            Some(ref mut cursor) => cursor.set_position(pos, &mut self.grid),
             None => KeyHandleResult::NotHandled
+        };
+        Some((pos, res))
+    }
+
+    // Handle right mouse button click at screen position. Toggle clicked cell and return its
+    // position and new state index.
+    pub(crate) fn handle_right_click(&mut self, x: u16, y: u16) -> Option<(Position, usize)> {
+        if self.message.is_some() {
+            return None
+        }
+        let pos = self.get_cell_at(x as usize, y as usize)?;
+        if self.grid.is_disabled(pos) {
+            return None
         }
+        self.toggle_cell(pos).map(|index| (pos, index))
     }
 
-    pub(crate) fn show_message(&mut self, lines: &[&str]) {
-        let mut v = Vec::with_capacity(lines.len());
-        for &l in lines {
-            v.push(String::from(l));
+    // Handle mouse move to screen position. Return cell position if mouse pointer moved to
+    // another cell.
+    pub(crate) fn handle_mouse_move(&mut self, x: u16, y: u16) -> Option<Position> {
+        if self.message.is_some() {
+            return None
         }
-        self.message_lines = Some(v);
+        let pos = self.get_cell_at(x as usize, y as usize).filter(|&p| !self.grid.is_disabled(p));
+        if pos == self.hover {
+            return None
+        }
+        self.hover = pos;
+        if let Some(color) = self.hover_color {
+            self.grid.set_hover(pos.map(|p| (p, color)));
+        }
+        pos
+    }
+
+    pub(crate) fn show_message(&mut self, lines: &[&str], buttons: &[&str],
+                               position: DialogPosition) {
+        self.message = Some(MessageDialog::new(lines, buttons, position, self.dialog_style));
+    }
+
+    pub(crate) fn show_prompt(&mut self, label: &str, position: DialogPosition) {
+        self.message = Some(MessageDialog::new_prompt(label, position, self.dialog_style));
+    }
+
+    // Pass key to the prompt dialog. Return entered text and close dialog on Enter.
+    pub(crate) fn handle_prompt_key(&mut self, key: Key) -> Option<String> {
+        let text = match self.message.as_mut()?.handle_key(key) {
+            KeyHandleResult::DialogResult(_) => self.message.as_mut()?.take_input(),
+            _ => return None
+        };
+        self.hide_message();
+        Some(text)
+    }
+
+    pub(crate) fn set_dialog_style(&mut self, style: DialogStyle) {
+        self.dialog_style = style;
+    }
+
+    // Close message dialog and return it, so it can be shown again with `restore_message`.
+    pub(crate) fn take_message(&mut self) -> Option<MessageDialog> {
+        self.update_all = true;
+        self.message.take()
+    }
+
+    pub(crate) fn restore_message(&mut self, message: Option<MessageDialog>) {
+        self.message = message;
+        self.update_all = true;
     }
 
     pub(crate) fn hide_message(&mut self) {
-        self.message_lines = None;
+        self.message = None;
         self.update_all = true;
     }
 
     fn get_message_dialog(&self) -> Option<String> {
-        if let Some(ref msg_lines) = self.message_lines {
-            let line_max_len = msg_lines.iter().map(|x| str_utils::get_str_len(x)).max()
-                    .expect("Message lines slice must not be empty.");
-            // We want to have at least 1 character margin between border and text.
-            // So 8 means: board border + margin + dialog border + margin, from both sides.
-            let dlg_w = line_max_len.min(self.width - 8) + 4;
-            let dlg_h = msg_lines.len().min(self.height - 8) + 4;
-            // Center dialog on the board.
-            let x = (self.position.0 + (self.width - dlg_w) / 2) as u16;
-            let mut y = (self.position.1 + (self.height - dlg_h) / 2) as u16;
-
-            let mut res = String::with_capacity((dlg_w + str_utils::GOTO_SEQUENCE_WIDTH) * dlg_h);
-            res.push_str(&format!(
-                "{}{}{}{}{}{}{}{}{}",
-                cursor::Goto(x, y),
-                chars::DOUBLE_BORDER_TOP_LEFT,
-                chars::DOUBLE_BORDER_HOR_LINE.to_string().repeat(dlg_w - 2),
-                chars::DOUBLE_BORDER_TOP_RIGHT,
-                cursor::Goto(x, y + 1),
-                chars::DOUBLE_BORDER_VERT_LINE,
-                " ".repeat(dlg_w - 2),
-                chars::DOUBLE_BORDER_VERT_LINE,
-                cursor::Goto(x, y + 2),
-            ));
-            y += 2;
-
-            for i in 2..dlg_h - 2 {
-                y += 1;
-                let line = &msg_lines[i - 2];
-                let s = if line.starts_with(TEXT_ALIGN_CENTER) {
-                    let ll = &line[TEXT_ALIGN_CENTER.len()..];
-                    if str_utils::get_str_len(ll) < dlg_w - 4 {
-                        format!("{:^width$}", ll, width = dlg_w - 4)
-                    } else {
-                        str_utils::get_str_range(ll, 0, dlg_w - 4).to_string()
-                    }
-                } else if line.starts_with(TEXT_ALIGN_RIGHT) {
-                    let ll = &line[TEXT_ALIGN_CENTER.len()..];
-                    if str_utils::get_str_len(ll) < dlg_w - 4 {
-                        format!("{:>width$}", ll, width = dlg_w - 4)
-                    } else {
-                        str_utils::get_str_range(ll, 0, dlg_w - 4).to_string()
-                    }
-                } else {
-                    if str_utils::get_str_len(line) < dlg_w - 4 {
-                        format!("{:width$}", &line, width = dlg_w - 4)
-                    } else {
-                        str_utils::get_str_range(line, 0, dlg_w - 4).to_string()
-                    }
-                };
-                res.push_str(&format!(
-                    "{} {} {}{}",
-                    chars::DOUBLE_BORDER_VERT_LINE,
-                    s,
-                    chars::DOUBLE_BORDER_VERT_LINE,
-                    cursor::Goto(x, y),
-                ));
-            }
+        self.message.as_ref().map(|m| m.get_content(self.position, self.width, self.height))
+    }
 
-            res.push_str(&format!(
-                "{}{}{}{}{}{}{}",
-                chars::DOUBLE_BORDER_VERT_LINE,
-                " ".repeat(dlg_w - 2),
-                chars::DOUBLE_BORDER_VERT_LINE,
-                cursor::Goto(x, y + 1),
-                chars::DOUBLE_BORDER_BOTTOM_LEFT,
-                chars::DOUBLE_BORDER_HOR_LINE.to_string().repeat(dlg_w - 2),
-                chars::DOUBLE_BORDER_BOTTOM_RIGHT
-            ));
-            Some(res)
+    // Get title with separators ready to be put into the top border. Return title start
+    // position, title string and its width in columns.
+    fn get_title(&self) -> Option<(usize, String, usize)> {
+        let (align, title) = str_utils::split_align_prefix(self.title.as_ref()?);
+        // Title is placed between corners and 1 horizontal line on each side. It has separators
+        // and spaces around it.
+        let avail = self.width.checked_sub(4)?;
+        let max_len = avail.checked_sub(4).filter(|&l| l > 0)?;
+        let text = if str_utils::get_str_len(title) > max_len {
+            str_utils::get_str_range(title, 0, max_len)
         } else {
-            None
-        }
+            title
+        };
+        let len = str_utils::get_str_len(text) + 4;
+        let (before, _) = str_utils::get_padding(len, avail, align);
+        let chars = self.edge_styles[Edge::Top.index()].chars();
+        Some((before + 2, format!("{} {} {}", chars.title_start, text, chars.title_end), len))
     }
 
     fn get_border_char(&self, w: usize, h: usize) -> Option<char> {
         let h_cell_border = h % (self.cell_height + 1) == 0;
         let v_cell_border = w % (self.cell_width + 1) == 0;
+        let (last_w, last_h) = (self.width - 1, self.height - 1);
+        let top = self.edge_styles[Edge::Top.index()];
+        let bottom = self.edge_styles[Edge::Bottom.index()];
+        let left = self.edge_styles[Edge::Left.index()];
+        let right = self.edge_styles[Edge::Right.index()];
 
         if w == 0 && h == 0 {
-            Some(chars::DOUBLE_BORDER_TOP_LEFT)
-        } else if w == self.width - 1 && h == 0 {
-            Some(chars::DOUBLE_BORDER_TOP_RIGHT)
-        } else if w == 0 && h == self.height -1  {
-            Some(chars::DOUBLE_BORDER_BOTTOM_LEFT)
-        } else if w == self.width - 1 && h == self.height -1 {
-            Some(chars::DOUBLE_BORDER_BOTTOM_RIGHT)
-        } else if h == 0  {
+            Some(top.max(left).chars().top_left)
+        } else if w == last_w && h == 0 {
+            Some(top.max(right).chars().top_right)
+        } else if w == 0 && h == last_h {
+            Some(bottom.max(left).chars().bottom_left)
+        } else if w == last_w && h == last_h {
+            Some(bottom.max(right).chars().bottom_right)
+        } else if h == 0 {
             if self.cell_borders && v_cell_border {
-                Some(chars::DOUBLE_BORDER_JOIN_UP)
+                Some(top.chars().join_up)
             } else {
-                Some(chars::DOUBLE_BORDER_HOR_LINE)
+                Some(top.chars().hor_line)
             }
-        } else if h == self.height -1  {
+        } else if h == last_h {
             if self.cell_borders && v_cell_border {
-                Some(chars::DOUBLE_BORDER_JOIN_DOWN)
+                Some(bottom.chars().join_down)
             } else {
-                Some(chars::DOUBLE_BORDER_HOR_LINE)
+                Some(bottom.chars().hor_line)
             }
         } else if w == 0 {
             if self.cell_borders && h_cell_border {
-                Some(chars::DOUBLE_BORDER_JOIN_LEFT)
+                Some(left.chars().join_left)
             } else {
-                Some(chars::DOUBLE_BORDER_VERT_LINE)
+                Some(left.chars().vert_line)
             }
-        } else if w == self.width - 1 {
+        } else if w == last_w {
             if self.cell_borders && h_cell_border {
-                Some(chars::DOUBLE_BORDER_JOIN_RIGHT)
+                Some(right.chars().join_right)
             } else {
-                Some(chars::DOUBLE_BORDER_VERT_LINE)
+                Some(right.chars().vert_line)
             }
         } else if self.cell_borders {
             if h_cell_border && v_cell_border {
@@ -422,6 +2321,25 @@ impl Board {
         }
     }
 
+    // Get position of the cell at screen position. Return None if there is no cell (border or
+    // outside of the board).
+    fn get_cell_at(&self, x: usize, y: usize) -> Option<Position> {
+        let (step_x, step_y) = if self.cell_borders {
+            (self.cell_width + 1, self.cell_height + 1)
+        } else {
+            (self.cell_width, self.cell_height)
+        };
+        let dx = x.checked_sub(self.position.0 + 1)?;
+        let dy = y.checked_sub(self.position.1 + 1)?;
+        let (column, row) = (dx / step_x, dy / step_y);
+        let (offset, columns, rows) = self.get_visible_area();
+        if column >= columns || row >= rows || dx % step_x >= self.cell_width ||
+                dy % step_y >= self.cell_height {
+            return None
+        }
+        Some(Position(offset.0 + column, offset.1 + row))
+    }
+
     fn get_cell_top_left(&self, pos: usize) -> (u16, u16) {
         let start_x = self.position.0 + 1;
         let start_y = self.position.1 + 1;
@@ -435,8 +2353,121 @@ impl Board {
         } else {
             self.cell_height
         };
-        let x = start_x + (pos % self.columns) * step_x;
-        let y = start_y + (pos / self.columns) * step_y;
+        let Position(offset_x, offset_y) = self.viewport_offset();
+        let x = start_x + (pos % self.columns - offset_x) * step_x;
+        let y = start_y + (pos / self.columns - offset_y) * step_y;
         (x as u16, y as u16)
     }
 }
+
+/// Board builder.
+///
+/// It is an alternative to [`Board::new`] with named parameters. By default board has 1x1 cells
+/// without borders and has no resource table.
+///
+/// [`Board::new`]: struct.Board.html#method.new
+///
+/// # Examples
+///
+/// The same board as in [`Board::new`] example.
+///
+/// ```no_run
+/// # use gameboard::{BoardBuilder, ResourceTable};
+/// # fn create_resources() -> ResourceTable { ResourceTable::new() }
+/// let mut board = BoardBuilder::new(3, 3)
+///     .cell_size(10, 5)
+///     .borders(true)
+///     .resources(create_resources())
+///     .build();
+/// ```
+pub struct BoardBuilder {
+    columns: usize,
+    rows: usize,
+    cell_width: usize,
+    cell_height: usize,
+    cell_borders: bool,
+    resources: Option<ResourceTable>,
+}
+
+impl BoardBuilder {
+    /// Creates board builder.
+    ///
+    /// # Arguments
+    ///
+    /// `columns` - horizontal number of cells
+    ///
+    /// `rows` - vertical number of cells
+    pub fn new(columns: usize, rows: usize) -> Self {
+        BoardBuilder {
+            columns,
+            rows,
+            cell_width: 1,
+            cell_height: 1,
+            cell_borders: false,
+            resources: None,
+        }
+    }
+
+    /// Sets cell size in characters.
+    pub fn cell_size(mut self, width: usize, height: usize) -> Self {
+        self.cell_width = width;
+        self.cell_height = height;
+        self
+    }
+
+    /// Shows or hides cell borders.
+    pub fn borders(mut self, cell_borders: bool) -> Self {
+        self.cell_borders = cell_borders;
+        self
+    }
+
+    /// Sets resource table.
+    pub fn resources(mut self, resources: ResourceTable) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Creates board.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Board::new`](struct.Board.html#method.new).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::BoardBuilder;
+    /// let board = BoardBuilder::new(4, 2).build();
+    /// assert_eq!((board.columns(), board.rows()), (4, 2));
+    /// ```
+    pub fn build(self) -> Board {
+        Board::new(self.columns, self.rows, self.cell_width, self.cell_height, self.cell_borders,
+                   self.resources)
+    }
+}
+
+// Check that every resource string fills the whole cell. Panic with the first invalid resource id.
+fn validate_resources(resources: &ResourceTable, cell_len: usize) {
+    let mut ids: Vec<&u16> = resources.keys().collect();
+    ids.sort();
+    for id in ids {
+        let (_, content) = str_utils::split_align_prefix(&resources[id]);
+        let len = str_utils::get_str_len(content);
+        if len != cell_len {
+            panic!("Resource {} has {} characters, cell size is {} characters.", id, len,
+                   cell_len);
+        }
+    }
+}
+
+// Get total board size in characters (with borders).
+fn get_board_size(columns: usize, rows: usize, cell_width: usize, cell_height: usize,
+                  cell_borders: bool) -> (usize, usize) {
+    let mut w_borders = 2;
+    let mut h_borders = 2;
+    if cell_borders {
+        w_borders += columns - 1;
+        h_borders += rows - 1;
+    }
+    (columns * cell_width + w_borders, rows * cell_height + h_borders)
+}
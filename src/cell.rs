@@ -1,17 +1,31 @@
 //! Board cell.
 
+use std::mem;
 use std::rc::Rc;
 
-use unicode_segmentation::UnicodeSegmentation;
-use termion::{style, cursor, color};
-
+use crate::ansi::{style, cursor, color};
 use crate::board::ResourceTable;
+use crate::chars;
+use crate::color::Color;
+use crate::str_utils::{self, TextAlign};
 
-const RESOURCE_TABLE_ERR_MSG: &'static str =
+const RESOURCE_TABLE_ERR_MSG: &str =
     "If you use Cell::ResourceId, you must add resource table to Board.";
 
+/// Space between cell edges and cell content.
+///
+/// Padding area is filled with spaces and has the same background color as the cell.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct CellPadding {
+    /// Number of empty rows above content.
+    pub top: usize,
+    /// Number of empty columns before content.
+    pub left: usize,
+}
+
 /// Cell content.
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cell {
     /// Empty cell. It will be filled with spaces.
     Empty,
@@ -23,9 +37,39 @@ pub enum Cell {
     Char(char),
     /// Arbitrary string. String will be written into cell by rows.
     ///
+    /// If string doesn't fill the whole row, the row is aligned horizontally:
+    ///
+    /// * Strings are left-aligned by default
+    /// * Strings started with *|^|* are centered
+    /// * Strings started with *|>|* are right-aligned
+    ///
+    /// Right-to-left text (Hebrew, Arabic) is written by [`RightToLeft`](#variant.RightToLeft)
+    /// cell.
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell};
+    /// use termion::{cursor::Goto, style};
+    ///
+    /// fn render(text: &str) -> String {
+    ///     let mut board = Board::new(1, 1, 10, 1, false, None);
+    ///     board.init_from_vec(&[Cell::Content(text.to_string())], None);
+    ///     board.render()
+    /// }
+    ///
+    /// let row = |s: &str| format!("{}{}{}", Goto(2, 2), s, style::Reset);
+    /// assert!(render("abcd").ends_with(&row("abcd      ")));
+    /// assert!(render("|^|abcd").ends_with(&row("   abcd   ")));
+    /// assert!(render("|>|abcd").ends_with(&row("      abcd")));
+    /// // Odd padding puts the extra space after centered text.
+    /// assert!(render("|^|abc").ends_with(&row("   abc    ")));
+    /// ```
+    ///
     /// You can use [escape sequences]. Termion provides `termion::style` and `termion::color` for
     /// this. You don't have to reset style at the end, it'll be done automatically.
     ///
+    /// Wide characters (CJK, emoji) take 2 columns. If wide character doesn't fit into the row,
+    /// it is moved to the next row and the rest of the row is filled with space.
+    ///
     /// # Implementation note
     ///
     /// If you use [`Cursor`], do not use `termion::style::Reset` and `termion::color::Bg` inside
@@ -39,6 +83,7 @@ pub enum Cell {
     ///
     /// ```no_run
     /// use termion::{style, color};
+    /// # use gameboard::{Board, Cell, Cursor, Position, ResourceTable};
     ///
     /// fn create_resources() -> ResourceTable {
     ///     let mut res = ResourceTable::new();
@@ -83,10 +128,231 @@ pub enum Cell {
     ///         )],
     ///     Some(cursor));
     /// ```
+    ///
+    /// Wide characters in 5x2 cell:
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell};
+    /// use termion::{cursor::Goto, style};
+    ///
+    /// let mut board = Board::new(1, 1, 5, 2, false, None);
+    /// board.init_from_vec(&[Cell::Content(String::from("日本語"))], None);
+    /// assert!(board.render().ends_with(
+    ///     &format!("{}日本 {}語   {}", Goto(2, 2), Goto(2, 3), style::Reset)));
+    ///
+    /// // Emoji doesn't fit into the first row of 3x2 cell, so it is moved to the second row.
+    /// let mut board = Board::new(1, 1, 3, 2, false, None);
+    /// board.init_from_vec(&[Cell::Content(String::from("ab😀c"))], None);
+    /// assert!(board.render().ends_with(
+    ///     &format!("{}ab {}😀c{}", Goto(2, 2), Goto(2, 3), style::Reset)));
+    /// ```
     Content(String),
+    /// Multi-row content. Each string is one cell row, rows are written from the top of the
+    /// cell.
+    ///
+    /// Rows longer than cell width are truncated, shorter rows are padded with spaces. Extra
+    /// rows are ignored. If sprite has fewer rows than cell height, the rest of the cell is
+    /// filled with spaces.
+    ///
+    /// Each row can start with alignment prefix and contain escape sequences, the same as in
+    /// [`Content`](#variant.Content). Style is reset at the end of each row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Cell;
+    /// let cross = Cell::Sprite(vec![
+    ///     String::from(" X   X"),
+    ///     String::from("   X"),
+    ///     String::from(" X   X"),
+    /// ]);
+    /// let circle = Cell::Sprite(vec![
+    ///     String::from("|^|OOO"),
+    ///     String::from("|^|O   O"),
+    ///     String::from("|^|OOO"),
+    /// ]);
+    /// ```
+    Sprite(Vec<String>),
+    /// Two colored pixels, one above another. Cell is filled with `▀` character with `top`
+    /// foreground color and `bottom` background color, so 1x1 cells have double vertical
+    /// resolution. It can be used for pixel art.
+    ///
+    /// Cursor, hover and highlight background colors fill the whole cell, pixel colors are
+    /// hidden while cell is highlighted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell, Color};
+    /// use termion::{color, cursor::Goto, style};
+    ///
+    /// let mut board = Board::new(1, 1, 1, 1, false, None);
+    /// board.init_from_vec(&[Cell::HalfBlock {
+    ///     top: Color::Rgb(255, 0, 0),
+    ///     bottom: Color::Rgb(0, 0, 255),
+    /// }], None);
+    /// assert!(board.render().ends_with(&format!("{}{}{}▀{}", Goto(2, 2),
+    ///     color::Fg(color::Rgb(255, 0, 0)), color::Bg(color::Rgb(0, 0, 255)), style::Reset)));
+    /// ```
+    HalfBlock {
+        /// Top pixel color.
+        top: Color,
+        /// Bottom pixel color.
+        bottom: Color,
+    },
+    /// Right-to-left text (Hebrew, Arabic) of `Content` or `Sprite` cell.
+    ///
+    /// Characters of each row are written from the right edge of the row in reverse order, so
+    /// text stored in logical order is shown correctly. Rows are right-aligned unless they start
+    /// with *|^|* prefix. Other wrapped cells are written as is. Use
+    /// [`set_rtl`](#method.set_rtl) to create this cell.
+    RightToLeft(Box<Cell>),
 }
 
 impl Cell {
+    /// Creates cell with arbitrary string. Same as [`Content`](#variant.Content).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Cell;
+    /// assert_eq!(Cell::text("|^|Hi"), Cell::Content(String::from("|^|Hi")));
+    /// ```
+    pub fn text(text: &str) -> Cell {
+        Cell::Content(text.to_string())
+    }
+
+    /// Creates cell with string written in `fg` color.
+    ///
+    /// Color is added after the alignment prefix. Only foreground color is set, so the cell
+    /// doesn't break [`Cursor`] highlighting.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Cell;
+    /// use termion::color;
+    ///
+    /// assert_eq!(Cell::colored("|^|X", color::Red),
+    ///            Cell::Content(format!("|^|{}X", color::Fg(color::Red))));
+    /// ```
+    pub fn colored<C: Into<Color>>(text: &str, fg: C) -> Cell {
+        let (_, content) = str_utils::split_align_prefix(text);
+        let prefix = &text[..text.len() - content.len()];
+        Cell::Content(format!("{}{}{}", prefix, color::Fg(fg.into()), content))
+    }
+
+    /// Creates cell filled with `c` character. Same as [`Char`](#variant.Char).
+    pub fn filled(c: char) -> Cell {
+        Cell::Char(c)
+    }
+
+    /// Creates multi-row cell. Each string is one cell row. Same as [`Sprite`](#variant.Sprite).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Cell;
+    /// let cross = Cell::from_lines(&[" X   X", "   X", " X   X"]);
+    /// ```
+    pub fn from_lines(lines: &[&str]) -> Cell {
+        Cell::Sprite(lines.iter().map(|l| l.to_string()).collect())
+    }
+
+    /// Enables or disables right-to-left text (Hebrew, Arabic) of `Content` and `Sprite` cells.
+    /// Other cells are not changed.
+    ///
+    /// Right-to-left text is disabled by default. If it is enabled, the cell is wrapped into
+    /// [`RightToLeft`](#variant.RightToLeft) cell, disabling it unwraps the cell. Cell content
+    /// isn't changed.
+    ///
+    /// Styled right-to-left text is not supported: escape sequences at the start of the text
+    /// are kept, other escape sequences are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Board, Cell};
+    /// use termion::{cursor::Goto, style};
+    ///
+    /// let mut cell = Cell::text("שלום");
+    /// cell.set_rtl(true);
+    /// assert_eq!(cell, Cell::RightToLeft(Box::new(Cell::text("שלום"))));
+    /// let mut board = Board::new(1, 1, 3, 2, false, None);
+    /// board.init_from_vec(&[cell.clone()], None);
+    /// assert!(board.render().ends_with(&format!("{}ולש{}  ם{}", Goto(2, 2), Goto(2, 3),
+    ///                                           style::Reset)));
+    ///
+    /// cell.set_rtl(false);
+    /// assert_eq!(cell, Cell::text("שלום"));
+    ///
+    /// // Centered right-to-left text.
+    /// let mut cell = Cell::text("|^|שלום");
+    /// cell.set_rtl(true);
+    /// let mut board = Board::new(1, 1, 6, 1, false, None);
+    /// board.init_from_vec(&[cell], None);
+    /// assert!(board.render().ends_with(&format!("{} םולש {}", Goto(2, 2), style::Reset)));
+    /// ```
+    pub fn set_rtl(&mut self, rtl: bool) {
+        match self {
+            Cell::Content(_) | Cell::Sprite(_) if rtl => {
+                let cell = mem::replace(self, Cell::Empty);
+                *self = Cell::RightToLeft(Box::new(cell));
+            },
+            Cell::RightToLeft(cell) if !rtl => {
+                let cell = mem::replace(cell.as_mut(), Cell::Empty);
+                *self = cell;
+            },
+            _ => (),
+        }
+    }
+
+    /// Returns width in terminal columns of the widest row of cell content rendered in a cell
+    /// `cell_width` columns wide.
+    ///
+    /// Escape sequences and alignment prefix are not counted, wide characters take 2 columns.
+    /// `Content` is split into rows the same way as on the board, `Sprite` rows are truncated.
+    /// `Char` and `HalfBlock` fill the whole cell, so `cell_width` is returned.
+    /// `ResourceId` content is unknown without resource table, `cell_width` is returned as well.
+    /// `Empty` cell has no content and returns 0.
+    ///
+    /// Use `usize::MAX` as `cell_width` to get content width without splitting into rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Cell;
+    /// use termion::color;
+    ///
+    /// let cell = Cell::colored("|^|Score", color::Red);
+    /// assert_eq!(cell.display_width(10), 5);
+    /// assert_eq!(cell.display_width(3), 3);
+    /// // Wide character doesn't fit into the first row.
+    /// assert_eq!(Cell::text("ab😀c").display_width(3), 3);
+    /// assert_eq!(Cell::text("ab😀c").display_width(usize::MAX), 5);
+    /// assert_eq!(Cell::from_lines(&["ab", "abcd"]).display_width(3), 3);
+    /// ```
+    pub fn display_width(&self, cell_width: usize) -> usize {
+        match self {
+            Cell::Empty => 0,
+            Cell::Char(_) | Cell::HalfBlock { .. } | Cell::ResourceId(_) => cell_width,
+            Cell::Content(content) => {
+                let (_, content) = str_utils::split_align_prefix(content);
+                str_utils::get_wrapped_width(content, cell_width)
+            },
+            Cell::Sprite(rows) => rows.iter()
+                .map(|row| {
+                    let (_, row) = str_utils::split_align_prefix(row);
+                    str_utils::get_str_len(str_utils::get_str_range(row, 0, cell_width))
+                })
+                .max()
+                .unwrap_or(0),
+            Cell::RightToLeft(cell) => cell.display_width(cell_width),
+        }
+    }
+
     // Add cell content to string.
     pub(crate) fn add_value_to_str(&self, dst: &mut String,
                                    resources: Rc<Option<ResourceTable>>) {
@@ -98,35 +364,66 @@ impl Cell {
                     let content = &rt[id];
                     dst.push_str(&format!("{}{}", content, style::Reset));
                 } else {
-                    panic!(RESOURCE_TABLE_ERR_MSG);
+                    panic!("{}", RESOURCE_TABLE_ERR_MSG);
                 }
             },
-            Cell::Content(content) => dst.push_str(&format!("{}{}", content, style::Reset))
+            Cell::Content(content) => {
+                let (_, content) = str_utils::split_align_prefix(content);
+                dst.push_str(&format!("{}{}", content, style::Reset))
+            },
+            Cell::Sprite(rows) => {
+                let row = rows.first().map_or("", |r| r.as_str());
+                dst.push_str(&format!("{}{}", Cell::align_row(row, 1, false), style::Reset))
+            },
+            Cell::HalfBlock { top, bottom } =>
+                dst.push_str(&format!("{}{}{}{}", color::Fg(*top), color::Bg(*bottom),
+                                      chars::UPPER_HALF_BLOCK, style::Reset)),
+            // One column cell has one character, so text direction doesn't matter.
+            Cell::RightToLeft(cell) => cell.add_value_to_str(dst, resources),
         };
     }
 
-    // Get formatted cell content ready to display in terminal.
+    // Get formatted cell content ready to display in terminal. Content is written into the
+    // area inside padding. Padding must be less than cell size.
     pub(crate) fn get_content(&self, width: usize, height: usize, x: u16, y: u16,
+                              padding: CellPadding,
                               resources: Rc<Option<ResourceTable>>) -> String {
         match self {
-            Cell::Empty => Cell::prepare_str_from_char(' ', width, height, x, y),
-            Cell::Char(c) => Cell::prepare_str_from_char(*c, width, height, x, y),
+            Cell::Empty => Cell::prepare_str_from_char(' ', width, height, x, y, padding),
+            Cell::Char(c) => Cell::prepare_str_from_char(*c, width, height, x, y, padding),
             Cell::ResourceId(id) => {
                 if let Some(rt) = resources.as_ref() {
                     let content = &rt[id];
-                    Cell::prepare_str(content, width, height, x, y)
+                    Cell::prepare_str(content, width, height, x, y, padding)
                 } else {
-                    panic!(RESOURCE_TABLE_ERR_MSG);
+                    panic!("{}", RESOURCE_TABLE_ERR_MSG);
                 }
             },
-            Cell::Content(content) => Cell::prepare_str(content, width, height, x, y)
+            Cell::Content(content) => Cell::prepare_str(content, width, height, x, y, padding),
+            Cell::Sprite(rows) =>
+                Cell::prepare_sprite(rows, width, height, x, y, padding, false),
+            Cell::HalfBlock { top, bottom } => {
+                let content = format!("{}{}{}", color::Fg(*top), color::Bg(*bottom),
+                                      chars::UPPER_HALF_BLOCK.to_string().repeat(width * height));
+                Cell::prepare_str(&content, width, height, x, y, padding)
+            },
+            Cell::RightToLeft(cell) => match cell.as_ref() {
+                Cell::Content(content) => {
+                    let (align, content) = str_utils::split_align_prefix(content);
+                    let content = str_utils::rtl_to_ltr(content, width - padding.left, align);
+                    Cell::prepare_str(&content, width, height, x, y, padding)
+                },
+                Cell::Sprite(rows) =>
+                    Cell::prepare_sprite(rows, width, height, x, y, padding, true),
+                cell => cell.get_content(width, height, x, y, padding, resources),
+            },
         }
     }
 
     // Create new cell from this one by adding background color. Used by Cursor.
     pub(crate) fn with_bg_color(&self, width: usize, height: usize,
                                 resources: Rc<Option<ResourceTable>>,
-                                bg_color: color::Rgb) -> Cell {
+                                bg_color: Color) -> Cell {
         match self {
             Cell::Empty =>
                 Cell::Content(
@@ -137,65 +434,160 @@ impl Cell {
             Cell::ResourceId(id) => {
                 if let Some(rt) = resources.as_ref() {
                     let content = &rt[id];
-                    Cell::Content(format!("{}{}", color::Bg(bg_color), content))
+                    Cell::Content(Cell::add_bg_color(content, bg_color))
                 } else {
-                    panic!(RESOURCE_TABLE_ERR_MSG);
+                    panic!("{}", RESOURCE_TABLE_ERR_MSG);
                 }
             },
-            Cell::Content(content) => Cell::Content(format!("{}{}", color::Bg(bg_color), content))
+            Cell::Content(content) => Cell::Content(Cell::add_bg_color(content, bg_color)),
+            Cell::Sprite(rows) => {
+                // Missing rows are added, so the whole cell gets background color.
+                let empty = String::new();
+                Cell::Sprite(
+                    (0..height)
+                        .map(|i| Cell::add_bg_color(rows.get(i).unwrap_or(&empty), bg_color))
+                        .collect())
+            },
+            Cell::HalfBlock { .. } => Cell::HalfBlock { top: bg_color, bottom: bg_color },
+            Cell::RightToLeft(cell) =>
+                Cell::RightToLeft(Box::new(cell.with_bg_color(width, height, resources,
+                                                              bg_color))),
         }
     }
 
-    // Fill cell with char and add Goto sequences.
+    // Add background color to the string. Alignment prefix is kept at the start.
+    fn add_bg_color(content: &str, bg_color: Color) -> String {
+        let (_, text) = str_utils::split_align_prefix(content);
+        let prefix = &content[..content.len() - text.len()];
+        format!("{}{}{}", prefix, color::Bg(bg_color), text)
+    }
+
+    // Fill cell with char and add Goto sequences. Padding is filled with spaces.
     fn prepare_str_from_char(content: char, width: usize, height: usize,
-                             x: u16, y: u16) -> String {
-        let mut y = y;
+                             x: u16, y: u16, padding: CellPadding) -> String {
         let mut res = String::with_capacity(width * height * 2);
-        for _ in 0..height {
-            res.push_str(
-                &format!("{}{}", cursor::Goto(x, y), content.to_string().repeat(width)));
-            y += 1;
+        for row in 0..height {
+            let line = if row < padding.top {
+                " ".repeat(width)
+            } else {
+                format!("{}{}", " ".repeat(padding.left),
+                        content.to_string().repeat(width - padding.left))
+            };
+            res.push_str(&format!("{}{}", cursor::Goto(x, y + row as u16), line));
         }
         res
     }
 
-    // Split cell content string into lines and add Goto sequences. Add style reset at the end.
-    fn prepare_str(content: &str, width: usize, height: usize, x: u16, y: u16) -> String {
-        const CSI_SGR_START: char = '\x1b';
-        const CSI_SGR_END: char = 'm';
-
+    // Split cell content string into lines and add Goto sequences. The last incomplete line is
+    // aligned according to alignment prefix. Add style reset at the end.
+    fn prepare_str(content: &str, width: usize, height: usize, x: u16, y: u16,
+                   padding: CellPadding) -> String {
+        let (align, content) = str_utils::split_align_prefix(content);
         let mut res = String::with_capacity(content.len() * 2);
         // Set cursor to cell top left corner
         res.push_str(&cursor::Goto(x, y).to_string());
 
-        let mut line_start = 0;
-        let mut ch_count = 0;
-        let mut is_csi = false;
+        // Escape sequences before the first visible character are written before padding, so
+        // padding gets the same background color.
+        let mut line_start = str_utils::get_grapheme_offset(content, 0);
+        res.push_str(&content[..line_start]);
         let mut y = y;
         let mut height = height;
-        for (i, ch) in UnicodeSegmentation::grapheme_indices(content, true) {
-            if ch.as_bytes()[0] as char == CSI_SGR_START {
-                is_csi = true;
-            } else if is_csi && ch.as_bytes()[0] as char == CSI_SGR_END {
-                is_csi = false;
-            } else if !is_csi {
-                ch_count += 1;
-                if ch_count == width {
-                    res.push_str(&content[line_start..i + ch.len()]);
-                    ch_count = 0;
-                    line_start = i + ch.len();
-                    y += 1;
-                    height -= 1;
-                    if height > 0 {
-                        res.push_str(&cursor::Goto(x, y).to_string());
-                    } else {
-                        break;
-                    }
+        for _ in 0..padding.top {
+            res.push_str(&format!("{}{}", " ".repeat(width), cursor::Goto(x, y + 1)));
+            y += 1;
+            height -= 1;
+        }
+        let left = " ".repeat(padding.left);
+        res.push_str(&left);
+        let width = width - padding.left;
+
+        // Start of the first visible character in the line. Escape sequences before it are
+        // written before alignment spaces, so spaces get the same background color.
+        let mut text_start = 0;
+        // Line width in columns.
+        let mut ch_count = 0;
+        for (i, ch) in str_utils::visible_graphemes(content) {
+            let ch_width = str_utils::get_grapheme_width(ch);
+            if ch_count > 0 && ch_count + ch_width > width {
+                // Line is full. If wide character doesn't fit, the rest of the line is filled
+                // with spaces and character is moved to the next line.
+                res.push_str(&content[line_start..i]);
+                res.push_str(&" ".repeat(width.saturating_sub(ch_count)));
+                ch_count = 0;
+                line_start = i;
+                y += 1;
+                height -= 1;
+                if height > 0 {
+                    res.push_str(&format!("{}{}", cursor::Goto(x, y), left));
+                } else {
+                    break;
                 }
             }
+            if ch_count == 0 {
+                text_start = i;
+            }
+            ch_count += ch_width;
+        }
+        if height > 0 && ch_count > 0 {
+            let (before, after) = str_utils::get_padding(ch_count, width, align);
+            res.push_str(&format!("{}{}{}{}", &content[line_start..text_start],
+                                  " ".repeat(before), &content[text_start..],
+                                  " ".repeat(after)));
         }
         // Reset all styles at the end
-        res.push_str(&style::Reset.to_string());
+        res.push_str(style::Reset.as_ref());
+        res
+    }
+
+    // Write sprite rows with Goto sequences. Missing rows are filled with spaces. Characters of
+    // right-to-left rows are written in reverse order.
+    fn prepare_sprite(rows: &[String], width: usize, height: usize, x: u16, y: u16,
+                      padding: CellPadding, rtl: bool) -> String {
+        // Top padding rows get escape sequences of the first row, so they have the same
+        // background color.
+        let (_, first) = str_utils::split_align_prefix(rows.first().map_or("", |r| r.as_str()));
+        let lead = &first[..str_utils::get_grapheme_offset(first, 0)];
+        let mut res = String::with_capacity(width * height * 2);
+        for row in 0..height {
+            let line = if row < padding.top {
+                format!("{}{}", lead, " ".repeat(width))
+            } else {
+                let text = rows.get(row - padding.top).map_or("", |r| r.as_str());
+                let aligned = Cell::align_row(text, width - padding.left, rtl);
+                let text_start = str_utils::get_grapheme_offset(&aligned, 0);
+                format!("{}{}{}", &aligned[..text_start], " ".repeat(padding.left),
+                        &aligned[text_start..])
+            };
+            res.push_str(&format!("{}{}{}", cursor::Goto(x, y + row as u16), line,
+                                  style::Reset));
+        }
         res
     }
+
+    // Truncate or pad sprite row to `width` according to alignment prefix. Escape sequences
+    // before the first visible character are written before alignment spaces. Right-to-left row
+    // is reversed and right-aligned unless it is centered.
+    fn align_row(row: &str, width: usize, rtl: bool) -> String {
+        let (align, row) = str_utils::split_align_prefix(row);
+        let row = if str_utils::get_str_len(row) > width {
+            str_utils::get_str_range(row, 0, width)
+        } else {
+            row
+        };
+        let reversed;
+        let (align, row) = if rtl {
+            reversed = str_utils::reverse_str(row);
+            (if align == TextAlign::Center { align } else { TextAlign::Right }, reversed.as_str())
+        } else {
+            (align, row)
+        };
+        // Truncated row can be narrower than `width` if wide character doesn't fit.
+        let len = str_utils::get_str_len(row);
+        let text_start = str_utils::get_grapheme_offset(row, 0);
+        let (before, after) = str_utils::get_padding(len, width, align);
+        format!("{}{}{}{}", &row[..text_start], " ".repeat(before), &row[text_start..],
+                " ".repeat(after))
+    }
 }
+
@@ -1,15 +1,15 @@
+use std::borrow::Cow;
 use std::rc::Rc;
-use std::collections::HashSet;
-use std::slice::Iter;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use termion::color;
 
 use crate::game::Position;
 use crate::board::{ResourceTable, CellUpdates};
 use crate::cell::Cell;
+use crate::chars;
+use crate::color::Color;
 
-const DEFAULT_UPDATES_CAPACITY: usize = 16;
-
+#[derive(Clone)]
 pub(crate) struct CellGrid {
     _rows: usize,
     columns: usize,
@@ -18,7 +18,21 @@ pub(crate) struct CellGrid {
     cells: Vec<Cell>,
     resources: Rc<Option<ResourceTable>>,
     update_all: bool,
-    updates: HashSet<usize>,
+    /// Updated cells. Sorted set is used to iterate cells in row-major order.
+    updates: BTreeSet<usize>,
+    /// If `false`, updated cells are not tracked and all cells are compared with rendered ones.
+    track_updates: bool,
+    /// Background colors set by user.
+    backgrounds: HashMap<usize, Color>,
+    /// Highlighted cells. Highlight color has priority over user background color.
+    highlights: HashMap<usize, Color>,
+    /// Background color of all cells.
+    base_background: Option<Color>,
+    /// Cell under mouse pointer and its background color.
+    hover: Option<(usize, Color)>,
+    disabled: HashSet<usize>,
+    /// Cells as they were written to the terminal last time. `None` if cell was not written yet.
+    rendered: Vec<Option<Cell>>,
 }
 
 impl CellGrid {
@@ -32,12 +46,19 @@ impl CellGrid {
             cells: vec![Cell::Empty; columns * rows],
             resources,
             update_all: true,
-            updates: HashSet::with_capacity(DEFAULT_UPDATES_CAPACITY),
+            updates: BTreeSet::new(),
+            track_updates: true,
+            backgrounds: HashMap::new(),
+            highlights: HashMap::new(),
+            base_background: None,
+            hover: None,
+            disabled: HashSet::new(),
+            rendered: vec![None; columns * rows],
         }
     }
 
-    pub(crate) fn init_from_vec(&mut self, cells: &Vec<Cell>) {
-        self.cells = cells.clone();
+    pub(crate) fn init_from_vec(&mut self, cells: &[Cell]) {
+        self.cells = cells.to_vec();
         self.update_all = true;
     }
 
@@ -48,26 +69,97 @@ impl CellGrid {
         self.update_all = true;
     }
 
+    // Change grid size. Cells in the overlapping region are kept.
+    pub(crate) fn resize(&mut self, columns: usize, rows: usize) {
+        let mut cells = vec![Cell::Empty; columns * rows];
+        let mut backgrounds = HashMap::new();
+        let mut highlights = HashMap::new();
+        let mut disabled = HashSet::new();
+        for (i, cell) in self.cells.iter().enumerate() {
+            let Position(x, y) = self.get_position(i);
+            if x < columns && y < rows {
+                let new_idx = y * columns + x;
+                cells[new_idx] = cell.clone();
+                if let Some(&c) = self.backgrounds.get(&i) {
+                    backgrounds.insert(new_idx, c);
+                }
+                if let Some(&c) = self.highlights.get(&i) {
+                    highlights.insert(new_idx, c);
+                }
+                if self.disabled.contains(&i) {
+                    disabled.insert(new_idx);
+                }
+            }
+        }
+        self._rows = rows;
+        self.columns = columns;
+        self.cells = cells;
+        self.backgrounds = backgrounds;
+        self.highlights = highlights;
+        self.disabled = disabled;
+        self.hover = None;
+        self.rendered = vec![None; columns * rows];
+        self.updates.clear();
+        self.update_all = true;
+    }
+
     pub(crate) fn has_updates(&self) -> bool {
-        self.update_all || self.updates.len() > 0
+        self.update_all || !self.track_updates || !self.updates.is_empty()
+    }
+
+    // Enable or disable updated cells tracking.
+    pub(crate) fn set_track_updates(&mut self, track: bool) {
+        if track && !self.track_updates {
+            // Cells changed while tracking was disabled are unknown, check all of them.
+            self.updates.extend(0..self.cells.len());
+        }
+        if !track {
+            self.updates.clear();
+        }
+        self.track_updates = track;
     }
 
     pub(crate) fn need_update_all(&self) -> bool {
         self.update_all
     }
 
-    pub(crate) fn iter(&self) -> Iter<Cell> {
-        self.cells.iter()
+    // Get cells as they are set by user and cursor.
+    pub(crate) fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub(crate) fn get_position(&self, idx: usize) -> Position {
+        Position(idx % self.columns, idx / self.columns)
+    }
+
+    // Iterate cells with background colors applied.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Cow<'_, Cell>> {
+        (0..self.cells.len()).map(move |i| self.get_cell(i))
     }
 
-    pub(crate) fn updated_iter(&self) -> UpdatedIterator {
+    // Iterate updated cells by rows. Cells which look the same as on the screen are skipped. If
+    // updates are not tracked, all cells are checked.
+    pub(crate) fn updated_iter(&self) -> UpdatedIterator<'_> {
+        let updates: Box<dyn Iterator<Item = usize>> = if self.track_updates {
+            Box::new(self.updates.iter().cloned())
+        } else {
+            Box::new(0..self.cells.len())
+        };
         UpdatedIterator {
-            cells: &self.cells,
-            updates: self.updates.iter().cloned().collect()
+            grid: self,
+            updates
         }
     }
 
-    pub(crate) fn update_complete(&mut self) {
+    // Mark updates as written. `all_written` is `true` if all cells were written.
+    pub(crate) fn update_complete(&mut self, all_written: bool) {
+        if all_written || !self.track_updates {
+            self.rendered = self.iter().map(|c| Some(c.into_owned())).collect();
+        } else {
+            for &idx in &self.updates {
+                self.rendered[idx] = Some(self.get_cell(idx).into_owned());
+            }
+        }
         self.updates.clear();
         self.update_all = false;
     }
@@ -76,7 +168,7 @@ impl CellGrid {
         for (cell, cell_pos) in updates {
             let pos = self.get_cell_pos(*cell_pos);
             self.cells[pos] = cell.clone();
-            self.updates.insert(pos);
+            self.mark_updated(pos);
         }
     }
 
@@ -84,36 +176,148 @@ impl CellGrid {
     pub(crate) fn update_cell(&mut self, cell: Cell, pos: Position) {
         let pos = self.get_cell_pos(pos);
         self.cells[pos] = cell.clone();
-        self.updates.insert(pos);
+        self.mark_updated(pos);
     }
 
     // This method is for Cursor only.
-    pub(crate) fn update_cell_bg_color(&mut self, pos: Position, bg_color: color::Rgb) -> Cell {
+    pub(crate) fn update_cell_bg_color(&mut self, pos: Position, bg_color: Color) -> Cell {
         let pos = self.get_cell_pos(pos);
         let original_cell = self.cells[pos].clone();
         self.cells[pos] = original_cell.with_bg_color(self.cell_width, self.cell_height,
                                                       Rc::clone(&self.resources), bg_color);
-        self.updates.insert(pos);
+        self.mark_updated(pos);
         original_cell
     }
 
+    pub(crate) fn set_background(&mut self, pos: Position, bg_color: Option<Color>) {
+        let pos = self.get_cell_pos(pos);
+        match bg_color {
+            Some(c) => self.backgrounds.insert(pos, c),
+            None => self.backgrounds.remove(&pos)
+        };
+        self.mark_updated(pos);
+    }
+
+    pub(crate) fn set_highlight(&mut self, pos: Position, color: Color) {
+        let pos = self.get_cell_pos(pos);
+        self.highlights.insert(pos, color);
+        self.mark_updated(pos);
+    }
+
+    pub(crate) fn remove_highlight(&mut self, pos: Position) {
+        let pos = self.get_cell_pos(pos);
+        if self.highlights.remove(&pos).is_some() {
+            self.mark_updated(pos);
+        }
+    }
+
+    // Remove highlighting from all cells.
+    pub(crate) fn clear_highlights(&mut self) {
+        let highlights: Vec<usize> = self.highlights.drain().map(|(idx, _)| idx).collect();
+        for idx in highlights {
+            self.mark_updated(idx);
+        }
+    }
+
+    // Set background color of all cells. It has the lowest priority.
+    pub(crate) fn set_base_background(&mut self, bg_color: Option<Color>) {
+        self.base_background = bg_color;
+        self.update_all = true;
+    }
+
+    // Set cell under mouse pointer. Previous hover cell is restored.
+    pub(crate) fn set_hover(&mut self, hover: Option<(Position, Color)>) {
+        if let Some((old, _)) = self.hover {
+            self.mark_updated(old);
+        }
+        self.hover = hover.map(|(pos, c)| (self.get_cell_pos(pos), c));
+        if let Some((new, _)) = self.hover {
+            self.mark_updated(new);
+        }
+    }
+
+    // Mark cell as damaged on the screen, so it is written again even if it isn't changed.
+    pub(crate) fn invalidate(&mut self, pos: Position) {
+        let pos = self.get_cell_pos(pos);
+        self.rendered[pos] = None;
+        self.mark_updated(pos);
+    }
+
+    pub(crate) fn set_disabled(&mut self, pos: Position, disabled: bool) {
+        let pos = self.get_cell_pos(pos);
+        if disabled {
+            self.disabled.insert(pos);
+        } else {
+            self.disabled.remove(&pos);
+        }
+        self.mark_updated(pos);
+    }
+
+    pub(crate) fn is_disabled(&self, pos: Position) -> bool {
+        self.disabled.contains(&self.get_cell_pos(pos))
+    }
+
+    // Get the nearest enabled cell position. Return None if all cells are disabled.
+    pub(crate) fn get_nearest_enabled(&self, pos: Position) -> Option<Position> {
+        if !self.is_disabled(pos) {
+            return Some(pos)
+        }
+        (0..self.cells.len())
+            .filter(|i| !self.disabled.contains(i))
+            .map(|i| self.get_position(i))
+            .min_by_key(|p| {
+                (p.0 as isize - pos.0 as isize).abs() + (p.1 as isize - pos.1 as isize).abs()
+            })
+    }
+
+    // Get cell with background color applied. Disabled cells are filled with solid block. Hover
+    // color has priority over highlight color, then user cell background and base background.
+    // Cursor background is a part of the cell content, so it is added after these colors and
+    // overrides them.
+    fn get_cell(&self, idx: usize) -> Cow<'_, Cell> {
+        if self.disabled.contains(&idx) {
+            return Cow::Owned(Cell::Char(chars::FULL_BLOCK))
+        }
+        let bg_color = match self.hover {
+            Some((pos, c)) if pos == idx => Some(c),
+            _ => self.highlights.get(&idx)
+                .or_else(|| self.backgrounds.get(&idx))
+                .cloned()
+                .or(self.base_background)
+        };
+        match bg_color {
+            Some(c) => Cow::Owned(self.cells[idx].with_bg_color(
+                self.cell_width, self.cell_height, Rc::clone(&self.resources), c)),
+            None => Cow::Borrowed(&self.cells[idx])
+        }
+    }
+
+    fn mark_updated(&mut self, idx: usize) {
+        if self.track_updates {
+            self.updates.insert(idx);
+        }
+    }
+
     fn get_cell_pos(&self, pos: Position) -> usize {
         pos.1 * self.columns + pos.0
     }
 }
 
 pub(crate) struct UpdatedIterator<'a> {
-    cells: &'a Vec<Cell>,
-    updates: Vec<usize>
+    grid: &'a CellGrid,
+    updates: Box<dyn Iterator<Item = usize> + 'a>
 }
 
 impl <'a> Iterator for UpdatedIterator<'a> {
-  type Item = (&'a Cell, usize);
+  type Item = (Cow<'a, Cell>, usize);
 
-  fn next(&mut self) -> Option<(&'a Cell, usize)> {
-      match self.updates.pop() {
-          Some(idx) => Some((&self.cells[idx], idx)),
-          None => None
+  fn next(&mut self) -> Option<(Cow<'a, Cell>, usize)> {
+      for idx in &mut self.updates {
+          let cell = self.grid.get_cell(idx);
+          if self.grid.rendered[idx].as_ref() != Some(cell.as_ref()) {
+              return Some((cell, idx))
+          }
       }
+      None
   }
 }
@@ -23,3 +23,73 @@ pub(crate) const SINGLE_BORDER_HOR_LINE: char = '─';
 pub(crate) const SINGLE_BORDER_VERT_LINE: char = '│';
 
 pub(crate) const SINGLE_BORDER_CROSS: char = '┼';
+
+pub(crate) const FULL_BLOCK: char = '█';
+
+pub(crate) const UPPER_HALF_BLOCK: char = '▀';
+
+/// Characters of the board border. Joins connect border with single cell border lines.
+pub(crate) struct BorderChars {
+    pub(crate) hor_line: char,
+    pub(crate) vert_line: char,
+    pub(crate) top_left: char,
+    pub(crate) top_right: char,
+    pub(crate) bottom_left: char,
+    pub(crate) bottom_right: char,
+    pub(crate) join_left: char,
+    pub(crate) join_right: char,
+    pub(crate) join_up: char,
+    pub(crate) join_down: char,
+    /// Left side of the title in horizontal line.
+    pub(crate) title_start: char,
+    /// Right side of the title in horizontal line.
+    pub(crate) title_end: char,
+}
+
+pub(crate) const SINGLE_BORDER: BorderChars = BorderChars {
+    hor_line: SINGLE_BORDER_HOR_LINE,
+    vert_line: SINGLE_BORDER_VERT_LINE,
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    join_left: '├',
+    join_right: '┤',
+    join_up: '┬',
+    join_down: '┴',
+    title_start: '┤',
+    title_end: '├',
+};
+
+pub(crate) const DOUBLE_BORDER: BorderChars = BorderChars {
+    hor_line: DOUBLE_BORDER_HOR_LINE,
+    vert_line: DOUBLE_BORDER_VERT_LINE,
+    top_left: DOUBLE_BORDER_TOP_LEFT,
+    top_right: DOUBLE_BORDER_TOP_RIGHT,
+    bottom_left: DOUBLE_BORDER_BOTTOM_LEFT,
+    bottom_right: DOUBLE_BORDER_BOTTOM_RIGHT,
+    join_left: DOUBLE_BORDER_JOIN_LEFT,
+    join_right: DOUBLE_BORDER_JOIN_RIGHT,
+    join_up: DOUBLE_BORDER_JOIN_UP,
+    join_down: DOUBLE_BORDER_JOIN_DOWN,
+    title_start: '╡',
+    title_end: '╞',
+};
+
+pub(crate) const HEAVY_BORDER: BorderChars = BorderChars {
+    hor_line: '━',
+    vert_line: '┃',
+    top_left: '┏',
+    top_right: '┓',
+    bottom_left: '┗',
+    bottom_right: '┛',
+    join_left: '┠',
+    join_right: '┨',
+    join_up: '┯',
+    join_down: '┷',
+    title_start: '┫',
+    title_end: '┣',
+};
+
+/// Filled part of the progress bar.
+pub(crate) const BAR_FILLED: char = '█';
@@ -0,0 +1,325 @@
+//! Terminal colors.
+
+use std::borrow::Cow;
+use std::env;
+#[cfg(feature = "termion")]
+use std::fmt;
+
+#[cfg(feature = "termion")]
+use termion::color;
+
+#[cfg(feature = "termion")]
+use crate::ansi;
+
+/// Terminal color.
+///
+/// All color parameters of this crate take this type. Many terminals show 256 colors more
+/// reliably than truecolor.
+///
+/// With `termion` feature, truecolor `termion::color::Rgb`, 256-color
+/// `termion::color::AnsiValue` and 16 named termion colors (`termion::color::Red` etc.) can be
+/// converted to it with `From` trait. It also implements `termion::color::Color`, so it can be
+/// used with `termion::color::Fg` and `termion::color::Bg`.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::Color;
+/// use termion::color;
+///
+/// assert_eq!(Color::from(color::Rgb(0, 0, 200)), Color::Rgb(0, 0, 200));
+/// assert_eq!(Color::from(color::Red), Color::Ansi(1));
+/// assert_eq!(color::Bg(Color::Ansi(21)).to_string(), color::Bg(color::AnsiValue(21)).to_string());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    /// Truecolor (24-bit) color.
+    Rgb(u8, u8, u8),
+    /// Color from 256-color palette. Values 0-15 are 16 named colors.
+    Ansi(u8),
+}
+
+impl Color {
+    /// Converts color to the nearest color supported in color `mode`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gameboard::Color;
+    /// use gameboard::color::ColorMode;
+    ///
+    /// let c = Color::Rgb(250, 10, 10);
+    /// assert_eq!(c.to_mode(ColorMode::TrueColor), c);
+    /// assert_eq!(c.to_mode(ColorMode::Ansi256), Color::Ansi(196));
+    /// assert_eq!(c.to_mode(ColorMode::Ansi16), Color::Ansi(9));
+    /// assert_eq!(Color::Ansi(196).to_mode(ColorMode::Ansi16), Color::Ansi(9));
+    /// ```
+    pub fn to_mode(self, mode: ColorMode) -> Color {
+        match (self, mode) {
+            (_, ColorMode::TrueColor) | (Color::Ansi(_), ColorMode::Ansi256) => self,
+            (Color::Rgb(r, g, b), ColorMode::Ansi256) => Color::Ansi(rgb_to_ansi256(r, g, b)),
+            (Color::Rgb(r, g, b), ColorMode::Ansi16) => Color::Ansi(rgb_to_ansi16(r, g, b)),
+            (Color::Ansi(v), ColorMode::Ansi16) => Color::Ansi(ansi256_to_ansi16(v)),
+        }
+    }
+}
+
+#[cfg(feature = "termion")]
+impl color::Color for Color {
+    fn write_fg(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", ansi::color::Fg(*self))
+    }
+
+    fn write_bg(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", ansi::color::Bg(*self))
+    }
+}
+
+#[cfg(feature = "termion")]
+impl From<color::Rgb> for Color {
+    fn from(c: color::Rgb) -> Self {
+        Color::Rgb(c.0, c.1, c.2)
+    }
+}
+
+#[cfg(feature = "termion")]
+impl From<color::AnsiValue> for Color {
+    fn from(c: color::AnsiValue) -> Self {
+        Color::Ansi(c.0)
+    }
+}
+
+macro_rules! from_named_color {
+    ($($name:ident = $value:expr),*) => {
+        $(
+            #[cfg(feature = "termion")]
+            impl From<color::$name> for Color {
+                fn from(_: color::$name) -> Self {
+                    Color::Ansi($value)
+                }
+            }
+        )*
+    };
+}
+
+from_named_color!(Black = 0, Red = 1, Green = 2, Yellow = 3, Blue = 4, Magenta = 5, Cyan = 6,
+                  White = 7, LightBlack = 8, LightRed = 9, LightGreen = 10, LightYellow = 11,
+                  LightBlue = 12, LightMagenta = 13, LightCyan = 14, LightWhite = 15);
+
+/// Colors supported by terminal.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorMode {
+    /// Truecolor (24-bit). Colors are output as is.
+    TrueColor,
+    /// 256-color palette. Rgb colors are converted to the nearest palette color.
+    Ansi256,
+    /// 16 named colors. Rgb and 256-color palette colors are converted to the nearest named
+    /// color.
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Detects color mode of the current terminal using `COLORTERM` and `TERM` environment
+    /// variables.
+    ///
+    /// See [`from_env_values`] for details.
+    ///
+    /// [`from_env_values`]: #method.from_env_values
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").ok();
+        let term = env::var("TERM").ok();
+        ColorMode::from_env_values(colorterm.as_deref(), term.as_deref())
+    }
+
+    /// Returns color mode for `COLORTERM` and `TERM` environment variable values.
+    ///
+    /// `COLORTERM` set to `truecolor` or `24bit` means truecolor terminal. Otherwise `TERM`
+    /// containing `256color` means 256-color terminal and any other `TERM` means 16-color
+    /// terminal. If `TERM` isn't set, terminal is unknown (for example, on Windows) and
+    /// truecolor is assumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gameboard::color::ColorMode;
+    ///
+    /// assert_eq!(ColorMode::from_env_values(Some("truecolor"), Some("xterm-256color")),
+    ///            ColorMode::TrueColor);
+    /// assert_eq!(ColorMode::from_env_values(None, Some("xterm-256color")), ColorMode::Ansi256);
+    /// assert_eq!(ColorMode::from_env_values(None, Some("linux")), ColorMode::Ansi16);
+    /// assert_eq!(ColorMode::from_env_values(None, None), ColorMode::TrueColor);
+    /// ```
+    pub fn from_env_values(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        match (colorterm, term) {
+            (Some("truecolor"), _) | (Some("24bit"), _) | (_, None) => ColorMode::TrueColor,
+            (_, Some(term)) if term.contains("256color") => ColorMode::Ansi256,
+            _ => ColorMode::Ansi16,
+        }
+    }
+}
+
+// Intensity levels of 6x6x6 color cube of 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Default xterm values of 16 named colors.
+const NAMED_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// Returns the nearest 256-color palette index for Rgb color.
+///
+/// Only the color cube (16-231) and the grayscale ramp (232-255) are used, because named colors
+/// (0-15) differ between terminals.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::color::rgb_to_ansi256;
+///
+/// assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+/// assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+/// assert_eq!(rgb_to_ansi256(0, 0, 200), 20);
+/// assert_eq!(rgb_to_ansi256(100, 100, 100), 241);
+/// ```
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let cube_index = |v: u8| {
+        (0..CUBE_LEVELS.len()).min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs()).unwrap()
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_level = 8 + 10 * gray_index;
+
+    if distance((r, g, b), (gray_level, gray_level, gray_level)) < distance((r, g, b), cube) {
+        232 + gray_index
+    } else {
+        (16 + 36 * ri + 6 * gi + bi) as u8
+    }
+}
+
+/// Returns the nearest named color index (0-15) for Rgb color.
+///
+/// Default xterm values of named colors are used.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::color::rgb_to_ansi16;
+///
+/// assert_eq!(rgb_to_ansi16(10, 10, 10), 0);
+/// assert_eq!(rgb_to_ansi16(0, 0, 200), 4);
+/// assert_eq!(rgb_to_ansi16(250, 250, 240), 15);
+/// ```
+pub fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> u8 {
+    (0..NAMED_COLORS.len()).min_by_key(|&i| distance((r, g, b), NAMED_COLORS[i])).unwrap() as u8
+}
+
+// Return the nearest named color for 256-color palette index.
+fn ansi256_to_ansi16(value: u8) -> u8 {
+    match value {
+        0..=15 => value,
+        16..=231 => {
+            let v = value - 16;
+            rgb_to_ansi16(CUBE_LEVELS[(v / 36) as usize], CUBE_LEVELS[(v / 6 % 6) as usize],
+                          CUBE_LEVELS[(v % 6) as usize])
+        },
+        _ => {
+            let level = 8 + 10 * (value - 232);
+            rgb_to_ansi16(level, level, level)
+        },
+    }
+}
+
+// Squared distance between colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Converts colors in SGR escape sequences of `s` to color `mode`.
+///
+/// Extended colors (`38;2;r;g;b`, `38;5;n` and the same for background) are converted to the
+/// nearest colors supported in `mode`. In `ColorMode::Ansi16` mode basic color codes are written
+/// (`30`-`37` and `90`-`97` for foreground, `40`-`47` and `100`-`107` for background), because
+/// terminals without 256 colors may not support extended sequences at all. Other content is kept
+/// as is.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::color::{convert_colors, ColorMode};
+///
+/// let s = "\x1b[1;38;2;250;10;10;48;5;4mX\x1b[0m";
+/// assert_eq!(convert_colors(s, ColorMode::TrueColor), s);
+/// assert_eq!(convert_colors(s, ColorMode::Ansi256), "\x1b[1;38;5;196;48;5;4mX\x1b[0m");
+/// assert_eq!(convert_colors(s, ColorMode::Ansi16), "\x1b[1;91;44mX\x1b[0m");
+/// assert_eq!(convert_colors("\x1b[38;5;1;48;5;15m", ColorMode::Ansi16), "\x1b[31;107m");
+/// ```
+pub fn convert_colors(s: &str, mode: ColorMode) -> Cow<'_, str> {
+    if mode == ColorMode::TrueColor || !s.contains("\x1b[") {
+        return Cow::Borrowed(s)
+    }
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("\x1b[") {
+        res.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        // Parameters end with final byte.
+        match rest.find(|c: char| ('@'..='~').contains(&c)) {
+            Some(end) if rest.as_bytes()[end] == b'm' => {
+                res.push_str("\x1b[");
+                res.push_str(&convert_sgr_params(&rest[..end], mode));
+                res.push('m');
+                rest = &rest[end + 1..];
+            },
+            _ => res.push_str("\x1b["),
+        }
+    }
+    res.push_str(rest);
+    Cow::Owned(res)
+}
+
+// Convert extended color parameters of SGR sequence.
+fn convert_sgr_params(params: &str, mode: ColorMode) -> String {
+    let params: Vec<&str> = params.split(';').collect();
+    let mut res: Vec<String> = Vec::with_capacity(params.len());
+    let mut i = 0;
+    while i < params.len() {
+        let kind = params[i];
+        if kind == "38" || kind == "48" {
+            let parse = |n: usize| params.get(i + n).and_then(|v| v.parse::<u8>().ok());
+            let color = match params.get(i + 1) {
+                Some(&"2") => match (parse(2), parse(3), parse(4)) {
+                    (Some(r), Some(g), Some(b)) => Some((Color::Rgb(r, g, b), 5)),
+                    _ => None,
+                },
+                Some(&"5") => parse(2).map(|v| (Color::Ansi(v), 3)),
+                _ => None,
+            };
+            if let Some((color, len)) = color {
+                match color.to_mode(mode) {
+                    // Basic codes: 30-37 and 90-97 for foreground, 40-47 and 100-107 for
+                    // background.
+                    Color::Ansi(v) if mode == ColorMode::Ansi16 => {
+                        let base = if kind == "38" { 30 } else { 40 };
+                        let code = if v < 8 { base + v } else { base + 60 + v - 8 };
+                        res.push(code.to_string());
+                    },
+                    Color::Ansi(v) => res.push(format!("{};5;{}", kind, v)),
+                    Color::Rgb(r, g, b) => res.push(format!("{};2;{};{};{}", kind, r, g, b)),
+                }
+                i += len;
+                continue;
+            }
+        }
+        res.push(kind.to_string());
+        i += 1;
+    }
+    res.join(";")
+}
@@ -0,0 +1,201 @@
+//! Crossterm terminal backend.
+//!
+//! This module is available with `crossterm` feature only. It provides input stream and
+//! [`Terminal`] implementation based on [crossterm] crate. Use [`Game::new_crossterm`] to create
+//! game with this backend.
+//!
+//! Key and mouse events are converted to the same byte sequences terminal sends and parsed as
+//! usual input. Mouse events are encoded in SGR format, key modifiers of mouse events are ignored.
+//! Resize event is converted to the window size report (`ESC [ 8 ; rows ; columns t`), which is
+//! not a key, but makes the game check terminal size and redraw the screen without waiting for the
+//! next key. This backend doesn't require `termion` feature.
+//!
+//! [`Terminal`]: ../terminal/trait.Terminal.html
+//! [`Game::new_crossterm`]: ../game/struct.Game.html#method.new_crossterm
+//! [crossterm]: https://github.com/crossterm-rs/crossterm
+
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+                       MouseEvent, MouseEventKind};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::execute;
+
+use crate::terminal::Terminal;
+
+/// Input stream which reads key, mouse and resize events using crossterm.
+pub struct CrosstermInput {
+    /// Encoded key events not read yet.
+    buffer: Vec<u8>,
+    blocking: bool,
+}
+
+impl CrosstermInput {
+    /// Creates input stream which blocks until key is pressed.
+    ///
+    /// Use it with `Game::start`.
+    pub fn new() -> Self {
+        CrosstermInput {
+            buffer: Vec::new(),
+            blocking: true,
+        }
+    }
+
+    /// Creates non-blocking input stream.
+    ///
+    /// Read returns 0 bytes if no key was pressed. Use it with `Game::start_with_tick`.
+    pub fn new_async() -> Self {
+        CrosstermInput {
+            buffer: Vec::new(),
+            blocking: false,
+        }
+    }
+}
+
+impl Default for CrosstermInput {
+    fn default() -> Self {
+        CrosstermInput::new()
+    }
+}
+
+impl Read for CrosstermInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.buffer.is_empty() {
+            if !self.blocking && !event::poll(Duration::from_secs(0))? {
+                return Ok(0)
+            }
+            match event::read()? {
+                Event::Key(key) => encode_key(key, &mut self.buffer),
+                Event::Mouse(mouse) => encode_mouse(mouse, &mut self.buffer),
+                Event::Resize(width, height) => {
+                    write!(self.buffer, "\x1b[8;{};{}t", height, width)?;
+                },
+                _ => {}
+            }
+        }
+        let n = buf.len().min(self.buffer.len());
+        buf[..n].copy_from_slice(&self.buffer[..n]);
+        self.buffer.drain(..n);
+        Ok(n)
+    }
+}
+
+/// Terminal which uses crossterm.
+///
+/// Terminal is switched to alternate screen on creation and restored on drop. Raw mode is
+/// enabled by the game (see `Terminal::enable_raw_mode`) and disabled on drop as well.
+pub struct CrosstermTerminal<W: Write> {
+    output: W,
+}
+
+impl<W: Write> CrosstermTerminal<W> {
+    /// Creates new terminal.
+    pub fn new(mut output: W) -> io::Result<Self> {
+        execute!(output, EnterAlternateScreen)?;
+        Ok(CrosstermTerminal { output })
+    }
+}
+
+impl<W: Write> Drop for CrosstermTerminal<W> {
+    fn drop(&mut self) {
+        let _ = execute!(self.output, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl<W: Write> Write for CrosstermTerminal<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Terminal for CrosstermTerminal<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+}
+
+// Encode key event as the byte sequence which is parsed to the same key.
+fn encode_key(key: KeyEvent, buf: &mut Vec<u8>) {
+    if key.kind == KeyEventKind::Release {
+        return
+    }
+    let seq: &[u8] = match key.code {
+        KeyCode::Char(c) => {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && c.is_ascii_alphabetic() {
+                buf.push(c.to_ascii_lowercase() as u8 & 0x1f);
+            } else {
+                if key.modifiers.contains(KeyModifiers::ALT) {
+                    buf.push(0x1b);
+                }
+                let mut tmp = [0; 4];
+                buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+            }
+            return
+        },
+        KeyCode::Enter => b"\n",
+        KeyCode::Tab => b"\t",
+        KeyCode::Backspace => b"\x7f",
+        KeyCode::Esc => b"\x1b",
+        KeyCode::Null => b"\0",
+        KeyCode::Left => b"\x1b[D",
+        KeyCode::Right => b"\x1b[C",
+        KeyCode::Up => b"\x1b[A",
+        KeyCode::Down => b"\x1b[B",
+        KeyCode::Home => b"\x1b[H",
+        KeyCode::End => b"\x1b[F",
+        KeyCode::BackTab => b"\x1b[Z",
+        KeyCode::Insert => b"\x1b[2~",
+        KeyCode::Delete => b"\x1b[3~",
+        KeyCode::PageUp => b"\x1b[5~",
+        KeyCode::PageDown => b"\x1b[6~",
+        KeyCode::F(1) => b"\x1bOP",
+        KeyCode::F(2) => b"\x1bOQ",
+        KeyCode::F(3) => b"\x1bOR",
+        KeyCode::F(4) => b"\x1bOS",
+        KeyCode::F(5) => b"\x1b[15~",
+        KeyCode::F(6) => b"\x1b[17~",
+        KeyCode::F(7) => b"\x1b[18~",
+        KeyCode::F(8) => b"\x1b[19~",
+        KeyCode::F(9) => b"\x1b[20~",
+        KeyCode::F(10) => b"\x1b[21~",
+        KeyCode::F(11) => b"\x1b[23~",
+        KeyCode::F(12) => b"\x1b[24~",
+        _ => b""
+    };
+    buf.extend_from_slice(seq);
+}
+
+// Encode mouse event as SGR mouse sequence: ESC [ < Cb ; Cx ; Cy M (press) or m (release).
+fn encode_mouse(mouse: MouseEvent, buf: &mut Vec<u8>) {
+    let button = |button| match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    };
+    let (cb, last) = match mouse.kind {
+        MouseEventKind::Down(b) => (button(b), 'M'),
+        MouseEventKind::Up(b) => (button(b), 'm'),
+        // Motion flag is 32.
+        MouseEventKind::Drag(b) => (button(b) + 32, 'M'),
+        MouseEventKind::Moved => (35, 'M'),
+        MouseEventKind::ScrollUp => (64, 'M'),
+        MouseEventKind::ScrollDown => (65, 'M'),
+        _ => return
+    };
+    // Crossterm coordinates are 0-based, terminal sends 1-based ones.
+    let _ = write!(buf, "\x1b[<{};{};{}{}", cb, mouse.column + 1, mouse.row + 1, last);
+}
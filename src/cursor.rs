@@ -1,44 +1,84 @@
 //! Simple cursor implementation.
 //!
 //! You don't have to use this module. This implementation is very simple. Cursor handles 4 base
-//! movements and marks current position with background color. If you need more sophisticated
-//! cursor behavior, implement your own cursor.
+//! movements and marks current position with background color. It can also jump to the row ends
+//! or by page (see [`get_direction_with_jumps`]). Optionally it can select a path of cells (see
+//! [`Cursor::set_selection`]). If you need more sophisticated cursor behavior, implement your own
+//! cursor.
+//!
+//! [`get_direction_with_jumps`]: fn.get_direction_with_jumps.html
+//! [`Cursor::set_selection`]: struct.Cursor.html#method.set_selection
 
-use termion::color;
-use termion::event::Key;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 use crate::cell::Cell;
+use crate::color::Color;
 use crate::board::CellUpdates;
 use crate::cell_grid::CellGrid;
 use crate::game::Position;
+use crate::input::Key;
+
+/// Default cursor background color.
+const DEFAULT_BACKGROUND: Color = Color::Rgb(0, 0, 200);
+/// Default background color of selected cells.
+const DEFAULT_SELECTION_BACKGROUND: Color = Color::Rgb(0, 120, 120);
 
-/// Result of handling key press be cursor.
+/// Result of handling key press by cursor or message dialog.
 pub(crate) enum KeyHandleResult {
     /// Key not handled.
     NotHandled,
     /// Key handled, but position is not changed. No need to handle this key.
     Consumed,
     /// Cursor is moved to new position.
-    NewPosition(Position)
+    NewPosition(Position),
+    /// Cursor can't be moved in this direction.
+    Blocked(Direction),
+    /// Dialog button with this index is pressed.
+    DialogResult(usize),
 }
 
 /// Cursor move direction.
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub enum Direction {
     Left = 0,
     Right,
     Up,
     Down,
+    /// Jump to the first cell in the row.
+    Home,
+    /// Jump to the last cell in the row.
+    End,
+    /// Jump up by the number of visible rows.
+    PageUp,
+    /// Jump down by the number of visible rows.
+    PageDown,
 }
 
+/// Callback which checks if cursor can enter the cell at position.
+pub type CanEnterFn = Rc<RefCell<dyn FnMut(Position) -> bool>>;
+
 /// Cursor structure.
 pub struct Cursor {
     original_cell: Cell,
-    background: color::Rgb,
+    background: Color,
     position: Position,
     wrap_around: bool,
+    blink: bool,
+    /// Cell is highlighted. It is `false` when blinking cursor is hidden.
+    visible: bool,
+    /// Cursor is hidden by user.
+    hidden: bool,
     get_direction: fn(key: Key) -> Option<Direction>,
+    can_enter: Option<CanEnterFn>,
     rows: usize,
     columns: usize,
+    /// Key handler for selection mode. Selection is disabled if it is `None`.
+    get_select_direction: Option<fn(key: Key) -> Option<Direction>>,
+    selection_background: Color,
+    /// Selected cells in selection order with their original content. The last cell is the
+    /// cursor cell, its original content is kept in `original_cell`.
+    selection: Vec<(Position, Cell)>,
 }
 
 impl Cursor {
@@ -54,8 +94,13 @@ impl Cursor {
     ///
     /// `get_direction` - pointer to key handler function (optional). This function should
     /// translate key into cursor move direction. Function must return `None` if key is not
-    /// handled. If function isn't provided the default function is used.
+    /// handled. If function isn't provided the default function is used. Use
+    /// [`get_direction_with_jumps`] to enable *Home*, *End*, *PageUp* and *PageDown* keys.
+    ///
+    /// [`get_direction_with_jumps`]: fn.get_direction_with_jumps.html
     /// ```
+    /// # use gameboard::Key;
+    /// # use gameboard::cursor::Direction;
     /// fn get_direction_default(key: Key) -> Option<Direction> {
     ///     match key {
     ///         Key::Char('a') | Key::Left => Some(Direction::Left),
@@ -71,114 +116,487 @@ impl Cursor {
     ///
     /// ```no_run
     /// use termion::color;
+    /// # use gameboard::{Cursor, Position};
     ///
     /// const START_POSITION: Position = Position(1, 1);
     /// let cursor = Cursor::new(color::Rgb(0, 0, 200), START_POSITION, true, None);
     /// ```
-    pub fn new(background: color::Rgb, position: Position, wrap_around: bool,
-               get_direction: Option<fn(key: Key) -> Option<Direction>>) -> Self {
+    pub fn new<C: Into<Color>>(background: C, position: Position, wrap_around: bool,
+                               get_direction: Option<fn(key: Key) -> Option<Direction>>) -> Self {
         let fn_ptr = match get_direction {
             Some(ptr) => ptr,
             None => get_direction_default
         };
         Cursor {
             original_cell: Cell::Empty,
-            background,
+            background: background.into(),
             position,
             wrap_around,
+            blink: false,
+            visible: false,
+            hidden: false,
             get_direction: fn_ptr,
+            can_enter: None,
             rows: 0,
             columns: 0,
+            get_select_direction: None,
+            selection_background: DEFAULT_SELECTION_BACKGROUND,
+            selection: Vec::new(),
         }
     }
 
+    /// Sets background color of the cell where cursor is placed.
+    ///
+    /// Use `Game::set_cursor_color` to change color of the cursor which is already added to
+    /// the board.
+    pub fn set_background<C: Into<Color>>(&mut self, background: C) {
+        self.background = background.into();
+    }
+
+    /// Enables or disables cursor blinking.
+    ///
+    /// Blinking cursor highlighting is turned on and off every tick. Blinking works only if game
+    /// was started with `Game::start_with_tick`, otherwise cursor is always shown.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use termion::color;
+    /// # use gameboard::{Cursor, Position};
+    ///
+    /// let mut cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), true, None);
+    /// cursor.set_blink(true);
+    /// ```
+    pub fn set_blink(&mut self, blink: bool) {
+        self.blink = blink;
+    }
+
+    /// Shows or hides cursor.
+    ///
+    /// Hidden cursor doesn't highlight the cell, but it keeps its position and still can be
+    /// moved. Cursor is visible by default. Use `Game::set_cursor_visible` to show or hide the
+    /// cursor which is already added to the board.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.hidden = !visible;
+    }
+
+    /// Sets callback which allows or forbids cursor to enter the cell.
+    ///
+    /// Callback is called with the new cursor position before cursor is moved by key or mouse
+    /// click. If it returns `false`, cursor stays at the current position. Unlike disabled
+    /// cells, forbidden cells are not skipped. Use `None` to remove callback.
+    ///
+    /// Callback must not change the board or the game. If callback is already running (for
+    /// example, it caused cursor movement itself), the move is forbidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use termion::color;
+    /// # use gameboard::{Cursor, Position};
+    ///
+    /// // Cursor can't leave the first row.
+    /// let mut cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), true, None);
+    /// cursor.set_can_enter(Some(Rc::new(RefCell::new(|pos: Position| pos.1 == 0))));
+    /// ```
+    pub fn set_can_enter(&mut self, can_enter: Option<CanEnterFn>) {
+        self.can_enter = can_enter;
+    }
+
+    /// Enables or disables selection mode.
+    ///
+    /// `get_select_direction` translates key into cursor move direction the same way as
+    /// `get_direction` in [`Cursor::new`], but cursor moved by this key extends the selection.
+    /// Usually it is the same movement key with modifier, for example *Shift* + *W*. Keys are
+    /// checked by `get_direction` first. Use `None` to disable selection mode. Selection mode is
+    /// disabled by default.
+    ///
+    /// Selection is a path of cells. It starts at the cursor position (anchor) and every
+    /// selecting move adds the new cursor cell to the end. Moving back to a selected cell removes
+    /// all cells selected after it, so the path can be shortened by moving back. Path isn't
+    /// necessarily contiguous: disabled cells are skipped and cursor can wrap around, so
+    /// neighbor cells in the path may be not adjacent on the board. Any other cursor move (by
+    /// key, mouse click or because the cell was disabled) clears the selection.
+    ///
+    /// Selected cells are shown with selection background, cursor cell is shown with cursor
+    /// background. Use `Game::selection` to get selected cells.
+    ///
+    /// [`Cursor::new`]: #method.new
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use termion::color;
+    /// # use gameboard::{Cursor, Key, Position};
+    /// # use gameboard::cursor::Direction;
+    ///
+    /// // Shift + WASD select cells.
+    /// fn get_select_direction(key: Key) -> Option<Direction> {
+    ///     match key {
+    ///         Key::Char('A') => Some(Direction::Left),
+    ///         Key::Char('S') => Some(Direction::Down),
+    ///         Key::Char('W') => Some(Direction::Up),
+    ///         Key::Char('D') => Some(Direction::Right),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// let mut cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// cursor.set_selection(Some(get_select_direction));
+    /// ```
+    pub fn set_selection(&mut self,
+                         get_select_direction: Option<fn(key: Key) -> Option<Direction>>) {
+        self.get_select_direction = get_select_direction;
+    }
+
+    /// Sets background color of selected cells.
+    pub fn set_selection_background<C: Into<Color>>(&mut self, background: C) {
+        self.selection_background = background.into();
+    }
+
     pub(crate) fn init(&mut self, rows: usize, columns: usize, grid: &mut CellGrid) {
         self.rows = rows;
         self.columns = columns;
-        self.original_cell = grid.update_cell_bg_color(self.position, self.background);
+        self.position = Position(self.position.0.min(columns - 1), self.position.1.min(rows - 1));
+        // Cursor can't be placed at disabled cell.
+        if let Some(pos) = grid.get_nearest_enabled(self.position) {
+            self.position = pos;
+        }
+        self.highlight(grid);
     }
 
-    pub(crate) fn handle_key(&mut self, key: Key, grid: &mut CellGrid) -> KeyHandleResult {
-        match (self.get_direction)(key) {
-            Some(Direction::Left) => self.left(grid),
-            Some(Direction::Right) => self.right(grid),
-            Some(Direction::Up) => self.up(grid),
-            Some(Direction::Down) => self.down(grid),
+    // Handle cursor key. `page` is the number of visible rows.
+    pub(crate) fn handle_key(&mut self, key: Key, page: usize, grid: &mut CellGrid)
+            -> KeyHandleResult {
+        if let Some(direction) = (self.get_direction)(key) {
+            return self.move_to_direction(direction, page, grid, false)
+        }
+        match self.get_select_direction.and_then(|f| f(key)) {
+            Some(direction) => self.move_to_direction(direction, page, grid, true),
             None => KeyHandleResult::NotHandled
         }
     }
 
+    // Remove cursor and selection highlighting from the grid.
+    pub(crate) fn remove(&mut self, grid: &mut CellGrid) {
+        self.clear_selection(grid);
+        grid.update_cell(self.original_cell.clone(), self.position);
+    }
+
+    // Get selected cell positions in selection order.
+    pub(crate) fn get_selection(&self) -> Vec<Position> {
+        self.selection.iter().map(|&(pos, _)| pos).collect()
+    }
+
+    // Remove selection highlighting and clear selection. Cursor cell is not changed.
+    pub(crate) fn clear_selection(&mut self, grid: &mut CellGrid) {
+        for (pos, cell) in self.selection.drain(..) {
+            if pos != self.position {
+                grid.update_cell(cell, pos);
+            }
+        }
+    }
+
+    // Move cursor from disabled cell to the nearest enabled one.
+    pub(crate) fn leave_disabled(&mut self, grid: &mut CellGrid) {
+        if let Some(pos) = grid.get_nearest_enabled(self.position) {
+            if pos != self.position {
+                self.move_cursor(pos, grid, false);
+            }
+        }
+    }
+
+    // Highlight current cell with the current background color.
+    pub(crate) fn refresh(&mut self, grid: &mut CellGrid) {
+        // Restore original cell first, so the old color is not kept inside cell content.
+        grid.update_cell(self.original_cell.clone(), self.position);
+        self.highlight(grid);
+    }
+
+    // Toggle highlighting of blinking cursor. Called every tick.
+    pub(crate) fn blink(&mut self, grid: &mut CellGrid) {
+        if self.hidden {
+            return
+        }
+        if self.visible && self.blink {
+            grid.update_cell(self.original_cell.clone(), self.position);
+            self.visible = false;
+        } else if !self.visible {
+            // Blinking could be disabled while cursor was hidden, show it in this case as well.
+            self.highlight(grid);
+        }
+    }
+
+    pub(crate) fn get_position(&self) -> Position {
+        self.position
+    }
+
+    // Get cell content without cursor and selection highlighting. Return `None` if cell at
+    // position is not highlighted.
+    pub(crate) fn get_original_cell(&self, pos: Position) -> Option<&Cell> {
+        if pos == self.position {
+            return Some(&self.original_cell)
+        }
+        self.selection.iter().find(|&&(p, _)| p == pos).map(|(_, cell)| cell)
+    }
+
+    pub(crate) fn set_position(&mut self, position: Position, grid: &mut CellGrid)
+            -> KeyHandleResult {
+        if position == self.position || grid.is_disabled(position) || !self.can_enter(position) {
+            return KeyHandleResult::Consumed
+        }
+        self.move_cursor(position, grid, false)
+    }
+
     pub(crate) fn check_updates(&mut self, updates: &CellUpdates, grid: &mut CellGrid) {
         for (_, pos) in updates {
             if *pos == self.position {
                 // User updated the cell where cursor is placed.
                 // We need to add background color for this cell.
-                self.original_cell = grid.update_cell_bg_color(self.position, self.background);
-                break;
+                self.highlight(grid);
+            } else if let Some(entry) = self.selection.iter_mut().find(|(p, _)| p == pos) {
+                entry.1 = grid.update_cell_bg_color(*pos, self.selection_background);
             }
         }
     }
 
-    fn left(&mut self, grid: &mut CellGrid) -> KeyHandleResult {
-        let mut x = self.position.0;
-        if x == 0 && !self.wrap_around {
-            return KeyHandleResult::Consumed;
-        } else if x == 0 && self.wrap_around {
-            x = self.columns - 1;
-        } else {
-            x -= 1;
-        };
-        self.move_cursor(Position(x, self.position.1), grid)
+    // Move cursor to the next enabled cell in the direction. Disabled cells are skipped.
+    // Selection is extended if `select` is `true`.
+    fn move_to_direction(&mut self, direction: Direction, page: usize, grid: &mut CellGrid,
+                         select: bool) -> KeyHandleResult {
+        match direction {
+            Direction::Left | Direction::Right | Direction::Up | Direction::Down => {},
+            _ => return self.jump(direction, page, grid, select)
+        }
+        let mut pos = self.position;
+        loop {
+            pos = match self.get_next_position(pos, direction) {
+                // Board edge or we wrapped around to the cursor position.
+                None => return KeyHandleResult::Blocked(direction),
+                Some(p) if p == self.position => return KeyHandleResult::Blocked(direction),
+                Some(p) => p
+            };
+            if !grid.is_disabled(pos) {
+                if !self.can_enter(pos) {
+                    return KeyHandleResult::Blocked(direction)
+                }
+                return self.move_cursor(pos, grid, select)
+            }
+        }
     }
 
-    fn right(&mut self, grid: &mut CellGrid) -> KeyHandleResult {
-        let mut x = self.position.0;
-        if x == self.columns - 1 && !self.wrap_around {
-            return KeyHandleResult::Consumed;
-        } else if x == self.columns - 1 && self.wrap_around {
-            x = 0;
-        } else {
-            x += 1;
+    // Move cursor to the row end or by `page` rows. If the target cell is disabled, the nearest
+    // enabled cell towards the cursor is used. Jumps don't wrap around.
+    fn jump(&mut self, direction: Direction, page: usize, grid: &mut CellGrid, select: bool)
+            -> KeyHandleResult {
+        let Position(x, y) = self.position;
+        let page = page.max(1);
+        // Candidate positions from the farthest one.
+        let candidates: Vec<Position> = match direction {
+            Direction::Home => (0..x).map(|i| Position(i, y)).collect(),
+            Direction::End => (x + 1..self.columns).rev().map(|i| Position(i, y)).collect(),
+            Direction::PageUp => (y.saturating_sub(page)..y).map(|j| Position(x, j)).collect(),
+            Direction::PageDown =>
+                (y + 1..(y + page + 1).min(self.rows)).rev().map(|j| Position(x, j)).collect(),
+            _ => Vec::new()
         };
-        self.move_cursor(Position(x, self.position.1), grid)
+        match candidates.into_iter().find(|&pos| !grid.is_disabled(pos)) {
+            Some(pos) if self.can_enter(pos) => self.move_cursor(pos, grid, select),
+            _ => KeyHandleResult::Blocked(direction)
+        }
     }
 
-    fn up(&mut self, grid: &mut CellGrid) -> KeyHandleResult {
-        let mut y = self.position.1;
-        if y == 0 && !self.wrap_around {
-            return KeyHandleResult::Consumed;
-        } else if y == 0 && self.wrap_around {
-            y = self.rows - 1;
-        } else {
-            y -= 1;
-        };
-        self.move_cursor(Position(self.position.0, y), grid)
+    // Get the adjacent position in the direction. Return None at board edge if wrapping around
+    // is disabled.
+    fn get_next_position(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let Position(x, y) = pos;
+        let (last_x, last_y) = (self.columns - 1, self.rows - 1);
+        match direction {
+            Direction::Left if x > 0 => Some(Position(x - 1, y)),
+            Direction::Right if x < last_x => Some(Position(x + 1, y)),
+            Direction::Up if y > 0 => Some(Position(x, y - 1)),
+            Direction::Down if y < last_y => Some(Position(x, y + 1)),
+            _ if !self.wrap_around => None,
+            Direction::Left => Some(Position(last_x, y)),
+            Direction::Right => Some(Position(0, y)),
+            Direction::Up => Some(Position(x, last_y)),
+            Direction::Down => Some(Position(x, 0)),
+            _ => None,
+        }
     }
 
-    fn down(&mut self, grid: &mut CellGrid) -> KeyHandleResult {
-        let mut y = self.position.1;
-        if y == self.rows - 1 && !self.wrap_around {
-            return KeyHandleResult::Consumed;
-        } else if y == self.rows - 1 && self.wrap_around {
-            y = 0;
-        } else {
-            y += 1;
-        };
-        self.move_cursor(Position(self.position.0, y), grid)
+    // Ask user callback if cursor can be moved to position.
+    fn can_enter(&self, pos: Position) -> bool {
+        match self.can_enter {
+            // Callback is busy if it is called recursively.
+            Some(ref f) => f.try_borrow_mut().map_or(false, |mut f| f(pos)),
+            None => true
+        }
     }
 
-    fn move_cursor(&mut self, new_pos: Position, grid: &mut CellGrid) -> KeyHandleResult {
+    fn move_cursor(&mut self, new_pos: Position, grid: &mut CellGrid, select: bool)
+            -> KeyHandleResult {
         // Restore original content of current cell.
         grid.update_cell(self.original_cell.clone(), self.position);
+        if select {
+            self.extend_selection(new_pos, grid);
+        } else {
+            self.clear_selection(grid);
+        }
         // Move cursor to new position.
         self.position = new_pos;
         // Add bg color to new cell and get original cell from grid.
-        self.original_cell = grid.update_cell_bg_color(self.position, self.background);
+        self.highlight(grid);
         KeyHandleResult::NewPosition(self.position)
     }
+
+    // Add cursor cell to selection and select the new cursor cell. If the new cell is already
+    // selected, cells selected after it are removed from selection.
+    fn extend_selection(&mut self, new_pos: Position, grid: &mut CellGrid) {
+        if self.selection.is_empty() {
+            // Cursor position is the selection anchor.
+            self.selection.push((self.position, Cell::Empty));
+        }
+        let original = grid.update_cell_bg_color(self.position, self.selection_background);
+        if let Some(last) = self.selection.last_mut() {
+            last.1 = original;
+        }
+        if let Some(i) = self.selection.iter().position(|&(p, _)| p == new_pos) {
+            for (pos, cell) in self.selection.drain(i..) {
+                grid.update_cell(cell, pos);
+            }
+        }
+        // Original content of the cursor cell is saved by cursor.
+        self.selection.push((new_pos, Cell::Empty));
+    }
+
+    // Add bg color to current cell and save original cell. Hidden cursor saves original cell
+    // only.
+    fn highlight(&mut self, grid: &mut CellGrid) {
+        if self.hidden {
+            let Position(x, y) = self.position;
+            self.original_cell = grid.cells()[y * self.columns + x].clone();
+            self.visible = false;
+            return
+        }
+        self.original_cell = grid.update_cell_bg_color(self.position, self.background);
+        self.visible = true;
+    }
+}
+
+/// Cursor builder.
+///
+/// It is an alternative to [`Cursor::new`] with named parameters. By default cursor is blue, it is
+/// placed at `Position(0, 0)`, doesn't wrap around and uses the default key handler.
+///
+/// [`Cursor::new`]: struct.Cursor.html#method.new
+///
+/// # Examples
+///
+/// ```no_run
+/// use termion::color;
+/// # use gameboard::{CursorBuilder, Position};
+///
+/// let cursor = CursorBuilder::new()
+///     .background(color::Rgb(0, 150, 0))
+///     .position(Position(1, 1))
+///     .wrap_around(true)
+///     .build();
+/// ```
+pub struct CursorBuilder {
+    background: Color,
+    position: Position,
+    wrap_around: bool,
+    blink: bool,
+    get_direction: Option<fn(key: Key) -> Option<Direction>>,
+    get_select_direction: Option<fn(key: Key) -> Option<Direction>>,
+    selection_background: Color,
 }
 
-fn get_direction_default(key: Key) -> Option<Direction> {
+impl CursorBuilder {
+    /// Creates cursor builder.
+    pub fn new() -> Self {
+        CursorBuilder {
+            background: DEFAULT_BACKGROUND,
+            position: Position(0, 0),
+            wrap_around: false,
+            blink: false,
+            get_direction: None,
+            get_select_direction: None,
+            selection_background: DEFAULT_SELECTION_BACKGROUND,
+        }
+    }
+
+    /// Sets background color of the cell where cursor is placed.
+    pub fn background<C: Into<Color>>(mut self, background: C) -> Self {
+        self.background = background.into();
+        self
+    }
+
+    /// Sets cursor start position.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets if cursor should be wrapped around.
+    pub fn wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    /// Enables or disables cursor blinking. See [`Cursor::set_blink`].
+    ///
+    /// [`Cursor::set_blink`]: struct.Cursor.html#method.set_blink
+    pub fn blink(mut self, blink: bool) -> Self {
+        self.blink = blink;
+        self
+    }
+
+    /// Sets key handler function which translates key into cursor move direction. See
+    /// [`Cursor::new`].
+    ///
+    /// [`Cursor::new`]: struct.Cursor.html#method.new
+    pub fn get_direction(mut self, get_direction: fn(key: Key) -> Option<Direction>) -> Self {
+        self.get_direction = Some(get_direction);
+        self
+    }
+
+    /// Enables selection mode with key handler function. See [`Cursor::set_selection`].
+    ///
+    /// [`Cursor::set_selection`]: struct.Cursor.html#method.set_selection
+    pub fn selection(mut self, get_select_direction: fn(key: Key) -> Option<Direction>) -> Self {
+        self.get_select_direction = Some(get_select_direction);
+        self
+    }
+
+    /// Sets background color of selected cells.
+    pub fn selection_background<C: Into<Color>>(mut self, background: C) -> Self {
+        self.selection_background = background.into();
+        self
+    }
+
+    /// Creates cursor.
+    pub fn build(self) -> Cursor {
+        let mut cursor = Cursor::new(self.background, self.position, self.wrap_around,
+                                     self.get_direction);
+        cursor.set_blink(self.blink);
+        cursor.set_selection(self.get_select_direction);
+        cursor.set_selection_background(self.selection_background);
+        cursor
+    }
+}
+
+impl Default for CursorBuilder {
+    fn default() -> Self {
+        CursorBuilder::new()
+    }
+}
+
+/// Default key handler. Translates *WASD* and arrow keys into cursor move direction.
+pub fn get_direction_default(key: Key) -> Option<Direction> {
     match key {
         Key::Char('a') | Key::Left => Some(Direction::Left),
         Key::Char('s') | Key::Down => Some(Direction::Down),
@@ -187,3 +605,59 @@ fn get_direction_default(key: Key) -> Option<Direction> {
         _ => None,
     }
 }
+
+/// Key handler which extends [`get_direction_default`] with jump keys.
+///
+/// *Home* and *End* move cursor to the first and last cell in the row. *PageUp* and *PageDown*
+/// move cursor by the number of visible rows (board height if viewport is not set). Disabled
+/// cells are skipped, cursor stops at the nearest enabled cell towards its position.
+///
+/// [`get_direction_default`]: fn.get_direction_default.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Read;
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use gameboard::{cursor, Board, CursorBuilder, Game, InputListener, Terminal, Key, Position};
+/// use gameboard::test::{RecordingOutput, ScriptedInput};
+///
+/// struct App {
+///     moves: Vec<Position>,
+/// }
+///
+/// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+///     type Action = ();
+///
+///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+///
+///     fn cursor_moved(&mut self, position: Position, _game: &mut Game<R, W, Self>) {
+///         self.moves.push(position);
+///     }
+/// }
+///
+/// let cursor = CursorBuilder::new()
+///     .get_direction(cursor::get_direction_with_jumps)
+///     .build();
+/// let mut board = Board::new(5, 4, 1, 1, false, None);
+/// board.init_from_str(&" ".repeat(20), Some(cursor));
+/// board.set_viewport(Some((5, 2)));
+///
+/// let app = Rc::new(RefCell::new(App { moves: Vec::new() }));
+/// let input = ScriptedInput::new(&[Key::End, Key::PageDown, Key::PageDown, Key::Home]);
+/// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+/// game.init(board, None);
+/// game.start();
+/// assert!(app.borrow().moves == [Position(4, 0), Position(4, 2), Position(4, 3),
+///                                Position(0, 3)]);
+/// ```
+pub fn get_direction_with_jumps(key: Key) -> Option<Direction> {
+    match key {
+        Key::Home => Some(Direction::Home),
+        Key::End => Some(Direction::End),
+        Key::PageUp => Some(Direction::PageUp),
+        Key::PageDown => Some(Direction::PageDown),
+        _ => get_direction_default(key),
+    }
+}
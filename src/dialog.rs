@@ -0,0 +1,330 @@
+//! Message dialog.
+
+use crate::ansi::{color, cursor, style};
+use crate::chars;
+use crate::color::Color;
+use crate::cursor::KeyHandleResult;
+use crate::input::Key;
+use crate::game::Position;
+use crate::str_utils::{self, TextAlign};
+
+/// Space between buttons in characters.
+const BUTTON_SPACING: usize = 2;
+/// Minimal space between board and dialog borders: board border + margin.
+const BOARD_MARGIN: usize = 2;
+/// Minimal width of the prompt dialog input field.
+const INPUT_MIN_WIDTH: usize = 20;
+
+/// Position of the message dialog on the board.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DialogPosition {
+    /// Dialog is displayed at the top of the board.
+    Top,
+    /// Dialog is displayed at the bottom of the board.
+    Bottom,
+    /// Dialog is centered on the board.
+    Center,
+    /// Top left corner of the dialog is placed at this position. Position is in characters
+    /// relative to the top left corner of the board (including borders). Dialog is moved inside
+    /// the board if it doesn't fit.
+    Custom(Position),
+}
+
+/// Colors of the message dialog.
+///
+/// Colors which are not set are terminal defaults. Default style has no colors.
+///
+/// Background color is applied to the whole dialog including borders and margins. If dialog line
+/// contains `termion::style::Reset`, the rest of the line will be printed with default colors.
+#[derive(Copy, Clone, PartialEq, Default)]
+pub struct DialogStyle {
+    border_color: Option<Color>,
+    background_color: Option<Color>,
+    text_color: Option<Color>,
+}
+
+impl DialogStyle {
+    /// Creates new dialog style.
+    ///
+    /// `border_color` - color of the dialog border.
+    ///
+    /// `background_color` - background color of the dialog.
+    ///
+    /// `text_color` - color of the dialog text and buttons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gameboard::{Color, DialogStyle};
+    /// use termion::color;
+    ///
+    /// let style = DialogStyle::new(Some(color::Yellow.into()), Some(Color::Ansi(18)),
+    ///                              Some(Color::Rgb(255, 255, 255)));
+    /// ```
+    pub fn new(border_color: Option<Color>, background_color: Option<Color>,
+               text_color: Option<Color>) -> Self {
+        DialogStyle {
+            border_color,
+            background_color,
+            text_color,
+        }
+    }
+
+    // Get escape sequences to start dialog border.
+    fn border(&self) -> String {
+        let mut res = self.background();
+        if let Some(c) = self.border_color {
+            res.push_str(&color::Fg(c).to_string());
+        }
+        res
+    }
+
+    // Get escape sequences to start dialog text.
+    fn text(&self) -> String {
+        let mut res = self.background();
+        if let Some(c) = self.text_color {
+            res.push_str(&color::Fg(c).to_string());
+        }
+        res
+    }
+
+    fn background(&self) -> String {
+        match self.background_color {
+            Some(c) => color::Bg(c).to_string(),
+            None => String::new()
+        }
+    }
+}
+
+/// Message dialog with optional buttons.
+pub(crate) struct MessageDialog {
+    lines: Vec<String>,
+    buttons: Vec<String>,
+    selected: usize,
+    position: DialogPosition,
+    style: DialogStyle,
+    // Text entered in the prompt dialog. None if dialog has no input field.
+    input: Option<String>,
+    // Input cursor position in graphemes.
+    input_cursor: usize,
+}
+
+impl MessageDialog {
+    pub(crate) fn new(lines: &[&str], buttons: &[&str], position: DialogPosition,
+                      style: DialogStyle) -> Self {
+        MessageDialog {
+            lines: lines.iter().map(|&l| String::from(l)).collect(),
+            buttons: buttons.iter().map(|&b| format!(" {} ", b)).collect(),
+            selected: 0,
+            position,
+            style,
+            input: None,
+            input_cursor: 0,
+        }
+    }
+
+    // Create dialog with `label` and single line input field.
+    pub(crate) fn new_prompt(label: &str, position: DialogPosition, style: DialogStyle) -> Self {
+        let mut dialog = MessageDialog::new(&[label], &[], position, style);
+        dialog.input = Some(String::new());
+        dialog
+    }
+
+    // Take text entered in the prompt dialog.
+    pub(crate) fn take_input(&mut self) -> String {
+        self.input_cursor = 0;
+        self.input.take().unwrap_or_default()
+    }
+
+    // Select button with Left/Right keys, return selected button index on Enter.
+    pub(crate) fn handle_key(&mut self, key: Key) -> KeyHandleResult {
+        if self.input.is_some() {
+            return self.handle_input_key(key)
+        }
+        if self.buttons.is_empty() {
+            return KeyHandleResult::NotHandled
+        }
+        match key {
+            Key::Left => {
+                self.selected = self.selected.saturating_sub(1);
+                KeyHandleResult::Consumed
+            },
+            Key::Right => {
+                self.selected = (self.selected + 1).min(self.buttons.len() - 1);
+                KeyHandleResult::Consumed
+            },
+            Key::Enter => KeyHandleResult::DialogResult(self.selected),
+            _ => KeyHandleResult::NotHandled
+        }
+    }
+
+    // Edit prompt input field. Return DialogResult on Enter.
+    fn handle_input_key(&mut self, key: Key) -> KeyHandleResult {
+        let input = match self.input {
+            Some(ref mut input) => input,
+            None => return KeyHandleResult::NotHandled
+        };
+        match key {
+            Key::Enter => return KeyHandleResult::DialogResult(0),
+            Key::Char(c) if !c.is_control() => {
+                let offset = str_utils::get_grapheme_offset(input, self.input_cursor);
+                input.insert(offset, c);
+                // Character can be combined with the previous grapheme, so we count graphemes
+                // again to get the new cursor position.
+                self.input_cursor =
+                    str_utils::get_grapheme_count(&input[..offset + c.len_utf8()]);
+            },
+            Key::Backspace if self.input_cursor > 0 => {
+                let start = str_utils::get_grapheme_offset(input, self.input_cursor - 1);
+                let end = str_utils::get_grapheme_offset(input, self.input_cursor);
+                input.replace_range(start..end, "");
+                self.input_cursor -= 1;
+            },
+            Key::Left => self.input_cursor = self.input_cursor.saturating_sub(1),
+            Key::Right =>
+                self.input_cursor =
+                    (self.input_cursor + 1).min(str_utils::get_grapheme_count(input)),
+            _ => return KeyHandleResult::NotHandled
+        }
+        KeyHandleResult::Consumed
+    }
+
+    // Get dialog ready to display in terminal. Dialog is always placed inside the board.
+    pub(crate) fn get_content(&self, board_pos: Position, board_width: usize,
+                              board_height: usize) -> String {
+        let buttons_len = self.get_buttons_len();
+        let input_len = if self.input.is_some() { INPUT_MIN_WIDTH } else { 0 };
+        let line_max_len = self.lines.iter().map(|x| str_utils::get_str_len(x)).max()
+                .unwrap_or(0).max(buttons_len).max(input_len);
+        // Empty line and buttons line.
+        let button_rows = if self.buttons.is_empty() { 0 } else { 2 };
+        let input_rows = if self.input.is_some() { 1 } else { 0 };
+        // We want to have at least 1 character margin between border and text.
+        // So 8 means: board border + margin + dialog border + margin, from both sides.
+        let dlg_w = line_max_len.min(board_width - 8) + 4;
+        let text_rows = self.lines.len()
+                .min((board_height - 8).saturating_sub(button_rows + input_rows));
+        let dlg_h = text_rows + button_rows + input_rows + 4;
+        let (x, y) = self.get_origin(board_width, board_height, dlg_w, dlg_h);
+        let x = (board_pos.0 + x) as u16;
+        let y = (board_pos.1 + y) as u16;
+
+        let text_w = dlg_w - 4;
+        let border = self.style.border();
+        let empty_row = format!("{}{}{}{}{}", border, chars::DOUBLE_BORDER_VERT_LINE,
+                                " ".repeat(dlg_w - 2), chars::DOUBLE_BORDER_VERT_LINE, style::Reset);
+        let mut rows = Vec::with_capacity(dlg_h);
+        rows.push(format!("{}{}{}{}{}", border, chars::DOUBLE_BORDER_TOP_LEFT,
+                          chars::DOUBLE_BORDER_HOR_LINE.to_string().repeat(dlg_w - 2),
+                          chars::DOUBLE_BORDER_TOP_RIGHT, style::Reset));
+        rows.push(empty_row.clone());
+        for line in self.lines.iter().take(text_rows) {
+            let (align, line) = str_utils::split_align_prefix(line);
+            rows.push(self.get_text_row(&str_utils::align_str(line, text_w, align)));
+        }
+        if self.input.is_some() {
+            rows.push(self.get_text_row(&self.get_input_field(text_w)));
+        }
+        if !self.buttons.is_empty() {
+            rows.push(empty_row.clone());
+            let (before, after) = str_utils::get_padding(buttons_len, text_w, TextAlign::Center);
+            rows.push(self.get_text_row(
+                &format!("{}{}{}", " ".repeat(before), self.get_buttons(), " ".repeat(after))));
+        }
+        rows.push(empty_row);
+        rows.push(format!("{}{}{}{}{}", border, chars::DOUBLE_BORDER_BOTTOM_LEFT,
+                          chars::DOUBLE_BORDER_HOR_LINE.to_string().repeat(dlg_w - 2),
+                          chars::DOUBLE_BORDER_BOTTOM_RIGHT, style::Reset));
+
+        let mut res = String::with_capacity((dlg_w + str_utils::GOTO_SEQUENCE_WIDTH) * dlg_h);
+        for (i, row) in rows.iter().enumerate() {
+            res.push_str(&format!("{}{}", cursor::Goto(x, y + i as u16), row));
+        }
+        res
+    }
+
+    // Get top left corner of the dialog relative to the board.
+    fn get_origin(&self, board_width: usize, board_height: usize, dlg_w: usize,
+                  dlg_h: usize) -> (usize, usize) {
+        let max_x = board_width - BOARD_MARGIN - dlg_w;
+        let max_y = board_height - BOARD_MARGIN - dlg_h;
+        let center_x = (board_width - dlg_w) / 2;
+        match self.position {
+            DialogPosition::Top => (center_x, BOARD_MARGIN),
+            DialogPosition::Bottom => (center_x, max_y),
+            DialogPosition::Center => (center_x, (board_height - dlg_h) / 2),
+            DialogPosition::Custom(Position(x, y)) =>
+                (x.max(BOARD_MARGIN).min(max_x), y.max(BOARD_MARGIN).min(max_y)),
+        }
+    }
+
+    // Get input field line. Input cursor is highlighted. If text is too long, the part around
+    // cursor is shown.
+    fn get_input_field(&self, width: usize) -> String {
+        let input = self.input.as_deref().unwrap_or("");
+        let graphemes: Vec<&str> = str_utils::visible_graphemes(input).map(|(_, g)| g).collect();
+        // Cursor after the last character is shown as a space.
+        let mut cursor_width = graphemes.get(self.input_cursor)
+            .map_or(1, |g| str_utils::get_grapheme_width(g));
+        let mut start = self.input_cursor;
+        while start > 0 {
+            let w = str_utils::get_grapheme_width(graphemes[start - 1]);
+            if cursor_width + w > width {
+                break;
+            }
+            cursor_width += w;
+            start -= 1;
+        }
+        let mut res = style::Underline.to_string();
+        let mut col = 0;
+        for (i, g) in graphemes.iter().enumerate().skip(start) {
+            let w = str_utils::get_grapheme_width(g);
+            if col + w > width {
+                break;
+            }
+            if i == self.input_cursor {
+                res.push_str(&format!("{}{}{}", style::Invert, g, style::NoInvert));
+            } else {
+                res.push_str(g);
+            }
+            col += w;
+        }
+        if self.input_cursor == graphemes.len() && col < width {
+            res.push_str(&format!("{} {}", style::Invert, style::NoInvert));
+            col += 1;
+        }
+        res.push_str(&" ".repeat(width - col));
+        res.push_str(style::NoUnderline.as_ref());
+        res
+    }
+
+    fn get_text_row(&self, text: &str) -> String {
+        let border = self.style.border();
+        format!("{}{}{} {}{}{} {}{}", border, chars::DOUBLE_BORDER_VERT_LINE, self.style.text(),
+                text, style::Reset, border, chars::DOUBLE_BORDER_VERT_LINE, style::Reset)
+    }
+
+    // Get buttons line. Selected button is highlighted.
+    fn get_buttons(&self) -> String {
+        let mut res = String::new();
+        for (i, button) in self.buttons.iter().enumerate() {
+            if i > 0 {
+                res.push_str(&" ".repeat(BUTTON_SPACING));
+            }
+            if i == self.selected {
+                res.push_str(&format!("{}{}{}", style::Invert, button, style::NoInvert));
+            } else {
+                res.push_str(button);
+            }
+        }
+        res
+    }
+
+    fn get_buttons_len(&self) -> usize {
+        if self.buttons.is_empty() {
+            return 0
+        }
+        self.buttons.iter().map(|b| str_utils::get_str_len(b)).sum::<usize>() +
+            BUTTON_SPACING * (self.buttons.len() - 1)
+    }
+}
@@ -0,0 +1,282 @@
+//! Input events.
+//!
+//! Input is parsed by termion when `termion` feature is enabled. Otherwise the same escape
+//! sequences are parsed by this module.
+
+use std::io::Read;
+
+use crate::input::Key;
+
+#[cfg(feature = "termion")]
+pub(crate) use termion::event::{Event, MouseButton, MouseEvent};
+#[cfg(feature = "termion")]
+pub(crate) use termion::input::Events;
+#[cfg(not(feature = "termion"))]
+pub(crate) use self::parser::{Event, Events, MouseButton, MouseEvent};
+
+// Create iterator over input events of `input` stream.
+#[cfg(feature = "termion")]
+pub(crate) fn events<R: Read>(input: R) -> Events<R> {
+    termion::input::TermRead::events(input)
+}
+
+#[cfg(not(feature = "termion"))]
+pub(crate) fn events<R: Read>(input: R) -> Events<R> {
+    Events::new(input)
+}
+
+// Convert key of input event.
+#[cfg(feature = "termion")]
+pub(crate) fn to_key(key: termion::event::Key) -> Key {
+    Key::from(key)
+}
+
+#[cfg(not(feature = "termion"))]
+pub(crate) fn to_key(key: Key) -> Key {
+    key
+}
+
+#[cfg(not(feature = "termion"))]
+mod parser {
+    use std::io::{self, Read};
+    use std::str;
+
+    use crate::input::Key;
+
+    pub(crate) enum Event {
+        Key(Key),
+        Mouse(MouseEvent),
+        Unsupported(Vec<u8>),
+    }
+
+    pub(crate) enum MouseEvent {
+        Press(MouseButton, u16, u16),
+        Release,
+        Hold(u16, u16),
+    }
+
+    pub(crate) enum MouseButton {
+        Left,
+        Right,
+        Middle,
+        WheelUp,
+        WheelDown,
+    }
+
+    // Iterator over input events. Sequences are parsed the same way termion does it.
+    pub(crate) struct Events<R> {
+        source: R,
+        leftover: Option<u8>,
+        sequence: Vec<u8>,
+    }
+
+    impl<R: Read> Events<R> {
+        pub(crate) fn new(source: R) -> Self {
+            Events {
+                source,
+                leftover: None,
+                sequence: Vec::new(),
+            }
+        }
+
+        // Read next byte of the sequence. Return `None` if there is no more input.
+        fn next_byte(&mut self) -> Option<u8> {
+            let byte = match self.leftover.take() {
+                Some(byte) => byte,
+                None => {
+                    let mut buf = [0; 1];
+                    match self.source.read(&mut buf) {
+                        Ok(1) => buf[0],
+                        _ => return None,
+                    }
+                }
+            };
+            self.sequence.push(byte);
+            Some(byte)
+        }
+
+        fn parse(&mut self, first: u8) -> Option<Event> {
+            let key = match first {
+                0x1b => return self.parse_escape(),
+                b'\n' | b'\r' => Key::Enter,
+                b'\t' => Key::Tab,
+                0x7f => Key::Backspace,
+                c @ 0x01..=0x1a => Key::Ctrl((c - 0x01 + b'a') as char),
+                c @ 0x1c..=0x1f => Key::Ctrl((c - 0x1c + b'4') as char),
+                0 => Key::Null,
+                c => Key::Char(self.parse_char(c)?),
+            };
+            Some(Event::Key(key))
+        }
+
+        fn parse_escape(&mut self) -> Option<Event> {
+            let key = match self.next_byte()? {
+                b'O' => match self.next_byte()? {
+                    c @ b'P'..=b'S' => Key::F(c - b'P' + 1),
+                    _ => return None,
+                },
+                b'[' => return self.parse_csi(),
+                c => Key::Alt(self.parse_char(c)?),
+            };
+            Some(Event::Key(key))
+        }
+
+        fn parse_csi(&mut self) -> Option<Event> {
+            let key = match self.next_byte()? {
+                b'[' => match self.next_byte()? {
+                    c @ b'A'..=b'E' => Key::F(c - b'A' + 1),
+                    _ => return None,
+                },
+                b'D' => Key::Left,
+                b'C' => Key::Right,
+                b'A' => Key::Up,
+                b'B' => Key::Down,
+                b'H' => Key::Home,
+                b'F' => Key::End,
+                b'Z' => Key::BackTab,
+                b'M' => return self.parse_x10_mouse(),
+                b'<' => return self.parse_sgr_mouse(),
+                c @ b'0'..=b'9' => return self.parse_numbered(c),
+                _ => return None,
+            };
+            Some(Event::Key(key))
+        }
+
+        // X10 mouse encoding: ESC [ M Cb Cx Cy.
+        fn parse_x10_mouse(&mut self) -> Option<Event> {
+            let cb = self.next_byte()?.wrapping_sub(32);
+            let x = u16::from(self.next_byte()?.saturating_sub(32));
+            let y = u16::from(self.next_byte()?.saturating_sub(32));
+            let event = match cb & 0b11 {
+                0 if cb & 0x40 != 0 => MouseEvent::Press(MouseButton::WheelUp, x, y),
+                0 => MouseEvent::Press(MouseButton::Left, x, y),
+                1 if cb & 0x40 != 0 => MouseEvent::Press(MouseButton::WheelDown, x, y),
+                1 => MouseEvent::Press(MouseButton::Middle, x, y),
+                2 => MouseEvent::Press(MouseButton::Right, x, y),
+                _ => MouseEvent::Release,
+            };
+            Some(Event::Mouse(event))
+        }
+
+        // SGR mouse encoding: ESC [ < Cb ; Cx ; Cy M (press) or m (release).
+        fn parse_sgr_mouse(&mut self) -> Option<Event> {
+            let mut params = Vec::new();
+            let last = loop {
+                match self.next_byte()? {
+                    c @ b'M' | c @ b'm' => break c,
+                    c => params.push(c),
+                }
+            };
+            let (cb, x, y) = parse_mouse_params(&params)?;
+            let button = match cb {
+                0 => MouseButton::Left,
+                1 => MouseButton::Middle,
+                2 => MouseButton::Right,
+                64 => MouseButton::WheelUp,
+                65 => MouseButton::WheelDown,
+                32 => return Some(Event::Mouse(MouseEvent::Hold(x, y))),
+                3 => return Some(Event::Mouse(MouseEvent::Release)),
+                _ => return None,
+            };
+            let event = if last == b'M' {
+                MouseEvent::Press(button, x, y)
+            } else {
+                MouseEvent::Release
+            };
+            Some(Event::Mouse(event))
+        }
+
+        // Sequence with numeric parameters: ESC [ Cb ; Cx ; Cy M (urxvt mouse encoding) or
+        // ESC [ Code ~ (special key).
+        fn parse_numbered(&mut self, first: u8) -> Option<Event> {
+            let mut params = vec![first];
+            let last = loop {
+                match self.next_byte()? {
+                    c @ 64..=126 => break c,
+                    c => params.push(c),
+                }
+            };
+            match last {
+                b'M' => {
+                    let (cb, x, y) = parse_mouse_params(&params)?;
+                    let event = match cb {
+                        32 => MouseEvent::Press(MouseButton::Left, x, y),
+                        33 => MouseEvent::Press(MouseButton::Middle, x, y),
+                        34 => MouseEvent::Press(MouseButton::Right, x, y),
+                        35 => MouseEvent::Release,
+                        64 => MouseEvent::Hold(x, y),
+                        96 | 97 => MouseEvent::Press(MouseButton::WheelUp, x, y),
+                        _ => return None,
+                    };
+                    Some(Event::Mouse(event))
+                },
+                b'~' => {
+                    let key = match str::from_utf8(&params).ok()?.parse::<u8>().ok()? {
+                        1 | 7 => Key::Home,
+                        2 => Key::Insert,
+                        3 => Key::Delete,
+                        4 | 8 => Key::End,
+                        5 => Key::PageUp,
+                        6 => Key::PageDown,
+                        v @ 11..=15 => Key::F(v - 10),
+                        v @ 17..=21 => Key::F(v - 11),
+                        v @ 23..=24 => Key::F(v - 12),
+                        _ => return None,
+                    };
+                    Some(Event::Key(key))
+                },
+                _ => None,
+            }
+        }
+
+        // Parse one- or multi-byte UTF-8 character starting with `first` byte.
+        fn parse_char(&mut self, first: u8) -> Option<char> {
+            if first.is_ascii() {
+                return Some(first as char)
+            }
+            let mut bytes = vec![first];
+            while bytes.len() < 4 {
+                bytes.push(self.next_byte()?);
+                if let Ok(s) = str::from_utf8(&bytes) {
+                    return s.chars().next()
+                }
+            }
+            None
+        }
+    }
+
+    impl<R: Read> Iterator for Events<R> {
+        type Item = io::Result<Event>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            // Single ESC byte is *Esc* key, escape sequences are read at once. So two bytes are
+            // read to distinguish them.
+            let first = match self.leftover.take() {
+                Some(byte) => byte,
+                None => {
+                    let mut buf = [0; 2];
+                    match self.source.read(&mut buf) {
+                        Ok(0) => return None,
+                        Ok(1) if buf[0] == 0x1b => return Some(Ok(Event::Key(Key::Esc))),
+                        Ok(1) => buf[0],
+                        Ok(_) => {
+                            self.leftover = Some(buf[1]);
+                            buf[0]
+                        },
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            };
+            self.sequence = vec![first];
+            let event = self.parse(first)
+                .unwrap_or_else(|| Event::Unsupported(self.sequence.clone()));
+            Some(Ok(event))
+        }
+    }
+
+    // Parse mouse event parameters: Cb ; Cx ; Cy.
+    fn parse_mouse_params(params: &[u8]) -> Option<(u16, u16, u16)> {
+        let mut nums = str::from_utf8(params).ok()?.split(';').map(|n| n.parse::<u16>());
+        Some((nums.next()?.ok()?, nums.next()?.ok()?, nums.next()?.ok()?))
+    }
+}
@@ -1,30 +1,152 @@
 //! Main game object.
 
-use std::io::{Read, Write};
+use std::any::Any;
+use std::io::Read;
+#[cfg(feature = "termion")]
+use std::io::Write;
+#[cfg(feature = "crossterm")]
+use std::io::Stdout;
 use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
 use std::rc::{Rc, Weak};
+use std::mem;
+use std::ops::{Add, Deref, Sub};
+use std::thread;
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "termion")]
 use termion::raw::{IntoRawMode, RawTerminal};
+#[cfg(feature = "termion")]
 use termion::screen::AlternateScreen;
-use termion::input::{TermRead, Keys};
-use termion::{cursor};
-use termion::event::Key;
 
-use crate::board::{Board, CellUpdates};
+use crate::ansi::{clear, style};
+use crate::board::{Board, BoardState, CellUpdates};
+use crate::cell::Cell;
+use crate::color::{self, Color, ColorMode};
 use crate::info::{Info, InfoLayout};
-use crate::cursor::KeyHandleResult;
+use crate::cursor::{Direction, KeyHandleResult};
+use crate::input::{self, Key, KeyBindings};
+use crate::dialog::{DialogPosition, DialogStyle};
+use crate::event::{self, Event, Events, MouseButton, MouseEvent};
+use crate::terminal::{Terminal, MOUSE_DISABLE_SEQUENCE};
+use crate::theme::Theme;
+#[cfg(feature = "crossterm")]
+use crate::crossterm_backend::{CrosstermInput, CrosstermTerminal};
 
 const SCREEN_TOP: usize = 1;
 const SCREEN_LEFT: usize = 1;
+/// How long to sleep in tick mode when there is no input.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Maximum number of input events handled at once in input coalescing mode.
+const MAX_COALESCED_EVENTS: usize = 64;
+/// Quit confirmation dialog text shown when quit key is pressed.
+const QUIT_MESSAGE: &str = "|^|Quit the game?";
+/// Enable mouse button and any motion events reporting with SGR and urxvt coordinates.
+const MOUSE_ENABLE_SEQUENCE: &str = "\x1b[?1000h\x1b[?1003h\x1b[?1015h\x1b[?1006h";
 
 /// Board position.
 ///
 /// *x* (horizontal) and *y* (vertical) cell position on the board. Position is zero-based.
-#[derive(Copy, Clone, PartialEq, Eq)]
+///
+/// # Examples
+///
+/// ```
+/// # use gameboard::Position;
+/// let pos = Position(2, 3) + Position(1, 0);
+/// assert!(pos == Position(3, 3));
+/// assert!(pos - Position(3, 1) == Position::from((0, 2)));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position(pub usize, pub usize);
 
+impl Position {
+    /// Returns position moved by `dx` columns and `dy` rows.
+    ///
+    /// Returns `None` if any coordinate becomes negative. Board size is not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::Position;
+    /// assert!(Position(1, 1).offset(-1, 1) == Some(Position(0, 2)));
+    /// assert!(Position(0, 1).offset(-1, 0) == None);
+    /// ```
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Position> {
+        let x = self.0.checked_add_signed(dx)?;
+        let y = self.1.checked_add_signed(dy)?;
+        Some(Position(x, y))
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    /// Adds coordinates.
+    fn add(self, other: Position) -> Position {
+        Position(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    /// Subtracts coordinates. Panics on underflow in debug build, use [`offset`] to avoid it.
+    ///
+    /// [`offset`]: struct.Position.html#method.offset
+    fn sub(self, other: Position) -> Position {
+        Position(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    /// Converts (x, y) tuple.
+    fn from((x, y): (usize, usize)) -> Self {
+        Position(x, y)
+    }
+}
+
+impl From<Position> for (usize, usize) {
+    /// Converts to (x, y) tuple.
+    fn from(Position(x, y): Position) -> Self {
+        (x, y)
+    }
+}
+
+/// Rectangular area on the screen.
+///
+/// Coordinates are in terminal cells and one-based, the same as in `termion::cursor::Goto`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Rect {
+    /// Left column.
+    pub x: usize,
+    /// Top row.
+    pub y: usize,
+    /// Width in columns.
+    pub width: usize,
+    /// Height in rows.
+    pub height: usize,
+}
+
+/// Alignment of the board and information area on the screen.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ScreenAlign {
+    /// Top left corner of the screen.
+    TopLeft = 0,
+    /// Top right corner of the screen.
+    TopRight,
+    /// Bottom left corner of the screen.
+    BottomLeft,
+    /// Bottom right corner of the screen.
+    BottomRight,
+    /// Center of the screen.
+    Center,
+}
+
 /// Game state.
-#[derive(PartialEq, Eq, Copy, Clone)]
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum GameState {
     /// Initial game state.
     Created = 0,
@@ -37,17 +159,110 @@ pub enum GameState {
     Paused,
     /// Game is stopped. Input from keyboard is ignored.
     Stopped,
+    /// Game is stopped, but the board is still shown and can be inspected: cursor can be moved
+    /// and keys are passed to the input listener. Game enters this state from
+    /// `GameState::Stopped` when `Game::review` is called and returns back when `Game::stop` is
+    /// called. New game can be initialized in this state as well.
+    Reviewing,
+}
+
+/// Screen region which receives keys.
+///
+/// See `Game::set_focus`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Focus {
+    /// Keys are passed to the board cursor.
+    Board,
+    /// Keys scroll the information area with this index.
+    Info(usize),
+}
+
+/// Game lifecycle error.
+///
+/// Returned by `try_*` methods of [`Game`]. Methods without `try_` prefix panic with this error.
+///
+/// [`Game`]: struct.Game.html
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameError {
+    /// Method can't be called in current game state.
+    WrongState {
+        /// States the method can be called in.
+        expected: &'static [GameState],
+        /// Current game state.
+        actual: GameState,
+    },
+    /// Input listener object was dropped.
+    ListenerDropped,
+    /// Board and information areas can't be placed on the screen.
+    Layout(LayoutError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::WrongState { expected, actual } =>
+                write!(f, "Wrong game state: expected one of {:?}, got {:?}.", expected, actual),
+            GameError::ListenerDropped =>
+                write!(f, "Input listener was dropped."),
+            GameError::Layout(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for GameError {}
+
+/// Layout error.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LayoutError {
+    /// Board and information areas are larger than the terminal.
+    TooLarge {
+        /// Size (width, height) of the board with information areas.
+        needed: (usize, usize),
+        /// Terminal size (width, height).
+        available: (usize, usize),
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::TooLarge { needed, available } =>
+                write!(f, "Layout {}x{} doesn't fit the terminal {}x{}.", needed.0, needed.1,
+                       available.0, available.1),
+        }
+    }
 }
 
+impl Error for LayoutError {}
+
 /// User input listener.
-pub trait InputListener<R: Read, W: Write>
+pub trait InputListener<R: Read, W: Terminal>
     where Self: Sized {
+    /// Type of user actions which keys are bound to (see [`on_action`]). Use `()` if you don't
+    /// use key bindings.
+    ///
+    /// [`on_action`]: #method.on_action
+    type Action: Copy + Eq + Hash;
+
     /// This method is called when user press any key on keyboard.
     ///
-    /// Since this library uses termion crate, keys from `termion::event::Key` are supported only.
-    /// You can update game using `game` argument.
+    /// Keys are received from terminal backend and converted to [`Key`]. You can update game using
+    /// `game` argument.
+    ///
+    /// [`Key`]: ../input/enum.Key.html
+    ///
     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>);
 
+    /// This method is called when user pressed a key bound to `action` (see
+    /// [`set_bindings`]). Default implementation is empty.
+    ///
+    /// Bound keys are not passed to `handle_key`. Cursor movement keys and dialog keys are
+    /// handled before bindings are checked. You can update game using `game` argument.
+    ///
+    /// [`set_bindings`]: struct.Game.html#method.set_bindings
+    ///
+    fn on_action(&mut self, _action: Self::Action, _game: &mut Game<R, W, Self>) {}
+
     /// This method is called when user moved [`Cursor`]. Default implementation is empty. You
     /// don't need to implement it if you don't use [`Cursor`].
     ///
@@ -56,34 +271,337 @@ pub trait InputListener<R: Read, W: Write>
     /// [`Cursor`]: ../cursor/struct.Cursor.html
     ///
     fn cursor_moved(&mut self, _position: Position, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when user tried to move [`Cursor`], but it can't be moved. Default
+    /// implementation is empty.
+    ///
+    /// Cursor can be blocked by board edge (if wrapping around is disabled), by disabled cells
+    /// or by `Cursor::set_can_enter` callback. The `direction` is an attempted move direction.
+    /// You can use it to play a sound or flash the cell.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    fn cursor_blocked(&mut self, _direction: Direction, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when terminal size was changed. Default implementation is empty.
+    ///
+    /// The `width` and `height` are new terminal size in characters. After this method returns
+    /// the board and information area are laid out again and the whole screen is redrawn.
+    ///
+    /// Terminal size is checked when user input is received, so this method is called before
+    /// handling the first key pressed after resize.
+    fn on_resize(&mut self, _width: u16, _height: u16, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called every tick interval if game was started with
+    /// [`start_with_tick`]. It isn't called while the game is paused. Default implementation is
+    /// empty.
+    ///
+    /// It runs on the same thread as key handling, so you don't need any synchronization. You can
+    /// update game using `game` argument.
+    ///
+    /// [`start_with_tick`]: struct.Game.html#method.start_with_tick
+    ///
+    fn on_tick(&mut self, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when user pressed a button in the dialog opened by
+    /// [`show_dialog`]. Default implementation is empty.
+    ///
+    /// The `index` is an index of the pressed button. Dialog is closed before this method is
+    /// called. You can update game using `game` argument.
+    ///
+    /// [`show_dialog`]: struct.Game.html#method.show_dialog
+    ///
+    fn on_dialog_result(&mut self, _index: usize, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when user clicked board cell with the left mouse button. Default
+    /// implementation is empty. Mouse must be enabled with [`enable_mouse`].
+    ///
+    /// The `position` is a clicked cell position. If board has [`Cursor`], it is moved to this
+    /// cell and `cursor_moved` is called before this method. Clicks on borders and outside of
+    /// the board are ignored. You can update game using `game` argument.
+    ///
+    /// [`enable_mouse`]: struct.Game.html#method.enable_mouse
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    ///
+    fn on_click(&mut self, _position: Position, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when user clicked information area with the left mouse button.
+    /// Default implementation is empty. Mouse must be enabled with [`enable_mouse`].
+    ///
+    /// The `index` is an index of the information area in the list passed to `Game::init`.
+    /// The `row` and `col` are text position inside the area borders, starting from 0. Rows are
+    /// counted as displayed, so if wrapping is enabled, a long line takes several rows. Clicks on
+    /// borders are ignored. You can update game using `game` argument.
+    ///
+    /// [`enable_mouse`]: struct.Game.html#method.enable_mouse
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// struct App {
+    ///     clicked: Option<(usize, usize, usize)>,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    ///
+    ///     fn on_info_click(&mut self, index: usize, row: usize, col: usize,
+    ///                      _game: &mut Game<R, W, Self>) {
+    ///         self.clicked = Some((index, row, col));
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App { clicked: None }));
+    /// // Left button click at column 9, row 3.
+    /// let input: &[u8] = b"\x1b[<0;9;3M";
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(input, output.clone(), Rc::clone(&app));
+    /// game.enable_mouse();
+    /// // Board takes columns 1-5 with borders, info area text starts at column 7.
+    /// let menu = Info::new(10, InfoLayout::Right, &["New game", "Quit"]);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), Some(menu));
+    /// game.start();
+    /// assert!(output.screen_snapshot()[2].starts_with("║   ║║Quit      ║"));
+    /// assert_eq!(app.borrow().clicked, Some((0, 1, 2)));
+    /// ```
+    fn on_info_click(&mut self, _index: usize, _row: usize, _col: usize,
+                     _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when mouse pointer moved to another board cell. Default
+    /// implementation is empty. Mouse must be enabled with [`enable_mouse`].
+    ///
+    /// The `position` is a cell position under mouse pointer. This cell is highlighted (see
+    /// `Board::set_hover_color`). You can update game using `game` argument.
+    ///
+    /// [`enable_mouse`]: struct.Game.html#method.enable_mouse
+    ///
+    fn on_hover(&mut self, _position: Position, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when game state is changed from `old` to `new`. Default
+    /// implementation is empty.
+    ///
+    /// State is usually changed inside other listener methods (for example, `handle_key` calls
+    /// `game.pause`), so this method is called after the method which changed the state returns.
+    /// State changes made before the game is started (`Created` to `Initialized`) are reported
+    /// when game is started. You can update game using `game` argument.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, GameState, InputListener, Terminal, Key};
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         match (game.get_state(), key) {
+    ///             (GameState::Started, Key::Char('p')) => game.pause(Key::Char('p')),
+    ///             (GameState::Paused, _) => game.resume(),
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///
+    ///     fn on_state_change(&mut self, _old: GameState, new: GameState,
+    ///                        game: &mut Game<R, W, Self>) {
+    ///         let text = match new {
+    ///             GameState::Paused => "Paused. Press 'p' to resume.",
+    ///             _ => "Press 'p' to pause.",
+    ///         };
+    ///         game.update_info(&[text]);
+    ///     }
+    /// }
+    /// ```
+    fn on_state_change(&mut self, _old: GameState, _new: GameState,
+                       _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when focus is moved to another screen region (see
+    /// [`set_focus`]). Default implementation is empty.
+    ///
+    /// [`set_focus`]: struct.Game.html#method.set_focus
+    fn on_focus_change(&mut self, _old: Focus, _new: Focus, _game: &mut Game<R, W, Self>) {}
+
+    /// This method is called when cell is toggled by toggle key (see [`set_toggle_key`]) or by
+    /// right mouse button click. Default implementation is empty.
+    ///
+    /// The `index` is an index of the new cell state in the cycle set by
+    /// `Board::set_toggle_states`. Cells without states are not toggled and this method isn't
+    /// called for them.
+    ///
+    /// [`set_toggle_key`]: struct.Game.html#method.set_toggle_key
+    fn on_cell_toggle(&mut self, _position: Position, _index: usize,
+                      _game: &mut Game<R, W, Self>) {}
+}
+
+/// Reference to input listener.
+enum ListenerRef<L> {
+    /// Listener is owned by user, game keeps weak reference.
+    Shared(Weak<RefCell<L>>),
+    /// Listener is owned by game (see `Game::with_terminal_owned`). It is `None` while it is
+    /// taken out for dispatching.
+    Owned(Option<L>),
+}
+
+impl<L> ListenerRef<L> {
+    // Take listener out for dispatching. Return None if shared listener was dropped or owned
+    // listener is already taken. Listener must be put back with `restore`.
+    fn take(&mut self) -> Option<Listener<L>> {
+        match self {
+            ListenerRef::Shared(listener) => listener.upgrade().map(Listener::Shared),
+            ListenerRef::Owned(listener) => {
+                listener.take().map(|listener| Listener::Owned(RefCell::new(listener)))
+            },
+        }
+    }
+
+    // Put back listener taken with `take`.
+    fn restore(&mut self, listener: Listener<L>) {
+        if let (ListenerRef::Owned(slot), Listener::Owned(listener)) = (self, listener) {
+            *slot = Some(listener.into_inner());
+        }
+    }
+}
+
+/// Input listener taken out of the game for dispatching.
+enum Listener<L> {
+    Shared(Rc<RefCell<L>>),
+    Owned(RefCell<L>),
+}
+
+impl<L> Deref for Listener<L> {
+    type Target = RefCell<L>;
+
+    fn deref(&self) -> &RefCell<L> {
+        match self {
+            Listener::Shared(listener) => listener,
+            Listener::Owned(listener) => listener,
+        }
+    }
 }
 
 /// Main game object.
 ///
 /// All interactions with the game should be done using its API.
-pub struct Game<R: Read, W: Write, L: InputListener<R, W>> {
+///
+/// # Terminal restoration
+///
+/// When game object is dropped, mouse reporting is disabled, text attributes are reset and the
+/// cursor is shown. This also happens if input listener panics, because the game is dropped
+/// while the stack unwinds. Write errors are ignored then. Alternate screen and raw mode are
+/// restored by termion wrappers created in [`new`], which are dropped right after the game. See
+/// also [`install_panic_hook`].
+///
+/// [`new`]: #method.new
+/// [`install_panic_hook`]: ../terminal/fn.install_panic_hook.html
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::io::Read;
+/// use std::panic::{self, AssertUnwindSafe};
+/// use std::rc::Rc;
+/// use gameboard::{Board, Game, InputListener, Terminal, Key};
+/// use gameboard::test::{RecordingOutput, ScriptedInput};
+///
+/// struct App;
+///
+/// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+///     type Action = ();
+///
+///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {
+///         panic!("listener failed");
+///     }
+/// }
+///
+/// # panic::set_hook(Box::new(|_| {}));
+/// let output = RecordingOutput::new(80, 24);
+/// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+///     let input = ScriptedInput::new(&[Key::Char('a')]);
+///     let mut game = Game::with_terminal(input, output.clone(), Rc::new(RefCell::new(App)));
+///     game.init(Board::new(3, 3, 1, 1, false, None), None);
+///     game.enable_mouse();
+///     output.clear();
+///     game.start();
+/// }));
+/// assert!(result.is_err());
+///
+/// let restored = String::from_utf8(output.output()).unwrap();
+/// assert!(restored.contains("\x1b[?1000l"));
+/// assert!(restored.ends_with("\x1b[m\x1b[?25h"));
+/// ```
+pub struct Game<R: Read, W: Terminal, L: InputListener<R, W>> {
     board: Option<Board>,
-    info: Option<Info>,
+    infos: Vec<Info>,
     state: GameState,
-    input: Keys<R>,
+    input: Events<R>,
     output: W,
-    listener: Weak<RefCell<L>>,
-    resume_key: Option<Key>
+    listener: ListenerRef<L>,
+    resume_keys: Vec<Key>,
+    quit_key: Option<Key>,
+    /// Ctrl+C exits the game without confirmation.
+    quit_on_ctrl_c: bool,
+    bindings: KeyBindings<L::Action>,
+    /// User confirmed quit or `exit` was called.
+    should_exit: bool,
+    /// Last known terminal size.
+    screen_size: Option<(u16, u16)>,
+    alignment: ScreenAlign,
+    mouse_enabled: bool,
+    input_coalescing: bool,
+    /// Input stream is non-blocking, it is polled by the game loop with tick.
+    polled_input: bool,
+    /// Game loop is run by `start`, so input stream blocks and no input means it ended.
+    blocking_input: bool,
+    /// State changes (old, new) not reported to listener yet.
+    state_changes: Vec<(GameState, GameState)>,
+    focus: Focus,
+    focus_key: Option<Key>,
+    toggle_key: Option<Key>,
+    /// Focus changes (old, new) not reported to listener yet.
+    focus_changes: Vec<(Focus, Focus)>,
+    /// Number of ticks since the game was started.
+    tick_count: u64,
+    /// Time since the game was started with ticks.
+    elapsed: Duration,
+    color_mode: ColorMode,
+    /// Screen updates are deferred until `end_batch` is called.
+    batch: bool,
+    /// Next frame. Board and information area output is collected here and written to the
+    /// terminal with a single write, so partially drawn screen isn't shown.
+    frame: String,
 }
 
-
-impl<R: Read, W: Write, L: InputListener<R, W>> Drop for Game<R, W, L> {
+impl<R: Read, W: Terminal, L: InputListener<R, W>> Drop for Game<R, W, L> {
     fn drop(&mut self) {
-        write!(self.output, "{}", cursor::Show).unwrap();
-        self.output.flush().unwrap();
+        // Game is also dropped when input listener panics, so terminal is restored on
+        // best-effort basis. Panic while unwinding would abort the process.
+        if self.mouse_enabled {
+            let _ = write!(self.output, "{}", MOUSE_DISABLE_SEQUENCE);
+        }
+        let _ = write!(self.output, "{}", style::Reset);
+        let _ = self.output.show_cursor();
+        let _ = self.output.flush();
+        let _ = self.output.disable_raw_mode();
     }
 }
 
+#[cfg(feature = "termion")]
 impl<R: Read, W: Write, L> Game<R, AlternateScreen<RawTerminal<W>>, L>
     where L: InputListener<R, AlternateScreen<RawTerminal<W>>> {
 
     /// Creates new game object.
     ///
+    /// This method is available with `termion` feature only (enabled by default). Terminal is
+    /// switched to raw mode and alternate screen.
+    ///
     /// # Arguments
     ///
     /// `input` - input stream.
@@ -95,15 +613,17 @@ impl<R: Read, W: Write, L> Game<R, AlternateScreen<RawTerminal<W>>, L>
     /// # Examples
     ///
     /// ```no_run
-    /// use std::io::{self, Read, Write};
+    /// use std::io::{self, Read};
     /// use std::cell::RefCell;
     /// use std::rc::Rc;
-    /// use termion::event::Key;
-    /// use gameboard::{Game, InputListener};
+    /// use gameboard::Key;
+    /// use gameboard::{Game, InputListener, Terminal};
     ///
     /// struct App {}
     ///
-    /// impl<R: Read, W: Write> InputListener<R, W> for App {
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
     ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
     ///         match key {
     ///             Key::Char('q') => game.stop(),
@@ -124,22 +644,62 @@ impl<R: Read, W: Write, L> Game<R, AlternateScreen<RawTerminal<W>>, L>
     /// ```
     ///
     pub fn new(input: R, output: W, listener: Rc<RefCell<L>>) -> Self {
-        let mut alt_screen = AlternateScreen::from(output.into_raw_mode().unwrap());
-        write!(alt_screen, "{}", cursor::Hide).unwrap();
-        alt_screen.flush().unwrap();
+        // Termion terminal is in raw mode when it is created.
+        let alt_screen = AlternateScreen::from(output.into_raw_mode().unwrap());
+        Game::with_terminal(input, alt_screen, listener)
+    }
 
-        Game {
-            input: input.keys(),
-            output: alt_screen,
-            listener: Rc::downgrade(&listener),
-            board: None,
-            info: None,
-            state: GameState::Created,
-            resume_key: None
-        }
+    /// Creates new game object which owns input listener.
+    ///
+    /// This method is the same as [`new`] method, but listener is moved into the game, so you
+    /// don't need `Rc` and `RefCell`. The tradeoff is that listener can't be accessed outside of
+    /// the game. It gets the game in its methods, but it can't keep game reference and you can't
+    /// reset it between games (for example, in [`run`] setup). Reset listener in
+    /// [`on_state_change`] instead. Use [`new`] if you need access to listener.
+    ///
+    /// [`new`]: #method.new
+    /// [`run`]: #method.run
+    /// [`on_state_change`]: trait.InputListener.html#method.on_state_change
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::{self, Read};
+    /// use gameboard::{Board, Game, GameState, InputListener, Terminal, Key};
+    ///
+    /// struct App {
+    ///     moves: u32,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {
+    ///         self.moves += 1;
+    ///     }
+    ///
+    ///     fn on_state_change(&mut self, _old: GameState, new: GameState,
+    ///                        _game: &mut Game<R, W, Self>) {
+    ///         if new == GameState::Initialized {
+    ///             self.moves = 0;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let stdout = io::stdout();
+    /// let stdin = io::stdin();
+    /// let mut game = Game::new_owned(stdin.lock(), stdout.lock(), App { moves: 0 });
+    /// game.set_quit_key(Some(Key::Char('q')));
+    /// game.run(|| (Board::new(3, 3, 1, 1, false, None), None));
+    /// ```
+    pub fn new_owned(input: R, output: W, listener: L) -> Self {
+        // Termion terminal is in raw mode when it is created.
+        let alt_screen = AlternateScreen::from(output.into_raw_mode().unwrap());
+        Game::with_terminal_owned(input, alt_screen, listener)
     }
 }
 
+#[cfg(feature = "termion")]
 impl<R: Read, W: Write, L> Game<R, RawTerminal<W>, L>
     where L: InputListener<R, RawTerminal<W>> {
 
@@ -149,96 +709,382 @@ impl<R: Read, W: Write, L> Game<R, RawTerminal<W>, L>
     /// The `new` method uses `termion::screen::AlternateScreen` for output, which switches to
     /// the alternate screen buffer of the terminal. When application crashes, terminal switches
     /// to the main screen buffer and all debug/crash output is wiped out. This method uses main
-    /// screen buffer for output.
+    /// screen buffer for output. This method is available with `termion` feature only.
     ///
     /// [`new`]: #method.new
     pub fn new_dbg(input: R, output: W, listener: Rc<RefCell<L>>) -> Self {
-        let mut screen = output.into_raw_mode().unwrap();
-        write!(screen, "{}", cursor::Hide).unwrap();
-        screen.flush().unwrap();
+        let screen = output.into_raw_mode().unwrap();
+        Game::with_terminal(input, screen, listener)
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl<L> Game<CrosstermInput, CrosstermTerminal<Stdout>, L>
+    where L: InputListener<CrosstermInput, CrosstermTerminal<Stdout>> {
+
+    /// Creates new game object which uses crossterm for input and output.
+    ///
+    /// This method is available with `crossterm` feature only. Standard input and output are
+    /// used. Terminal is switched to raw mode and alternate screen. Input stream blocks until key
+    /// is pressed, use [`with_terminal`] and `CrosstermInput::new_async` for
+    /// [`start_with_tick`].
+    ///
+    /// [`with_terminal`]: #method.with_terminal
+    /// [`start_with_tick`]: #method.start_with_tick
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::Key;
+    /// use gameboard::{Game, InputListener, Terminal};
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, game: &mut Game<R, W, Self>) {
+    ///         game.stop();
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let game = Game::new_crossterm(Rc::clone(&app));
+    /// ```
+    pub fn new_crossterm(listener: Rc<RefCell<L>>) -> Self {
+        let terminal = CrosstermTerminal::new(std::io::stdout()).unwrap();
+        Game::with_terminal(CrosstermInput::new(), terminal, listener)
+    }
+}
+
+impl<R: Read, W: Terminal, L: InputListener<R, W>> Game<R, W, L> {
+    /// Creates new game object with custom terminal.
+    ///
+    /// Game output is written to `terminal` as is. Terminal is switched to raw mode with
+    /// `Terminal::enable_raw_mode`. This method can be used to run game with another terminal
+    /// library or to record game output. See [`Terminal`] for details.
+    ///
+    /// [`Terminal`]: ../terminal/trait.Terminal.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::Key;
+    /// use termion::raw::IntoRawMode;
+    /// use gameboard::{Game, InputListener, Terminal};
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, game: &mut Game<R, W, Self>) {
+    ///         game.stop();
+    ///     }
+    /// }
+    ///
+    /// let terminal = io::stdout().into_raw_mode().unwrap();
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let game = Game::with_terminal(io::stdin(), terminal, Rc::clone(&app));
+    /// ```
+    pub fn with_terminal(input: R, terminal: W, listener: Rc<RefCell<L>>) -> Self {
+        Game::create(input, terminal, ListenerRef::Shared(Rc::downgrade(&listener)))
+    }
+
+    /// Creates new game object with custom terminal which owns input listener.
+    ///
+    /// This method is the same as [`with_terminal`], but listener is moved into the game, so you
+    /// don't need `Rc` and `RefCell`. Listener can't be accessed outside of the game, reset it in
+    /// [`on_state_change`] between games.
+    ///
+    /// [`with_terminal`]: #method.with_terminal
+    /// [`on_state_change`]: trait.InputListener.html#method.on_state_change
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use gameboard::{Board, Game, GameState, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// struct App {
+    ///     moves: u32,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         self.moves += 1;
+    ///         if key == Key::Char('q') && self.moves == 3 {
+    ///             game.stop();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let input = ScriptedInput::new(&[Key::Char('a'), Key::Char('b'), Key::Char('q')]);
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal_owned(input, output, App { moves: 0 });
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// game.start();
+    /// assert_eq!(game.get_state(), GameState::Stopped);
+    /// ```
+    pub fn with_terminal_owned(input: R, terminal: W, listener: L) -> Self {
+        Game::create(input, terminal, ListenerRef::Owned(Some(listener)))
+    }
+
+    fn create(input: R, mut terminal: W, listener: ListenerRef<L>) -> Self {
+        terminal.enable_raw_mode().unwrap();
+        terminal.hide_cursor().unwrap();
+        terminal.flush().unwrap();
 
         Game {
-            input: input.keys(),
-            output: screen,
-            listener: Rc::downgrade(&listener),
+            input: event::events(input),
+            output: terminal,
+            listener,
             board: None,
-            info: None,
+            infos: Vec::new(),
             state: GameState::Created,
-            resume_key: None
+            resume_keys: Vec::new(),
+            quit_key: None,
+            quit_on_ctrl_c: false,
+            bindings: KeyBindings::new(),
+            should_exit: false,
+            screen_size: None,
+            alignment: ScreenAlign::TopLeft,
+            mouse_enabled: false,
+            input_coalescing: false,
+            polled_input: false,
+            blocking_input: false,
+            state_changes: Vec::new(),
+            focus: Focus::Board,
+            focus_key: None,
+            toggle_key: None,
+            focus_changes: Vec::new(),
+            tick_count: 0,
+            elapsed: Duration::from_secs(0),
+            color_mode: ColorMode::detect(),
+            batch: false,
+            frame: String::new(),
         }
     }
-}
 
-impl<R: Read, W: Write, L: InputListener<R, W>> Game<R, W, L> {
-    /// Initializes game with board and information area (optional).
+    /// Initializes game with board and information areas (optional).
     ///
     /// This method sets layout. Board and information will be displayed on the screen.
     /// Game state will be set to `GameState::Initialized`.
     ///
+    /// `infos` can be `None`, `Some(info)` or a list of information areas. Information areas are
+    /// placed in the order they are listed. Each area is attached to its side (see
+    /// [`InfoLayout`]) of the block formed by the board and all previous areas and takes the
+    /// whole length of this side. So if two areas have the same layout, the second one is placed
+    /// farther from the board. For example, for `[Right, Top]` areas the top area is above both
+    /// the board and the right area.
+    ///
+    /// [`InfoLayout`]: ../info/enum.InfoLayout.html
+    ///
     /// # Panics
     ///
-    /// This method can be called in `GameState::Created` or `GameState::Stopped` states only.
-    /// Panics if called in any other state.
+    /// This method can be called in `GameState::Created`, `GameState::Stopped` or
+    /// `GameState::Reviewing` states only. Panics if called in any other state. Use
+    /// [`try_init`] to get an error instead.
+    ///
+    /// [`try_init`]: #method.try_init
+    ///
+    /// # Examples
     ///
-    pub fn init(&mut self, board: Board, info: Option<Info>) {
-        if self.state != GameState::Created && self.state != GameState::Stopped {
-            panic!("You can initialize new or stopped game only.");
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// let board = Board::new(5, 5, 10, 5, true, None);
+    /// let title = Info::new(1, InfoLayout::Top, &["|^|Title"]);
+    /// let status = Info::new(20, InfoLayout::Right, &["Score: 0"]);
+    /// game.init(board, vec![status, title]);
+    /// # }
+    /// ```
+    pub fn init<I: IntoIterator<Item = Info>>(&mut self, board: Board, infos: I) {
+        if let Err(e) = self.try_init(board, infos) {
+            panic!("{}", e);
         }
+    }
+
+    /// Initializes game with board and information areas (optional).
+    ///
+    /// This method is the same as [`init`], but returns error instead of panicking.
+    ///
+    /// [`init`]: #method.init
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Created`,
+    /// `GameState::Stopped` or `GameState::Reviewing` state. Game isn't changed in this case.
+    ///
+    pub fn try_init<I>(&mut self, board: Board, infos: I) -> Result<(), GameError>
+        where I: IntoIterator<Item = Info>
+    {
+        self.check_state(&[GameState::Created, GameState::Stopped, GameState::Reviewing])?;
         self.board = Some(board);
-        self.info = info;
+        self.infos = infos.into_iter().collect();
+        self.focus = Focus::Board;
         self.layout();
 
         // Print initial screen
-        if let Some(ref mut board) = self.board {
-            self.output.write(board.get_border().as_bytes()).unwrap();
-            if let Some(updates) = board.get_updates() {
-                self.output.write(updates.as_bytes()).unwrap();
-            }
+        if let Some(ref board) = self.board {
+            self.frame.push_str(&board.get_border());
         }
-        if let Some(ref info) = self.info {
-            self.output.write(info.get_border().as_bytes()).unwrap();
-            if let Some(updates) = info.get_updates() {
-                self.output.write(updates.as_bytes()).unwrap();
-            }
+        for info in &self.infos {
+            self.frame.push_str(&info.get_border());
         }
-        self.output.flush().unwrap();
+        self.write_updates();
+
+        self.set_state(GameState::Initialized);
+        Ok(())
+    }
 
-        self.state = GameState::Initialized;
+    /// Initializes game with board and information areas (optional) if they fit the terminal.
+    ///
+    /// This method is the same as [`try_init`], but it checks terminal size first. Board and
+    /// information areas larger than the terminal are clipped, so this method can be used to
+    /// detect too small terminal. If terminal size is unknown, the check is skipped.
+    ///
+    /// [`try_init`]: #method.try_init
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Created`,
+    /// `GameState::Stopped` or `GameState::Reviewing` state and `GameError::Layout` if board and
+    /// information areas don't fit the terminal. Game isn't changed in these cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, GameError, Info, InfoLayout, InputListener, Terminal, Key,
+    ///                 LayoutError};
+    /// use gameboard::test::RecordingOutput;
+    /// # struct App {}
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    ///
+    /// // Terminal is 80x24.
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(io::empty(), output.clone(), Rc::clone(&app));
+    ///
+    /// let board = Board::new(50, 10, 1, 1, false, None);
+    /// let status = Info::new(30, InfoLayout::Right, &["Score: 0"]);
+    /// let error = LayoutError::TooLarge { needed: (84, 12), available: (80, 24) };
+    /// assert_eq!(game.init_checked(board, Some(status)), Err(GameError::Layout(error)));
+    /// assert!(output.screen_snapshot().iter().all(|row| row.trim().is_empty()));
+    ///
+    /// let board = Board::new(50, 10, 1, 1, false, None);
+    /// let status = Info::new(20, InfoLayout::Right, &["Score: 0"]);
+    /// assert_eq!(game.init_checked(board, Some(status)), Ok(()));
+    /// assert!(output.screen_snapshot()[1].contains("║Score: 0            ║"));
+    /// ```
+    pub fn init_checked<I>(&mut self, board: Board, infos: I) -> Result<(), GameError>
+        where I: IntoIterator<Item = Info>
+    {
+        self.check_state(&[GameState::Created, GameState::Stopped, GameState::Reviewing])?;
+        let infos: Vec<Info> = infos.into_iter().collect();
+        if let Ok((screen_w, screen_h)) = self.output.size() {
+            let needed = get_layout_size(&board, &infos);
+            let available = (screen_w as usize, screen_h as usize);
+            if needed.0 > available.0 || needed.1 > available.1 {
+                return Err(GameError::Layout(LayoutError::TooLarge { needed, available }));
+            }
+        }
+        self.try_init(board, infos)
     }
 
-    // Layout board and information area on the screen.
+    // Layout board and information areas on the screen.
     fn layout(&mut self) {
         if let Some(ref mut board) = self.board {
-            if let Some(ref mut info) = self.info {
-                let (b_w, b_h) = (board.get_width(), board.get_height());
-                let (mut i_w, mut i_h) = (board.get_width(), board.get_height());
-                let i_size = info.get_size();
-                let (b_x, b_y, i_x, i_y) = match info.get_layout() {
+            let (b_w, b_h) = (board.get_width() as isize, board.get_height() as isize);
+            // Bounds of the block formed by board and information areas. Coordinates are
+            // relative to the board top left corner.
+            let (mut left, mut top, mut right, mut bottom) = (0, 0, b_w, b_h);
+            let mut rects = Vec::with_capacity(self.infos.len());
+            for info in &self.infos {
+                let i_size = info.get_size() as isize;
+                // (x, y, width, height)
+                let rect = match info.get_layout() {
                     InfoLayout::Left => {
-                        i_w = i_size;
-                        (i_w + 1, SCREEN_TOP, SCREEN_LEFT, SCREEN_TOP)
+                        left -= i_size;
+                        (left, top, i_size, bottom - top)
                     }
                     InfoLayout::Right => {
-                        i_w = i_size;
-                        (SCREEN_LEFT, SCREEN_TOP, b_w + 1, SCREEN_TOP)
+                        right += i_size;
+                        (right - i_size, top, i_size, bottom - top)
                     }
                     InfoLayout::Top => {
-                        i_h = i_size;
-                        (SCREEN_LEFT, i_h + 1, SCREEN_LEFT, SCREEN_TOP)
+                        top -= i_size;
+                        (left, top, right - left, i_size)
                     }
                     InfoLayout::Bottom => {
-                        i_h = i_size;
-                        (SCREEN_LEFT, SCREEN_TOP, SCREEN_LEFT, b_h + 1)
+                        bottom += i_size;
+                        (left, bottom - i_size, right - left, i_size)
                     }
                 };
-                board.set_position(Position(b_x, b_y));
-                info.set_position_and_size(Position(i_x, i_y), i_w, i_h);
-            } else {
-                board.set_position(Position(SCREEN_LEFT, SCREEN_TOP));
-            };
+                rects.push(rect);
+            }
+            let screen_size = self.output.size().ok();
+            let (x, y) = get_layout_origin(self.alignment, screen_size, (right - left) as usize,
+                                           (bottom - top) as usize);
+            board.set_position(Position(x + (-left) as usize, y + (-top) as usize));
+            for (info, (i_x, i_y, i_w, i_h)) in self.infos.iter_mut().zip(rects) {
+                info.set_position_and_size(
+                    Position(x + (i_x - left) as usize, y + (i_y - top) as usize),
+                    i_w as usize, i_h as usize);
+            }
+        }
+    }
+
+    /// Sets alignment of the board and information area on the screen.
+    ///
+    /// Board and information area are placed at the top left corner of the screen by default.
+    /// If they are larger than the screen, they are placed at the top left corner regardless of
+    /// alignment.
+    ///
+    /// If game is initialized already, the layout is recomputed and the screen is redrawn.
+    ///
+    pub fn set_alignment(&mut self, alignment: ScreenAlign) {
+        self.alignment = alignment;
+        if self.state != GameState::Created {
+            self.layout();
+            self.redraw();
         }
     }
 
+    /// Enables or disables input coalescing.
+    ///
+    /// When holding a key, terminal repeats it faster than the game can redraw the screen, so
+    /// cursor keeps moving after the key is released. In coalescing mode all input events
+    /// available at the moment are read at once, identical consecutive key presses are collapsed
+    /// into one and the screen is updated once for all of them.
+    ///
+    /// Coalescing is disabled by default, so every key press is handled. It works with
+    /// non-blocking input only (see [`start_with_tick`]). Blocking input can't report whether
+    /// more keys are available, so [`start`] handles keys one by one regardless of this setting.
+    ///
+    /// [`start`]: #method.start
+    /// [`start_with_tick`]: #method.start_with_tick
+    ///
+    pub fn set_input_coalescing(&mut self, enabled: bool) {
+        self.input_coalescing = enabled;
+    }
+
     /// Starts listening user input.
     ///
     /// Game state will be set to `GameState::Started`.
@@ -247,208 +1093,2921 @@ impl<R: Read, W: Write, L: InputListener<R, W>> Game<R, W, L> {
     ///
     /// This method can be called in `GameState::Initialized` or `GameState::Stopped` states only.
     /// Panics if called in any other state. Also it panics if input listener object was dropped.
+    /// Use [`try_start`] to get an error instead.
+    ///
+    /// [`try_start`]: #method.try_start
     ///
     pub fn start(&mut self) {
-        if self.state != GameState::Initialized && self.state != GameState::Stopped {
-            panic!("You can start initialized or stopped game only.");
-        }
-        self.state = GameState::Started;
-
-        if let Some(listener) = self.listener.upgrade() {
-            while self.state == GameState::Started || self.state == GameState::Paused {
-                let key = match self.input.next() {
-                    None => break,
-                    Some(res) => match res {
-                        Err(_) => continue,
-                        Ok(c) => c
-                    }
-                };
-                if self.state == GameState::Paused {
-                    if let Some(resume_key) = self.resume_key {
-                        if key == resume_key {
-                            // In 'Paused' state we call key handler only if resume key is
-                            // pressed. User should call resume().
-                            listener.borrow_mut().handle_key(key, self);
-                        }
-                    }
-                } else {
-                    if let Some(ref mut board) = self.board {
-                        // We pass key to board first. If board has cursor, it'll try to handle
-                        // cursor movement and return new cursor position. Otherwise, user key
-                        // handler will be called.
-                        match board.handle_key(key) {
-                            KeyHandleResult::NotHandled =>
-                                listener.borrow_mut().handle_key(key, self),
-                            KeyHandleResult::NewPosition(pos) =>
-                                listener.borrow_mut().cursor_moved(pos, self),
-                            KeyHandleResult::Consumed => {},
-                        }
-                    }
-                }
-                // Update screen.
-                if let Some(ref mut board) = self.board {
-                    if let Some(updates) = board.get_updates() {
-                        self.output.write(updates.as_bytes()).unwrap();
-                    }
-                }
-                if let Some(ref info) = self.info {
-                    if let Some(updates) = info.get_updates() {
-                        self.output.write(updates.as_bytes()).unwrap();
-                    }
-                }
-                self.output.flush().unwrap();
-            }
-        } else {
-            panic!("You cannot start game without listener. Listener was dropped.");
-        };
+        if let Err(e) = self.try_start() {
+            panic!("{}", e);
+        }
     }
 
-    /// Stops listening user input.
+    /// Starts listening user input.
     ///
-    /// Game state will be set to `GameState::Stopped`.
+    /// This method is the same as [`start`], but returns error instead of panicking.
     ///
-    /// # Panics
+    /// [`start`]: #method.start
     ///
-    /// This method can be called in `GameState::Started` state only.
-    /// Panics if called in any other state.
+    /// # Errors
     ///
-    pub fn stop(&mut self) {
-        if self.state != GameState::Started {
-            panic!("You can stop started game only.");
-        }
-        self.state = GameState::Stopped;
-    }
-
-    /// Pauses listening user input (except resume key).
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Initialized` or
+    /// `GameState::Stopped` state and `GameError::ListenerDropped` if input listener object was
+    /// dropped. Game state isn't changed in these cases.
     ///
-    /// Game state will be set to `GameState::Paused`. This method is added for convenience.
-    /// The same functionality can easily be done on the client side.
-    /// All key presses are ignored while in `GameState::Paused` state. Pressing
-    /// resume key will call `handle_key`. User must call [`resume`] to get back to
-    /// `GameState::Started` state.
+    /// # Examples
     ///
-    /// [`resume`]: #method.resume
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, GameError, GameState, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
     ///
-    /// # Panics
+    /// struct App {}
     ///
-    /// This method can be called in `GameState::Started` state only.
-    /// Panics if called in any other state.
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
     ///
-    pub fn pause(&mut self, resume_key: Key) {
-        if self.state != GameState::Started {
-            panic!("You can pause started game only.");
-        }
-        self.resume_key = Some(resume_key);
-        self.state = GameState::Paused;
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(io::empty(), output, Rc::clone(&app));
+    /// assert_eq!(game.try_start(), Err(GameError::WrongState {
+    ///     expected: &[GameState::Initialized, GameState::Stopped],
+    ///     actual: GameState::Created,
+    /// }));
+    ///
+    /// game.try_init(Board::new(3, 3, 1, 1, false, None), None).unwrap();
+    /// drop(app);
+    /// assert_eq!(game.try_start(), Err(GameError::ListenerDropped));
+    /// assert_eq!(game.get_state(), GameState::Initialized);
+    /// ```
+    pub fn try_start(&mut self) -> Result<(), GameError> {
+        self.check_state(&[GameState::Initialized, GameState::Stopped])?;
+        let listener = self.listener.take().ok_or(GameError::ListenerDropped)?;
+        self.begin(&listener);
+        self.listener.restore(listener);
+        self.blocking_input = true;
+        while self.step() {}
+        self.blocking_input = false;
+        Ok(())
     }
 
-    /// Starts listening all user input.
+    /// Runs one iteration of the game loop.
     ///
-    /// Game state will be set to `GameState::Started`.
+    /// At most one input event is read and dispatched, then screen updates are written and
+    /// output is flushed. This method is an alternative to [`start`] for applications which
+    /// run their own event loop. Input stream should be non-blocking (for example,
+    /// `termion::async_stdin`), otherwise this method waits for the next key.
+    ///
+    /// If game is in `GameState::Initialized` state, it is started first (as [`start`] does).
+    /// Returns `true` if game is still running (it is in `GameState::Started` or
+    /// `GameState::Paused` state). Nothing is done if game isn't running, so the loop should end
+    /// when this method returns `false`. Ticks are not generated, use [`start_with_tick`] if you
+    /// need them.
+    ///
+    /// [`start`]: #method.start
+    /// [`start_with_tick`]: #method.start_with_tick
     ///
     /// # Panics
     ///
-    /// This method can be called in `GameState::Paused` state only.
-    /// Panics if called in any other state.
+    /// Panics if input listener object was dropped.
     ///
-    pub fn resume(&mut self) {
-        if self.state != GameState::Paused {
-            panic!("You can resume paused game only.");
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, GameState, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// struct App {
+    ///     keys: usize,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         self.keys += 1;
+    ///         if key == Key::Char('q') {
+    ///             game.stop();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App { keys: 0 }));
+    /// let input = ScriptedInput::new(&[Key::Char('a'), Key::Char('q'), Key::Char('b')]);
+    /// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    ///
+    /// let mut steps = 0;
+    /// while game.step() {
+    ///     // Other work of the application loop.
+    ///     steps += 1;
+    /// }
+    /// assert_eq!(steps, 1);
+    /// assert_eq!(app.borrow().keys, 2);
+    /// assert_eq!(game.get_state(), GameState::Stopped);
+    /// ```
+    pub fn step(&mut self) -> bool {
+        let listener = match self.listener.take() {
+            Some(listener) => listener,
+            None => panic!("{}", GameError::ListenerDropped),
+        };
+        if self.state == GameState::Initialized {
+            self.begin(&listener);
         }
-        self.resume_key = None;
-        self.state = GameState::Started;
+        let running = self.is_running() && self.step_with(&listener);
+        self.listener.restore(listener);
+        running
     }
 
-    /// Returns game state.
-    pub fn get_state(&self) -> GameState {
-        self.state
+    // Run one iteration of the game loop. Return `false` if game isn't running anymore or
+    // blocking input ended.
+    fn step_with(&mut self, listener: &RefCell<L>) -> bool {
+        if !self.dispatch_next_event(listener) {
+            if self.blocking_input {
+                return false
+            }
+            // No input, but game could be changed by the application.
+            self.check_resize(listener);
+            self.dispatch_state_changes(listener);
+            self.write_updates();
+        }
+        self.is_running()
     }
 
-    /// Updates cells content.
+    /// Runs the game until user quits.
+    ///
+    /// Game is initialized with board and information areas returned by `setup` and started.
+    /// Information areas can be anything [`init`] takes: a list, `Some(info)` or `None`. When
+    /// game is stopped (for example, to replay), `setup` is called again and the new game is
+    /// started. The loop ends when [`should_exit`] returns `true` (see [`set_quit_key`] and
+    /// [`exit`]) or input stream is closed. Input listener can be reset in `setup` as well.
+    ///
+    /// [`init`]: #method.init
+    /// [`should_exit`]: #method.should_exit
+    /// [`set_quit_key`]: #method.set_quit_key
+    /// [`exit`]: #method.exit
     ///
     /// # Panics
     ///
-    /// Panics if message dialog is open.
+    /// This method can be called in `GameState::Created` or `GameState::Stopped` states only.
+    /// Panics if called in any other state. Also it panics if input listener object was dropped.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// let mut updates = CellUpdates::with_capacity(2);
-    /// updates.push((Cell::Empty, Position(0, 1)));
-    /// updates.push((Cell::Char('x'), Position(0, 2)));
-    /// game.update_cells(updates);
+    /// # use std::io::{self, Read};
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// # struct App {}
+    /// # impl App { fn reset(&mut self) {} }
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// let stdout = io::stdout();
+    /// let stdin = io::stdin();
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let mut game = Game::new(stdin.lock(), stdout.lock(), Rc::clone(&app));
+    /// game.set_quit_key(Some(Key::Char('q')));
+    /// game.run(|| {
+    ///     app.borrow_mut().reset();
+    ///     let status = Info::new(12, InfoLayout::Right, &["Your turn"]);
+    ///     let title = Info::new(1, InfoLayout::Top, &["|^|Tic-tac-toe"]);
+    ///     (Board::new(3, 3, 1, 1, false, None), vec![status, title])
+    /// });
     /// ```
-    pub fn update_cells(&mut self, updates: CellUpdates) {
-        if let Some(ref mut board) = self.board {
-            board.update_cells(updates);
+    pub fn run<I, F>(&mut self, mut setup: F)
+        where I: IntoIterator<Item = Info>, F: FnMut() -> (Board, I)
+    {
+        while !self.should_exit {
+            let (board, infos) = setup();
+            self.init(board, infos);
+            self.start();
+            if self.state != GameState::Stopped {
+                // Input stream is closed.
+                break;
+            }
         }
     }
 
-    /// Updates information area content.
+    /// Starts listening user input and calls [`on_tick`] every `interval`.
+    ///
+    /// This method is the same as [`start`], but it doesn't block waiting for user input. It can
+    /// be used for games, where something happens without user actions (falling blocks,
+    /// countdowns etc.). Game input stream must be non-blocking, for example
+    /// `termion::async_stdin()`. If input stream blocks, ticks can be delayed until key is pressed.
+    ///
+    /// Ticks are called on the same thread as key handlers. Blinking [`Cursor`] is toggled every
+    /// tick. Pulses started with `pulse_cells` are advanced every tick as well.
+    ///
+    /// `on_tick` isn't called while the game is paused, [`tick_count`] and [`elapsed`] are frozen
+    /// as well. Cursor blinking, toasts and pulses keep running.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    /// [`on_tick`]: trait.InputListener.html#method.on_tick
+    /// [`start`]: #method.start
+    /// [`tick_count`]: #method.tick_count
+    /// [`elapsed`]: #method.elapsed
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Initialized` or `GameState::Stopped` states only.
+    /// Panics if called in any other state. Also it panics if input listener object was dropped.
+    /// Use [`try_start_with_tick`] to get an error instead.
+    ///
+    /// [`try_start_with_tick`]: #method.try_start_with_tick
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// game.update_info(&[
-    ///     "This is line 1.",
-    ///     "",
-    ///     "This is line 3.",
-    ///     "This is line 4.",
-    /// ]);
+    /// # use std::io::{self, Read};
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use std::time::Duration;
+    /// # use gameboard::Key;
+    /// # use gameboard::{Board, Game, InputListener, Terminal};
+    /// # struct App {}
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// let stdout = io::stdout();
+    /// let stdout = stdout.lock();
+    /// let stdin = termion::async_stdin();
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let game = Rc::new(RefCell::new(Game::new(stdin, stdout, Rc::clone(&app))));
+    /// game.borrow_mut().init(Board::new(5, 5, 1, 1, false, None), None);
+    /// game.borrow_mut().start_with_tick(Duration::from_millis(500));
     /// ```
-    pub fn update_info(&mut self, lines: &[&str]) {
-        if let Some(ref mut info) = self.info {
-            info.update(lines);
+    pub fn start_with_tick(&mut self, interval: Duration) {
+        if let Err(e) = self.try_start_with_tick(interval) {
+            panic!("{}", e);
         }
     }
 
-    /// Shows message dialog.
+    /// Starts listening user input and calls [`on_tick`] every `interval`.
     ///
-    /// This dialog can be used to ask user a questions. This dialog is modal. You can't update
-    /// board cells while it is open. It can be closed by calling [`hide_message`].
+    /// This method is the same as [`start_with_tick`], but returns error instead of panicking.
     ///
-    /// The dialog is displayed over the board. It will be centered automatically. It can't be
-    /// larger than board: last lines will be ignored, too long lines will be truncated.
+    /// [`on_tick`]: trait.InputListener.html#method.on_tick
+    /// [`start_with_tick`]: #method.start_with_tick
     ///
-    /// *lines* is a list of strings to display in the dialog. If you want space between lines,
-    /// add empty string to list.
+    /// # Errors
     ///
-    /// Text alignment:
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Initialized` or
+    /// `GameState::Stopped` state and `GameError::ListenerDropped` if input listener object was
+    /// dropped. Game state isn't changed in these cases.
     ///
-    /// * Lines are left-aligned by default
-    /// * Lines started with *|^|* are centered
-    /// * Lines started with *|>|* are right-aligned
+    pub fn try_start_with_tick(&mut self, interval: Duration) -> Result<(), GameError> {
+        self.check_state(&[GameState::Initialized, GameState::Stopped])?;
+        let listener = self.listener.take().ok_or(GameError::ListenerDropped)?;
+        self.begin(&listener);
+        self.polled_input = true;
+        let mut last_time = Instant::now();
+        let mut next_tick = last_time + interval;
+        while self.is_running() {
+            self.check_resize(&listener);
+            let has_input = if self.input_coalescing {
+                self.dispatch_available_events(&listener)
+            } else {
+                match self.input.next() {
+                    None => false,
+                    Some(Err(_)) => true,
+                    Some(Ok(event)) => {
+                        self.dispatch_event(event, &listener);
+                        true
+                    }
+                }
+            };
+            let now = Instant::now();
+            // Game clock doesn't run while paused.
+            if self.state != GameState::Paused {
+                self.elapsed += now - last_time;
+            }
+            last_time = now;
+            let is_tick = now >= next_tick && self.state != GameState::Stopped;
+            if is_tick {
+                if let Some(ref mut board) = self.board {
+                    board.blink_cursor();
+                    board.tick_toast();
+                    board.tick_pulse();
+                    board.tick_slides();
+                }
+                if self.state == GameState::Started {
+                    self.tick_count += 1;
+                    listener.borrow_mut().on_tick(self);
+                }
+                // If we are late, skip missed ticks.
+                next_tick = (next_tick + interval).max(now);
+            }
+            self.dispatch_state_changes(&listener);
+            if has_input || is_tick {
+                self.write_updates();
+            } else {
+                thread::sleep((next_tick - now).min(INPUT_POLL_INTERVAL));
+            }
+        }
+        self.polled_input = false;
+        self.listener.restore(listener);
+        Ok(())
+    }
+
+    /// Returns number of ticks since the game was started.
     ///
-    /// # Implementation note
+    /// Counter is incremented before every [`on_tick`] call and reset when the game is started.
+    /// Ticks skipped because the game was late are not counted. Counter doesn't change while the
+    /// game is paused. If game was started with blocking [`start`], it returns 0.
     ///
-    /// This crate iterates Unicode strings as a set of [grapheme clusters] to handle characters
-    /// like *g̈* correctly. When we slice strings to put them inside cells or dialogs, we expect
-    /// characters to have the same width. This is not always true for some Unicode symbols. Such
-    /// symbols will break layout.
+    /// [`on_tick`]: trait.InputListener.html#method.on_tick
+    /// [`start`]: #method.start
     ///
-    /// [`hide_message`]: #method.hide_message
-    /// [grapheme clusters]: http://www.unicode.org/reports/tr29/
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use std::time::Duration;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    ///
+    ///     fn on_tick(&mut self, game: &mut Game<R, W, Self>) {
+    ///         // Stop after 3 ticks.
+    ///         if game.tick_count() == 3 {
+    ///             game.stop();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(io::empty(), output, Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// game.start_with_tick(Duration::from_millis(10));
+    /// assert_eq!(game.tick_count(), 3);
+    /// assert!(game.elapsed() >= Duration::from_millis(30));
+    ///
+    /// // Input is closed, blocking loop exits at once.
+    /// game.start();
+    /// assert_eq!(game.tick_count(), 0);
+    /// assert_eq!(game.elapsed(), Duration::from_secs(0));
+    /// ```
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+
+    /// Returns time since the game was started with [`start_with_tick`].
+    ///
+    /// Time is updated by the tick loop and reset when the game is started. Time while the game
+    /// is paused is not counted. If game was started with blocking [`start`], it returns 0. See
+    /// [`tick_count`] for example.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// game.show_message(&[
-    ///     "|^|Congratulations! You win!",
-    ///     "",
-    ///     "Press 'r' to replay.",
-    ///     "Press 'q' to quit.",
-    /// ]);
+    /// Pause for 80 ms doesn't change tick counter and game time.
     /// ```
-    pub fn show_message(&mut self, lines: &[&str]) {
-        if let Some(ref mut board) = self.board {
-            board.show_message(lines);
+    /// # use std::io::{self, Read};
+    /// use std::cell::{Cell, RefCell};
+    /// use std::rc::Rc;
+    /// use std::time::{Duration, Instant};
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// const PAUSE: Duration = Duration::from_millis(80);
+    ///
+    /// // Non-blocking input which presses 'r' when the game has been paused for `PAUSE`.
+    /// struct ResumeKey {
+    ///     paused_at: Rc<Cell<Option<Instant>>>,
+    /// }
+    ///
+    /// impl Read for ResumeKey {
+    ///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    ///         match self.paused_at.get() {
+    ///             Some(time) if time.elapsed() >= PAUSE => {
+    ///                 self.paused_at.set(None);
+    ///                 buf[0] = b'r';
+    ///                 Ok(1)
+    ///             },
+    ///             _ => Ok(0)
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct App {
+    ///     ticks: Vec<u64>,
+    ///     paused_at: Rc<Cell<Option<Instant>>>,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, game: &mut Game<R, W, Self>) {
+    ///         game.resume();
+    ///     }
+    ///
+    ///     fn on_tick(&mut self, game: &mut Game<R, W, Self>) {
+    ///         self.ticks.push(game.tick_count());
+    ///         match game.tick_count() {
+    ///             2 => {
+    ///                 game.pause(Key::Char('r'));
+    ///                 self.paused_at.set(Some(Instant::now()));
+    ///             },
+    ///             4 => game.stop(),
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let paused_at = Rc::new(Cell::new(None));
+    /// let input = ResumeKey { paused_at: Rc::clone(&paused_at) };
+    /// let app = Rc::new(RefCell::new(App { ticks: Vec::new(), paused_at }));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(input, output, Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// let started = Instant::now();
+    /// game.start_with_tick(Duration::from_millis(10));
+    ///
+    /// assert_eq!(app.borrow().ticks, vec![1, 2, 3, 4]);
+    /// // Pause isn't counted, up to one tick interval of it may be.
+    /// assert!(started.elapsed() - game.elapsed() >= PAUSE - Duration::from_millis(10));
+    /// ```
+    ///
+    /// [`start_with_tick`]: #method.start_with_tick
+    /// [`start`]: #method.start
+    /// [`tick_count`]: #method.tick_count
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    // Start the game and notify the listener.
+    fn begin(&mut self, listener: &RefCell<L>) {
+        self.set_state(GameState::Started);
+        self.screen_size = self.output.size().ok();
+        self.reset_clock();
+        self.dispatch_state_changes(listener);
+    }
+
+    fn is_running(&self) -> bool {
+        self.state == GameState::Started || self.state == GameState::Paused
+    }
+
+    // Check if mouse input is handled: game is started or reviewed.
+    fn is_interactive(&self) -> bool {
+        self.state == GameState::Started || self.state == GameState::Reviewing
+    }
+
+    // Read one input event, dispatch it and write screen updates. Return `false` if there is no
+    // input.
+    fn dispatch_next_event(&mut self, listener: &RefCell<L>) -> bool {
+        let event = match self.input.next() {
+            None => return false,
+            Some(Err(_)) => return true,
+            Some(Ok(e)) => e
+        };
+        self.check_resize(listener);
+        self.dispatch_event(event, listener);
+        self.dispatch_state_changes(listener);
+        self.write_updates();
+        true
+    }
+
+    // Reset tick counter and elapsed time.
+    fn reset_clock(&mut self) {
+        self.tick_count = 0;
+        self.elapsed = Duration::from_secs(0);
+    }
+
+    // Report state changes to listener. Listener can change state again, these changes are
+    // reported as well.
+    fn dispatch_state_changes(&mut self, listener: &RefCell<L>) {
+        while !self.state_changes.is_empty() || !self.focus_changes.is_empty() {
+            for (old, new) in mem::take(&mut self.state_changes) {
+                listener.borrow_mut().on_state_change(old, new, self);
+            }
+            for (old, new) in mem::take(&mut self.focus_changes) {
+                listener.borrow_mut().on_focus_change(old, new, self);
+            }
         }
     }
 
-    /// Hides message dialog.
-    pub fn hide_message(&mut self) {
+    // Read all available input events and dispatch them. Identical consecutive key presses are
+    // dispatched once. Return `true` if there was any input.
+    fn dispatch_available_events(&mut self, listener: &RefCell<L>) -> bool {
+        let mut events: Vec<Event> = Vec::new();
+        let mut has_input = false;
+        for _ in 0..MAX_COALESCED_EVENTS {
+            match self.input.next() {
+                None => break,
+                Some(Err(_)) => has_input = true,
+                Some(Ok(event)) => {
+                    has_input = true;
+                    let is_repeat = match (&event, events.last()) {
+                        (Event::Key(key), Some(Event::Key(last))) => key == last,
+                        _ => false
+                    };
+                    if !is_repeat {
+                        events.push(event);
+                    }
+                }
+            }
+        }
+        for event in events {
+            if self.state == GameState::Stopped {
+                break;
+            }
+            self.dispatch_event(event, listener);
+        }
+        has_input
+    }
+
+    // Pass input event to the board or to the listener.
+    fn dispatch_event(&mut self, event: Event, listener: &RefCell<L>) {
+        match event {
+            Event::Key(key) => self.dispatch_key(event::to_key(key), listener),
+            Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y)) =>
+                self.dispatch_click(x, y, listener),
+            Event::Mouse(MouseEvent::Press(MouseButton::Right, x, y)) =>
+                self.dispatch_right_click(x, y, listener),
+            Event::Mouse(MouseEvent::Hold(x, y)) => self.dispatch_hover(x, y, listener),
+            Event::Unsupported(seq) => {
+                if let Some((x, y)) = input::parse_mouse_motion(&seq) {
+                    self.dispatch_hover(x, y, listener);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // Pass mouse click to the board cursor and to the listener.
+    fn dispatch_click(&mut self, x: u16, y: u16, listener: &RefCell<L>) {
+        if !self.is_interactive() || self.is_message_open() {
+            return
+        }
         if let Some(ref mut board) = self.board {
-            board.hide_message();
+            if let Some((pos, res)) = board.handle_click(x, y) {
+                if let KeyHandleResult::NewPosition(pos) = res {
+                    listener.borrow_mut().cursor_moved(pos, self);
+                }
+                listener.borrow_mut().on_click(pos, self);
+                return
+            }
+        }
+        let click = self.infos.iter().enumerate().find_map(|(i, info)| {
+            info.get_text_position(x as usize, y as usize).map(|(row, col)| (i, row, col))
+        });
+        if let Some((index, row, col)) = click {
+            listener.borrow_mut().on_info_click(index, row, col, self);
         }
     }
+
+    // Toggle clicked cell and notify the listener.
+    fn dispatch_right_click(&mut self, x: u16, y: u16, listener: &RefCell<L>) {
+        if self.state != GameState::Started || self.is_message_open() {
+            return
+        }
+        let toggled = self.board.as_mut().and_then(|b| b.handle_right_click(x, y));
+        if let Some((pos, index)) = toggled {
+            listener.borrow_mut().on_cell_toggle(pos, index, self);
+        }
+    }
+
+    // Highlight cell under mouse pointer and notify the listener.
+    fn dispatch_hover(&mut self, x: u16, y: u16, listener: &RefCell<L>) {
+        if !self.is_interactive() {
+            return
+        }
+        if let Some(ref mut board) = self.board {
+            if let Some(pos) = board.handle_mouse_move(x, y) {
+                listener.borrow_mut().on_hover(pos, self);
+            }
+        }
+    }
+
+    // Pass key to the board cursor or to the listener.
+    fn dispatch_key(&mut self, key: Key, listener: &RefCell<L>) {
+        if self.quit_on_ctrl_c && key == Key::Ctrl('c') {
+            self.exit();
+        } else if self.state == GameState::Paused {
+            if self.resume_keys.contains(&key) {
+                // In 'Paused' state we call key handler only if resume key is
+                // pressed. User should call resume().
+                listener.borrow_mut().handle_key(key, self);
+            }
+        } else if self.quit_key == Some(key) {
+            if self.confirm_quit(QUIT_MESSAGE) {
+                self.exit();
+            }
+        } else if self.focus_key == Some(key) && !self.is_message_open() {
+            self.toggle_focus();
+        } else if let Some(pos) = self.get_toggle_position(key) {
+            if let Some(index) = self.toggle_cell(pos) {
+                listener.borrow_mut().on_cell_toggle(pos, index, self);
+            }
+        } else if let (Focus::Info(index), false) = (self.focus, self.is_message_open()) {
+            // Scroll keys are handled by focused information area, other keys are passed to the
+            // listener. Board cursor doesn't receive keys.
+            if !self.handle_info_key(index, key) {
+                match self.bindings.get_action(key) {
+                    Some(action) => listener.borrow_mut().on_action(action, self),
+                    None => listener.borrow_mut().handle_key(key, self),
+                }
+            }
+        } else if let Some(ref mut board) = self.board {
+            // We pass key to board first. If board has cursor, it'll try to handle
+            // cursor movement and return new cursor position. Otherwise, user key
+            // handler will be called.
+            match board.handle_key(key) {
+                KeyHandleResult::NotHandled => match self.bindings.get_action(key) {
+                    Some(action) => listener.borrow_mut().on_action(action, self),
+                    None => listener.borrow_mut().handle_key(key, self),
+                },
+                KeyHandleResult::NewPosition(pos) =>
+                    listener.borrow_mut().cursor_moved(pos, self),
+                KeyHandleResult::Blocked(direction) =>
+                    listener.borrow_mut().cursor_blocked(direction, self),
+                KeyHandleResult::Consumed => {},
+                KeyHandleResult::DialogResult(index) =>
+                    listener.borrow_mut().on_dialog_result(index, self),
+            }
+        }
+    }
+
+    // Get cursor position if key is toggle key. Return `None` if board has no cursor, board
+    // isn't focused, message dialog is open or game isn't started.
+    fn get_toggle_position(&self, key: Key) -> Option<Position> {
+        if self.toggle_key != Some(key) || self.focus != Focus::Board || self.is_message_open() ||
+           self.state != GameState::Started {
+            return None
+        }
+        self.board.as_ref().and_then(|b| b.get_cursor_position())
+    }
+
+    // Scroll information area by key. Return `false` if key isn't a scroll key.
+    fn handle_info_key(&mut self, index: usize, key: Key) -> bool {
+        let page = match self.infos.get(index) {
+            Some(info) => info.get_page_height().max(1) as isize,
+            None => return false
+        };
+        let dy = match key {
+            Key::Up => -1,
+            Key::Down => 1,
+            Key::PageUp => -page,
+            Key::PageDown => page,
+            Key::Home => isize::MIN,
+            Key::End => isize::MAX,
+            _ => return false
+        };
+        self.scroll_info(index, dy);
+        true
+    }
+
+    // Check if terminal was resized. If it was, notify listener, layout and redraw the screen.
+    fn check_resize(&mut self, listener: &RefCell<L>) {
+        let size = self.output.size().ok();
+        if size != self.screen_size {
+            self.screen_size = size;
+            if let Some((width, height)) = size {
+                listener.borrow_mut().on_resize(width, height, self);
+                self.layout();
+                self.redraw();
+            }
+        }
+    }
+
+    // Render board and information area updates into the frame and write it to the output.
+    fn write_updates(&mut self) {
+        if self.batch {
+            // Updates are collected by board and information areas until the batch is ended.
+            return
+        }
+        if let Some(ref mut board) = self.board {
+            if let Some(updates) = board.get_updates() {
+                self.frame.push_str(&updates);
+            }
+        }
+        for info in &mut self.infos {
+            if let Some(updates) = info.get_updates() {
+                self.frame.push_str(&updates);
+            }
+        }
+        if !self.frame.is_empty() {
+            let frame = color::convert_colors(&self.frame, self.color_mode);
+            self.output.write_all(frame.as_bytes()).unwrap();
+            self.frame.clear();
+        }
+        self.output.flush().unwrap();
+    }
+
+    /// Redraws the whole screen.
+    ///
+    /// The screen is cleared, board, information area and message dialog (if it is open) are
+    /// drawn again. Use this method if terminal content was damaged, for example, by another
+    /// program or after returning from a suspended shell. The new screen content is written with
+    /// a single write, like all other screen updates, to avoid flicker.
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read, Write};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Key, Terminal};
+    ///
+    /// // Terminal which counts write calls.
+    /// struct Counter {
+    ///     writes: Rc<RefCell<usize>>,
+    /// }
+    ///
+    /// impl Write for Counter {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         *self.writes.borrow_mut() += 1;
+    ///         Ok(buf.len())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// impl Terminal for Counter {
+    ///     fn size(&self) -> io::Result<(u16, u16)> {
+    ///         Ok((120, 40))
+    ///     }
+    /// }
+    ///
+    /// # struct App;
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// let writes = Rc::new(RefCell::new(0));
+    /// let output = Counter { writes: Rc::clone(&writes) };
+    /// let app = Rc::new(RefCell::new(App));
+    /// let mut game = Game::with_terminal(io::empty(), output, Rc::clone(&app));
+    /// let mut board = Board::new(50, 20, 1, 1, false, None);
+    /// board.init_from_str(&"#".repeat(1000), None);
+    /// game.init(board, vec![Info::new(20, InfoLayout::Right, &["Bombs left", "100"])]);
+    ///
+    /// *writes.borrow_mut() = 0;
+    /// game.redraw();
+    /// // Cleared screen, board and information area are written at once.
+    /// assert_eq!(*writes.borrow(), 1);
+    /// ```
+    pub fn redraw(&mut self) {
+        if self.state == GameState::Created {
+            panic!("You can't redraw game before initialization.");
+        }
+        self.frame.push_str(clear::All.as_ref());
+        if let Some(ref mut board) = self.board {
+            board.request_full_redraw();
+        }
+        for info in &mut self.infos {
+            info.request_full_redraw();
+            self.frame.push_str(&info.get_border());
+        }
+        self.write_updates();
+    }
+
+    /// Writes arbitrary content to the terminal.
+    ///
+    /// Content is written as is and output is flushed. It can contain [escape sequences], for
+    /// example, `termion::cursor::Goto` to set position and `termion::color` to set colors.
+    /// Board and information areas are not affected, game doesn't know about this content.
+    ///
+    /// Caller is responsible for positioning. Content written over the board or information
+    /// areas is overwritten when they are updated. Use [`get_board_rect`] and
+    /// [`get_info_rect`] to find free space. The whole screen is cleared on [`redraw`] and after
+    /// terminal resize, content is lost in this case and should be written again. Style is not
+    /// reset after the content.
+    ///
+    /// [escape sequences]: https://en.wikipedia.org/wiki/ANSI_escape_code
+    /// [`get_board_rect`]: #method.get_board_rect
+    /// [`get_info_rect`]: #method.get_info_rect
+    /// [`redraw`]: #method.redraw
+    ///
+    /// # Examples
+    ///
+    /// Footer line below the board.
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// use termion::{cursor, style};
+    ///
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// if let Some(rect) = game.get_board_rect() {
+    ///     let y = (rect.y + rect.height) as u16;
+    ///     game.write_raw(&format!("{}{}Press 'q' to quit.{}", cursor::Goto(rect.x as u16, y),
+    ///                             style::Italic, style::Reset));
+    /// }
+    /// # }
+    /// ```
+    pub fn write_raw(&mut self, content: &str) {
+        self.output.write_all(content.as_bytes()).unwrap();
+        self.output.flush().unwrap();
+    }
+
+    /// Stops listening user input.
+    ///
+    /// Game state will be set to `GameState::Stopped`.
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Started` or `GameState::Reviewing` state only.
+    /// Panics if called in any other state. Use [`try_stop`] to get an error instead.
+    ///
+    /// [`try_stop`]: #method.try_stop
+    ///
+    pub fn stop(&mut self) {
+        if let Err(e) = self.try_stop() {
+            panic!("{}", e);
+        }
+    }
+
+    /// Stops listening user input.
+    ///
+    /// This method is the same as [`stop`], but returns error instead of panicking.
+    ///
+    /// [`stop`]: #method.stop
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Started` or
+    /// `GameState::Reviewing` state.
+    ///
+    pub fn try_stop(&mut self) -> Result<(), GameError> {
+        self.check_state(&[GameState::Started, GameState::Reviewing])?;
+        self.set_stopped();
+        Ok(())
+    }
+
+    /// Lets user inspect the final board after the game is stopped.
+    ///
+    /// Game enters `GameState::Reviewing` state and listens user input until [`stop`] or
+    /// [`exit`] is called or input stream is closed. Board content isn't changed. Cursor can be
+    /// moved, mouse clicks and keys are passed to the input listener as usual, so it can scroll
+    /// the board or show details. Ticks are not generated, cells can't be toggled by toggle key.
+    /// Input listener can call [`init`] to start a new game as well.
+    ///
+    /// [`stop`]: #method.stop
+    /// [`exit`]: #method.exit
+    /// [`init`]: #method.init
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Stopped` state only. Panics if called in any
+    /// other state. Also it panics if input listener object was dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Cursor, Game, GameState, InputListener, Terminal, Key, Position};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    /// use termion::color;
+    ///
+    /// struct App {
+    ///     position: Position,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         if key == Key::Char('q') {
+    ///             game.stop();
+    ///         }
+    ///     }
+    ///
+    ///     fn cursor_moved(&mut self, position: Position, _game: &mut Game<R, W, Self>) {
+    ///         self.position = position;
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App { position: Position(0, 0) }));
+    /// let input = ScriptedInput::new(&[Key::Char('q'), Key::Right, Key::Char('q')]);
+    /// let mut game = Game::with_terminal(input, output.clone(), Rc::clone(&app));
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(2, 1, 1, 1, false, None);
+    /// board.init_from_str("xo", Some(cursor));
+    /// game.init(board, None);
+    /// game.start();
+    /// assert_eq!(game.get_state(), GameState::Stopped);
+    ///
+    /// output.clear();
+    /// game.review();
+    /// assert!(app.borrow().position == Position(1, 0));
+    /// // Cursor movement is drawn.
+    /// let updates = String::from_utf8(output.output()).unwrap();
+    /// assert!(updates.contains('x') && updates.contains('o'));
+    /// assert_eq!(game.get_state(), GameState::Stopped);
+    /// ```
+    pub fn review(&mut self) {
+        if let Err(e) = self.try_review() {
+            panic!("{}", e);
+        }
+    }
+
+    /// Lets user inspect the final board after the game is stopped.
+    ///
+    /// This method is the same as [`review`], but returns error instead of panicking.
+    ///
+    /// [`review`]: #method.review
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Stopped` state and
+    /// `GameError::ListenerDropped` if input listener object was dropped.
+    pub fn try_review(&mut self) -> Result<(), GameError> {
+        self.check_state(&[GameState::Stopped])?;
+        let listener = self.listener.take().ok_or(GameError::ListenerDropped)?;
+        self.set_state(GameState::Reviewing);
+        self.dispatch_state_changes(&listener);
+        while self.state == GameState::Reviewing {
+            if !self.dispatch_next_event(&listener) {
+                break
+            }
+        }
+        self.listener.restore(listener);
+        Ok(())
+    }
+
+    /// Stops the game and marks that user wants to quit.
+    ///
+    /// Use [`should_exit`] to check this mark after the game is stopped. This method can be
+    /// called in any state. If game is started or paused, it is stopped.
+    ///
+    /// [`should_exit`]: #method.should_exit
+    pub fn exit(&mut self) {
+        self.should_exit = true;
+        if self.is_running() || self.state == GameState::Reviewing {
+            self.resume_keys.clear();
+            self.set_stopped();
+        }
+    }
+
+    /// Returns `true` if user confirmed quit (see [`set_quit_key`]) or [`exit`] was called.
+    ///
+    /// [`set_quit_key`]: #method.set_quit_key
+    /// [`exit`]: #method.exit
+    pub fn should_exit(&self) -> bool {
+        self.should_exit
+    }
+
+    /// Sets key which quits the game.
+    ///
+    /// When user presses this key, quit confirmation dialog is shown (see [`confirm_quit`]). If
+    /// user confirms it, [`exit`] is called. The key isn't passed to `handle_key`. It is handled
+    /// as usual while game is paused. Use `None` to remove quit key.
+    ///
+    /// [`confirm_quit`]: #method.confirm_quit
+    /// [`exit`]: #method.exit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal, Key};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_quit_key(Some(Key::Char('q')));
+    /// game.start();
+    /// if game.should_exit() {
+    ///     return
+    /// }
+    /// # }
+    /// ```
+    pub fn set_quit_key(&mut self, key: Option<Key>) {
+        self.quit_key = key;
+    }
+
+    /// Enables or disables quitting the game with Ctrl+C.
+    ///
+    /// Terminal is in raw mode, so Ctrl+C doesn't interrupt the program and is passed to
+    /// `handle_key` as `Key::Ctrl('c')` by default. If enabled, Ctrl+C calls [`exit`] without
+    /// confirmation in any state, even if a dialog is open. The key isn't passed to `handle_key`.
+    /// See also [`install_panic_hook`] to restore the terminal if the game panics.
+    ///
+    /// [`exit`]: #method.exit
+    /// [`install_panic_hook`]: ../terminal/fn.install_panic_hook.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// struct App {
+    ///     keys: usize,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {
+    ///         self.keys += 1;
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App { keys: 0 }));
+    /// let input = ScriptedInput::new(&[Key::Char('a'), Key::Ctrl('c'), Key::Char('b')]);
+    /// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+    /// game.set_quit_on_ctrl_c(true);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// game.start();
+    /// assert!(game.should_exit());
+    /// assert_eq!(app.borrow().keys, 1);
+    /// ```
+    pub fn set_quit_on_ctrl_c(&mut self, enabled: bool) {
+        self.quit_on_ctrl_c = enabled;
+    }
+
+    /// Sets key which moves focus to the next screen region (see [`toggle_focus`]).
+    ///
+    /// The key isn't passed to `handle_key`. Use `None` to remove focus key. There is no focus
+    /// key by default.
+    ///
+    /// [`toggle_focus`]: #method.toggle_focus
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Key};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_focus_key(Some(Key::Tab));
+    /// # }
+    /// ```
+    pub fn set_focus_key(&mut self, key: Option<Key>) {
+        self.focus_key = key;
+    }
+
+    /// Sets key which toggles the cell under cursor.
+    ///
+    /// Cell content is changed to the next state set by `Board::set_toggle_states` and
+    /// [`InputListener::on_cell_toggle`] is called. Nothing happens for cells without states.
+    /// The key isn't passed to `handle_key` if board has cursor. Cells can be toggled with right
+    /// mouse button click as well if mouse is enabled. Use `None` to remove toggle key. There is
+    /// no toggle key by default.
+    ///
+    /// [`InputListener::on_cell_toggle`]: trait.InputListener.html#method.on_cell_toggle
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Cell, Cursor, Game, InputListener, Terminal, Key, Position};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    /// use termion::color;
+    ///
+    /// struct App {
+    ///     flags: usize,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    ///
+    ///     fn on_cell_toggle(&mut self, _position: Position, index: usize,
+    ///                       _game: &mut Game<R, W, Self>) {
+    ///         if index == 1 {
+    ///             self.flags += 1;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App { flags: 0 }));
+    /// let input = ScriptedInput::new(&[Key::Char('f'), Key::Right, Key::Char('f')]);
+    /// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+    /// game.set_toggle_key(Some(Key::Char('f')));
+    /// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+    /// let mut board = Board::new(3, 1, 1, 1, false, None);
+    /// board.init_from_str("   ", Some(cursor));
+    /// for x in 0..3 {
+    ///     board.set_toggle_states(Position(x, 0), &[Cell::Char(' '), Cell::Char('F')]);
+    /// }
+    /// game.init(board, None);
+    /// game.start();
+    /// assert_eq!(app.borrow().flags, 2);
+    /// ```
+    pub fn set_toggle_key(&mut self, key: Option<Key>) {
+        self.toggle_key = key;
+    }
+
+    /// Toggles the cell. See `Board::toggle_cell`.
+    ///
+    /// [`InputListener::on_cell_toggle`] isn't called by this method. Returns index of the new
+    /// cell state or `None` if cell has no states or there is no board.
+    ///
+    /// [`InputListener::on_cell_toggle`]: trait.InputListener.html#method.on_cell_toggle
+    ///
+    /// # Panics
+    ///
+    /// Panics if message dialog is open.
+    pub fn toggle_cell(&mut self, position: Position) -> Option<usize> {
+        self.board.as_mut().and_then(|b| b.toggle_cell(position))
+    }
+
+    /// Attaches user data to the cell. See `Board::set_user_data`.
+    ///
+    /// Returns `false` if there is no board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// struct Mine {
+    ///     neighbours: u8,
+    /// }
+    ///
+    /// game.set_user_data(Position(2, 3), Mine { neighbours: 0 });
+    /// if let Some(mine) = game.get_user_data_mut::<Mine>(Position(2, 3)) {
+    ///     mine.neighbours += 1;
+    /// }
+    /// # }
+    /// ```
+    pub fn set_user_data<T: Any>(&mut self, position: Position, data: T) -> bool {
+        match self.board {
+            Some(ref mut board) => {
+                board.set_user_data(position, data);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Returns user data of the cell. See `Board::get_user_data`.
+    ///
+    /// Returns `None` if there is no board, cell has no data or data has another type.
+    pub fn get_user_data<T: Any>(&self, position: Position) -> Option<&T> {
+        self.board.as_ref().and_then(|b| b.get_user_data(position))
+    }
+
+    /// Returns mutable user data of the cell. See [`get_user_data`].
+    ///
+    /// [`get_user_data`]: #method.get_user_data
+    pub fn get_user_data_mut<T: Any>(&mut self, position: Position) -> Option<&mut T> {
+        self.board.as_mut().and_then(|b| b.get_user_data_mut(position))
+    }
+
+    /// Removes user data of the cell and returns it. See `Board::remove_user_data`.
+    pub fn remove_user_data(&mut self, position: Position) -> Option<Box<dyn Any>> {
+        self.board.as_mut().and_then(|b| b.remove_user_data(position))
+    }
+
+    /// Moves focus to the screen region.
+    ///
+    /// When board is focused, keys move the board cursor as usual. When information area is
+    /// focused, *Up*, *Down*, *Page Up*, *Page Down*, *Home* and *End* keys scroll its text
+    /// (see [`scroll_info`]), other keys are passed to the input listener and board cursor
+    /// doesn't move. Message dialog receives keys regardless of focus.
+    /// [`InputListener::on_focus_change`] is called if focus is changed. Focus is moved to the
+    /// board when game is initialized.
+    ///
+    /// [`scroll_info`]: #method.scroll_info
+    /// [`InputListener::on_focus_change`]: trait.InputListener.html#method.on_focus_change
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no information area with this index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Focus, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// struct App {
+    ///     focus: Focus,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    ///
+    ///     fn on_focus_change(&mut self, _old: Focus, new: Focus, _game: &mut Game<R, W, Self>) {
+    ///         self.focus = new;
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(17, 5);
+    /// let app = Rc::new(RefCell::new(App { focus: Focus::Board }));
+    /// let input = ScriptedInput::new(&[Key::Tab, Key::Down, Key::Down, Key::Down]);
+    /// let mut game = Game::with_terminal(input, output.clone(), Rc::clone(&app));
+    /// game.set_focus_key(Some(Key::Tab));
+    /// // Info area shows 3 lines of 5.
+    /// let info = Info::new(10, InfoLayout::Right, &["1", "2", "3", "4", "5"]);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), Some(info));
+    /// game.start();
+    ///
+    /// assert_eq!(app.borrow().focus, Focus::Info(0));
+    /// let screen = output.screen_snapshot();
+    /// assert!(screen[1].ends_with("║3         ║"));
+    /// assert!(screen[3].ends_with("║5         ║"));
+    /// ```
+    pub fn set_focus(&mut self, focus: Focus) {
+        if let Focus::Info(index) = focus {
+            if index >= self.infos.len() {
+                panic!("There is no information area with index {}.", index);
+            }
+        }
+        if focus != self.focus {
+            self.focus_changes.push((self.focus, focus));
+            self.focus = focus;
+        }
+    }
+
+    /// Moves focus to the next screen region: from the board to the first information area,
+    /// then to the next information area and back to the board.
+    pub fn toggle_focus(&mut self) {
+        let focus = match self.focus {
+            Focus::Board if !self.infos.is_empty() => Focus::Info(0),
+            Focus::Info(index) if index + 1 < self.infos.len() => Focus::Info(index + 1),
+            _ => Focus::Board,
+        };
+        self.set_focus(focus);
+    }
+
+    /// Returns focused screen region.
+    pub fn get_focus(&self) -> Focus {
+        self.focus
+    }
+
+    /// Scrolls text of the information area with `index` by `dy` lines.
+    ///
+    /// Negative `dy` scrolls up. Text can be scrolled until the last line is visible. Returns
+    /// `false` if there is no information area with this index or text can't be scrolled in
+    /// this direction.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Scroll game log to the end.
+    /// game.scroll_info(0, isize::MAX);
+    /// # }
+    /// ```
+    pub fn scroll_info(&mut self, index: usize, dy: isize) -> bool {
+        match self.infos.get_mut(index) {
+            Some(info) => info.scroll(dy),
+            None => false
+        }
+    }
+
+    /// Sets key bindings.
+    ///
+    /// Keys bound to actions are passed to `InputListener::on_action` instead of `handle_key`.
+    /// Keys which are not bound are passed to `handle_key`. Cursor movement keys, dialog keys,
+    /// quit key and resume keys are handled as usual, they are not affected by bindings. Game
+    /// has no bindings by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key, KeyBindings};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    /// enum Action {
+    ///     Place,
+    /// }
+    ///
+    /// struct App {
+    ///     placed: usize,
+    ///     other_keys: usize,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = Action;
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {
+    ///         self.other_keys += 1;
+    ///     }
+    ///
+    ///     fn on_action(&mut self, action: Action, _game: &mut Game<R, W, Self>) {
+    ///         if action == Action::Place {
+    ///             self.placed += 1;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut bindings = KeyBindings::new();
+    /// bindings.bind(Key::Char(' '), Action::Place);
+    /// bindings.bind(Key::Char('j'), Action::Place);
+    ///
+    /// let app = Rc::new(RefCell::new(App { placed: 0, other_keys: 0 }));
+    /// let input: &[u8] = b" jx";
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(input, output, Rc::clone(&app));
+    /// game.set_bindings(bindings);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// game.start();
+    /// assert_eq!(app.borrow().placed, 2);
+    /// assert_eq!(app.borrow().other_keys, 1);
+    /// ```
+    pub fn set_bindings(&mut self, bindings: KeyBindings<L::Action>) {
+        self.bindings = bindings;
+    }
+
+    // Stop the game and clean up things which can't work without the game loop.
+    fn set_stopped(&mut self) {
+        self.set_state(GameState::Stopped);
+        // Pulse and animations can't be advanced without ticks, don't leave cells highlighted or
+        // moving.
+        if let Some(ref mut board) = self.board {
+            board.stop_pulse();
+            board.finish_slides();
+        }
+    }
+
+    /// Pauses listening user input (except resume key).
+    ///
+    /// Game state will be set to `GameState::Paused`. This method is added for convenience.
+    /// The same functionality can easily be done on the client side.
+    /// All key presses are ignored while in `GameState::Paused` state. Pressing
+    /// resume key will call `handle_key`. User must call [`resume`] to get back to
+    /// `GameState::Started` state.
+    ///
+    /// [`resume`]: #method.resume
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Started` state only.
+    /// Panics if called in any other state. Use [`try_pause`] to get an error instead.
+    ///
+    /// [`try_pause`]: #method.try_pause
+    ///
+    pub fn pause(&mut self, resume_key: Key) {
+        self.pause_with_keys(vec![resume_key]);
+    }
+
+    /// Pauses listening user input (except resume keys).
+    ///
+    /// This method is the same as [`pause`], but any of `resume_keys` calls `handle_key`.
+    ///
+    /// [`pause`]: #method.pause
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Started` state only.
+    /// Panics if called in any other state. Use [`try_pause_with_keys`] to get an error instead.
+    ///
+    /// [`try_pause_with_keys`]: #method.try_pause_with_keys
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// struct App {
+    ///     resumed_by: Vec<Key>,
+    /// }
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         if game.is_paused() {
+    ///             self.resumed_by.push(key);
+    ///             game.resume();
+    ///         } else if key == Key::Char('p') {
+    ///             game.pause_with_keys([Key::Char(' '), Key::Enter]);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let app = Rc::new(RefCell::new(App { resumed_by: Vec::new() }));
+    /// // 'x' is ignored while paused.
+    /// let input: &[u8] = b"px pxx\n";
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(input, output, Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// game.start();
+    /// assert!(app.borrow().resumed_by == [Key::Char(' '), Key::Enter]);
+    /// assert!(!game.is_paused());
+    /// ```
+    pub fn pause_with_keys<K: Into<Vec<Key>>>(&mut self, resume_keys: K) {
+        if let Err(e) = self.try_pause_with_keys(resume_keys) {
+            panic!("{}", e);
+        }
+    }
+
+    /// Pauses listening user input (except resume key).
+    ///
+    /// This method is the same as [`pause`], but returns error instead of panicking.
+    ///
+    /// [`pause`]: #method.pause
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Started` state.
+    ///
+    pub fn try_pause(&mut self, resume_key: Key) -> Result<(), GameError> {
+        self.try_pause_with_keys(vec![resume_key])
+    }
+
+    /// Pauses listening user input (except resume keys).
+    ///
+    /// This method is the same as [`pause_with_keys`], but returns error instead of panicking.
+    ///
+    /// [`pause_with_keys`]: #method.pause_with_keys
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Started` state.
+    ///
+    pub fn try_pause_with_keys<K>(&mut self, resume_keys: K) -> Result<(), GameError>
+        where K: Into<Vec<Key>>
+    {
+        self.check_state(&[GameState::Started])?;
+        self.resume_keys = resume_keys.into();
+        self.set_state(GameState::Paused);
+        Ok(())
+    }
+
+    /// Returns `true` if game is in `GameState::Paused` state.
+    pub fn is_paused(&self) -> bool {
+        self.state == GameState::Paused
+    }
+
+    /// Starts listening all user input.
+    ///
+    /// Game state will be set to `GameState::Started`.
+    ///
+    /// # Panics
+    ///
+    /// This method can be called in `GameState::Paused` state only.
+    /// Panics if called in any other state. Use [`try_resume`] to get an error instead.
+    ///
+    /// [`try_resume`]: #method.try_resume
+    ///
+    pub fn resume(&mut self) {
+        if let Err(e) = self.try_resume() {
+            panic!("{}", e);
+        }
+    }
+
+    /// Starts listening all user input.
+    ///
+    /// This method is the same as [`resume`], but returns error instead of panicking.
+    ///
+    /// [`resume`]: #method.resume
+    ///
+    /// # Errors
+    ///
+    /// Returns `GameError::WrongState` if game isn't in `GameState::Paused` state.
+    ///
+    pub fn try_resume(&mut self) -> Result<(), GameError> {
+        self.check_state(&[GameState::Paused])?;
+        self.resume_keys.clear();
+        self.set_state(GameState::Started);
+        Ok(())
+    }
+
+    /// Returns game state.
+    pub fn get_state(&self) -> GameState {
+        self.state
+    }
+
+    // Check that game is in one of expected states.
+    fn check_state(&self, expected: &'static [GameState]) -> Result<(), GameError> {
+        if expected.contains(&self.state) {
+            Ok(())
+        } else {
+            Err(GameError::WrongState { expected, actual: self.state })
+        }
+    }
+
+    // Change state. Listener is notified later, because it can be borrowed now.
+    fn set_state(&mut self, state: GameState) {
+        self.state_changes.push((self.state, state));
+        self.state = state;
+    }
+
+    /// Returns game board.
+    ///
+    /// Returns `None` if game is not initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// if let Some(board) = game.get_board() {
+    ///     let cell_number = board.rows() * board.columns();
+    /// }
+    /// # }
+    /// ```
+    pub fn get_board(&self) -> Option<&Board> {
+        self.board.as_ref()
+    }
+
+    /// Returns board area on the screen including borders.
+    ///
+    /// Area is known after the game is initialized and is updated when layout changes (terminal
+    /// resize, alignment change etc.). It can be used to draw own content around the board (see
+    /// [`write_raw`]). Returns `None` if game is not initialized yet.
+    ///
+    /// [`write_raw`]: #method.write_raw
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key, Rect};
+    /// use gameboard::test::RecordingOutput;
+    /// # struct App {}
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(io::empty(), output.clone(), Rc::clone(&app));
+    /// assert_eq!(game.get_board_rect(), None);
+    ///
+    /// let status = Info::new(10, InfoLayout::Right, &["Score: 0"]);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), Some(status));
+    /// assert_eq!(game.get_board_rect(), Some(Rect { x: 1, y: 1, width: 5, height: 5 }));
+    /// assert_eq!(game.get_info_rect(0), Some(Rect { x: 6, y: 1, width: 12, height: 5 }));
+    /// assert_eq!(game.get_info_rect(1), None);
+    /// let row: String = output.screen_snapshot()[1].chars().skip(5).take(12).collect();
+    /// assert_eq!(row, "║Score: 0  ║");
+    /// ```
+    pub fn get_board_rect(&self) -> Option<Rect> {
+        self.board.as_ref().map(|b| b.get_rect())
+    }
+
+    /// Returns information area on the screen including borders.
+    ///
+    /// The `index` is an index of the information area in the list passed to `Game::init`.
+    /// Returns `None` if there is no such area. See [`get_board_rect`] for details.
+    ///
+    /// [`get_board_rect`]: #method.get_board_rect
+    pub fn get_info_rect(&self, index: usize) -> Option<Rect> {
+        self.infos.get(index).map(|i| i.get_rect())
+    }
+
+    /// Returns screen position (column, row) of the board cell top left corner.
+    ///
+    /// It can be used with [`write_raw`] to draw custom overlays next to the cell. Returns
+    /// `None` if game is not initialized yet, position is out of the board bounds or the cell
+    /// is scrolled out of the viewport. See `Board::cell_screen_pos` for details.
+    ///
+    /// [`write_raw`]: #method.write_raw
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// use termion::cursor::Goto;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Mark the cell corner.
+    /// if let Some((x, y)) = game.cell_screen_pos(Position(1, 1)) {
+    ///     game.write_raw(&format!("{}*", Goto(x, y)));
+    /// }
+    /// # }
+    /// ```
+    pub fn cell_screen_pos(&self, pos: Position) -> Option<(u16, u16)> {
+        self.board.as_ref().and_then(|b| b.cell_screen_pos(pos))
+    }
+
+    /// Returns iterator over all board cells with their positions.
+    ///
+    /// See `Board::iter_cells` for details. Iterator is empty if game is not initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Cell, Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// let empty_cells = game.iter_cells().filter(|(_, cell)| **cell == Cell::Empty).count();
+    /// # }
+    /// ```
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Position, &Cell)> {
+        self.board.iter().flat_map(|board| board.iter_cells())
+    }
+
+    /// Returns iterator over neighbor cell positions.
+    ///
+    /// See `Board::neighbors` for details. Iterator is empty if game is not initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// let mines = game.neighbors(Position(3, 3), true).filter(|&p| is_mine(p)).count();
+    /// # }
+    /// # fn is_mine(_p: Position) -> bool { false }
+    /// ```
+    pub fn neighbors(&self, pos: Position, diagonal: bool)
+            -> impl Iterator<Item = Position> + '_ {
+        self.board.iter().flat_map(move |board| board.neighbors(pos, diagonal))
+    }
+
+    /// Updates cells content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal, Cell, CellUpdates, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// let mut updates = CellUpdates::with_capacity(2);
+    /// updates.push((Cell::Empty, Position(0, 1)));
+    /// updates.push((Cell::Char('x'), Position(0, 2)));
+    /// game.update_cells(updates);
+    /// # }
+    /// ```
+    pub fn update_cells(&mut self, updates: CellUpdates) {
+        if let Some(ref mut board) = self.board {
+            board.update_cells(updates);
+        }
+    }
+
+    /// Changes number of board rows and columns.
+    ///
+    /// See `Board::resize` for details. Layout is recomputed and the whole screen is redrawn.
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Switch to expert level.
+    /// game.resize_board(16, 30);
+    /// # }
+    /// ```
+    pub fn resize_board(&mut self, rows: usize, columns: usize) {
+        if self.state == GameState::Created {
+            panic!("You can't resize board before initialization.");
+        }
+        if let Some(ref mut board) = self.board {
+            board.resize(rows, columns);
+        }
+        self.layout();
+        self.redraw();
+    }
+
+    /// Enables or disables borders between board cells.
+    ///
+    /// Board size changes, so layout is recomputed and the screen is redrawn. See
+    /// `Board::set_cell_borders` for details.
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::RecordingOutput;
+    /// # struct App {}
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    ///
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let output = RecordingOutput::new(80, 24);
+    /// let mut game = Game::with_terminal(io::empty(), output, Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// assert_eq!(game.get_board_rect().unwrap().width, 5);
+    /// game.set_cell_borders(true);
+    /// assert_eq!(game.get_board_rect().unwrap().width, 7);
+    /// ```
+    pub fn set_cell_borders(&mut self, enabled: bool) {
+        if self.state == GameState::Created {
+            panic!("You can't change cell borders before initialization.");
+        }
+        if let Some(ref mut board) = self.board {
+            board.set_cell_borders(enabled);
+        }
+        self.layout();
+        self.redraw();
+    }
+
+    /// Sets size of the visible part of the board in cells.
+    ///
+    /// See `Board::set_viewport` for details. Layout is recomputed and the whole screen is
+    /// redrawn.
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Show 40x20 cells of the large board.
+    /// game.set_viewport(Some((40, 20)));
+    /// # }
+    /// ```
+    pub fn set_viewport(&mut self, size: Option<(usize, usize)>) {
+        if self.state == GameState::Created {
+            panic!("You can't set viewport before initialization.");
+        }
+        if let Some(ref mut board) = self.board {
+            board.set_viewport(size);
+        }
+        self.layout();
+        self.redraw();
+    }
+
+    /// Scrolls board viewport by `dx` columns and `dy` rows.
+    ///
+    /// See `Board::scroll_viewport` for details.
+    pub fn scroll_viewport(&mut self, dx: isize, dy: isize) {
+        if let Some(ref mut board) = self.board {
+            board.scroll_viewport(dx, dy);
+        }
+    }
+
+    /// Restores board cells from the state.
+    ///
+    /// See `Board::restore` for details. Use `get_board` and `Board::snapshot` to get the state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if state positions are out of the board bounds or message dialog is open.
+    pub fn restore_board(&mut self, state: &BoardState) {
+        if let Some(ref mut board) = self.board {
+            board.restore(state);
+        }
+    }
+
+    /// Updates content of a single cell.
+    ///
+    /// This is a shortcut for [`update_cells`] with one cell.
+    ///
+    /// [`update_cells`]: #method.update_cells
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Cell, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_cell(Position(1, 1), Cell::Char('x'));
+    /// # }
+    /// ```
+    pub fn set_cell(&mut self, position: Position, cell: Cell) {
+        if let Some(ref mut board) = self.board {
+            board.set_cell(position, cell);
+        }
+    }
+
+    /// Exchanges content of two cells.
+    ///
+    /// See `Board::swap_cells` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Move the tile to the empty cell in sliding puzzle.
+    /// game.swap_cells(Position(1, 2), Position(2, 2));
+    /// # }
+    /// ```
+    pub fn swap_cells(&mut self, a: Position, b: Position) {
+        if let Some(ref mut board) = self.board {
+            board.swap_cells(a, b);
+        }
+    }
+
+    /// Fills rectangular region of cells with the same content.
+    ///
+    /// See `Board::fill_region` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if region is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Cell, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.fill_region(Position(0, 0), 3, 3, Cell::Empty);
+    /// # }
+    /// ```
+    pub fn fill_region(&mut self, top_left: Position, width: usize, height: usize, cell: Cell) {
+        if let Some(ref mut board) = self.board {
+            board.fill_region(top_left, width, height, cell);
+        }
+    }
+
+    /// Fills the whole board row with the same content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if row is out of the board bounds or message dialog is open.
+    pub fn fill_row(&mut self, row: usize, cell: Cell) {
+        if let Some(ref mut board) = self.board {
+            board.fill_row(row, cell);
+        }
+    }
+
+    /// Fills the whole board column with the same content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if column is out of the board bounds or message dialog is open.
+    pub fn fill_column(&mut self, column: usize, cell: Cell) {
+        if let Some(ref mut board) = self.board {
+            board.fill_column(column, cell);
+        }
+    }
+
+    /// Updates information area content.
+    ///
+    /// If game has several information areas, the first one is updated. Use
+    /// [`update_info_area`] to update other areas.
+    ///
+    /// Returns `false` if game has no information area. Nothing is updated in this case.
+    ///
+    /// [`update_info_area`]: #method.update_info_area
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.update_info(&[
+    ///     "This is line 1.",
+    ///     "",
+    ///     "This is line 3.",
+    ///     "This is line 4.",
+    /// ]);
+    /// # }
+    /// ```
+    ///
+    /// Game without information area isn't changed.
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key, Rect};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// # struct App;
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// let output = RecordingOutput::new(20, 6);
+    /// let app = Rc::new(RefCell::new(App));
+    /// let mut game = Game::with_terminal(ScriptedInput::new(&[]), output.clone(),
+    ///                                    Rc::clone(&app));
+    /// game.init(Board::new(3, 3, 1, 1, false, None), None);
+    /// assert!(!game.update_info(&["Score: 10"]));
+    /// game.start();
+    ///
+    /// assert_eq!(game.get_board_rect(), Some(Rect { x: 1, y: 1, width: 5, height: 5 }));
+    /// let screen = output.screen_snapshot();
+    /// assert_eq!(screen[0], "╔═══╗               ");
+    /// // Nothing is drawn outside of the board.
+    /// assert!(screen.iter().all(|row| row.chars().skip(5).all(|c| c == ' ')));
+    /// assert_eq!(screen[5].trim(), "");
+    /// ```
+    pub fn update_info(&mut self, lines: &[&str]) -> bool {
+        self.update_info_area(0, lines)
+    }
+
+    /// Updates content of the information area with `index`.
+    ///
+    /// Areas are indexed in the order they were passed to [`init`]. The whole area is redrawn on
+    /// the next screen update. Areas which are not updated are not redrawn.
+    ///
+    /// Returns `false` if there is no information area with this index. Nothing is updated in
+    /// this case.
+    ///
+    /// [`init`]: #method.init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         if key == Key::Char('u') {
+    ///             game.update_info_area(0, &["Updated"]);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let mut game = Game::with_terminal(ScriptedInput::new(&[Key::Char('x')]), output.clone(),
+    ///                                    Rc::clone(&app));
+    /// let info = Info::new(10, InfoLayout::Right, &["Help"]);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), Some(info));
+    ///
+    /// // Nothing is changed, nothing is written.
+    /// output.clear();
+    /// game.start();
+    /// assert!(output.output().is_empty());
+    /// ```
+    pub fn update_info_area(&mut self, index: usize, lines: &[&str]) -> bool {
+        match self.infos.get_mut(index) {
+            Some(info) => {
+                info.update(lines);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Changes text of one line of the information area with `index`.
+    ///
+    /// Only this line is redrawn, see `Info::update_line`. Areas are indexed in the order they
+    /// were passed to [`init`].
+    ///
+    /// Returns `false` if there is no information area with this index. Nothing is updated in
+    /// this case.
+    ///
+    /// [`init`]: #method.init
+    ///
+    /// # Panics
+    ///
+    /// Panics if information area has no line with `line` index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    /// use termion::{cursor::Goto, style};
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, game: &mut Game<R, W, Self>) {
+    ///         game.update_info_line(0, 1, "Score: 10");
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let input = ScriptedInput::new(&[Key::Char(' ')]);
+    /// let mut game = Game::with_terminal(input, output.clone(), Rc::clone(&app));
+    /// let info = Info::new(10, InfoLayout::Right, &["Level 1", "Score: 0", "Press q"]);
+    /// game.init(Board::new(3, 3, 1, 1, false, None), Some(info));
+    ///
+    /// output.clear();
+    /// game.start();
+    /// // Only the score line is written.
+    /// assert_eq!(String::from_utf8(output.output()).unwrap(),
+    ///            format!("{}Score: 10 {}", Goto(7, 3), style::Reset));
+    /// ```
+    pub fn update_info_line(&mut self, index: usize, line: usize, text: &str) -> bool {
+        match self.infos.get_mut(index) {
+            Some(info) => {
+                info.update_line(line, text);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Shows progress bar in the text row of the information area with `index`.
+    ///
+    /// See `Info::set_bar` for details. Only the bar row is redrawn. Areas are indexed in the
+    /// order they were passed to [`init`].
+    ///
+    /// Returns `false` if there is no information area with this index. Nothing is updated in
+    /// this case.
+    ///
+    /// [`init`]: #method.init
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Color, Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// # let (time_left, time_limit) = (10, 60);
+    /// game.set_info_bar(0, 2, time_left as f32 / time_limit as f32, Color::Rgb(200, 0, 0));
+    /// # }
+    /// ```
+    pub fn set_info_bar<C: Into<Color>>(&mut self, index: usize, row: usize, fraction: f32,
+                                        color: C) -> bool {
+        match self.infos.get_mut(index) {
+            Some(info) => {
+                info.set_bar(row, fraction, color);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Updates cells of the information area with `index`.
+    ///
+    /// Cells are added to the information area with `Info::set_cell_grid`. Areas are indexed in
+    /// the order they were passed to [`init`].
+    ///
+    /// Returns `false` if there is no information area with this index or it has no cells.
+    /// Nothing is updated in this case.
+    ///
+    /// [`init`]: #method.init
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the information area cells bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Cell, Game, InputListener, Terminal, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Show the next piece.
+    /// game.update_info_cells(0, vec![(Cell::Char('#'), Position(0, 0)),
+    ///                               (Cell::Empty, Position(1, 1))]);
+    /// # }
+    /// ```
+    pub fn update_info_cells(&mut self, index: usize, updates: CellUpdates) -> bool {
+        match self.infos.get_mut(index) {
+            Some(info) => info.update_cells(&updates),
+            None => false
+        }
+    }
+
+    /// Shows message dialog.
+    ///
+    /// This dialog can be used to ask user a questions. This dialog is modal. You can't update
+    /// board cells and cursor can't be moved while it is open. It can be closed by calling
+    /// [`hide_message`].
+    ///
+    /// The dialog is displayed over the board. It will be centered automatically. It can't be
+    /// larger than board: last lines will be ignored, too long lines will be truncated.
+    ///
+    /// *lines* is a list of strings to display in the dialog. If you want space between lines,
+    /// add empty string to list.
+    ///
+    /// Text alignment:
+    ///
+    /// * Lines are left-aligned by default
+    /// * Lines started with *|^|* are centered
+    /// * Lines started with *|>|* are right-aligned
+    ///
+    /// # Implementation note
+    ///
+    /// This crate iterates Unicode strings as a set of [grapheme clusters] to handle characters
+    /// like *g̈* correctly. Wide characters (CJK, emoji) take 2 terminal columns. If wide
+    /// character doesn't fit into the line, it is cut and its place is filled with space. Width
+    /// of some Unicode symbols depends on terminal. Such symbols can break layout.
+    ///
+    /// [`hide_message`]: #method.hide_message
+    /// [grapheme clusters]: http://www.unicode.org/reports/tr29/
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.show_message(&[
+    ///     "|^|Congratulations! You win!",
+    ///     "",
+    ///     "Press 'r' to replay.",
+    ///     "Press 'q' to quit.",
+    /// ]);
+    /// # }
+    /// ```
+    pub fn show_message(&mut self, lines: &[&str]) {
+        if let Some(ref mut board) = self.board {
+            board.show_message(lines, &[], DialogPosition::Center);
+        }
+    }
+
+    /// Shows message dialog at the specified position.
+    ///
+    /// This dialog is the same as the one opened by [`show_message`], but it is placed at
+    /// `position` instead of the board center. The dialog is always kept inside the board.
+    ///
+    /// [`show_message`]: #method.show_message
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal, DialogPosition};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.show_message_at(&["|^|Check!"], DialogPosition::Top);
+    /// # }
+    /// ```
+    pub fn show_message_at(&mut self, lines: &[&str], position: DialogPosition) {
+        if let Some(ref mut board) = self.board {
+            board.show_message(lines, &[], position);
+        }
+    }
+
+    /// Shows message dialog with buttons.
+    ///
+    /// This dialog is the same as the one opened by [`show_message`], but it has a row of
+    /// buttons at the bottom. User can select button with *Left*/*Right* keys and press it with
+    /// *Enter*. Dialog is closed and [`on_dialog_result`] is called with pressed button index.
+    /// Other keys are passed to `handle_key` as usual.
+    ///
+    /// [`show_message`]: #method.show_message
+    /// [`on_dialog_result`]: trait.InputListener.html#method.on_dialog_result
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.show_dialog(&["|^|You win!", "", "|^|Play again?"], &["Yes", "No"]);
+    /// # }
+    /// ```
+    pub fn show_dialog(&mut self, lines: &[&str], buttons: &[&str]) {
+        if let Some(ref mut board) = self.board {
+            board.show_message(lines, buttons, DialogPosition::Center);
+        }
+    }
+
+    /// Shows dialog with single line input field and returns entered text.
+    ///
+    /// The dialog contains `label` and input field under it. User can type text, move input
+    /// cursor with *Left*/*Right* keys and delete characters with *Backspace*. Dialog is closed
+    /// when user presses *Enter*. Other keys are ignored.
+    ///
+    /// This method blocks until user presses *Enter*. It reads game input stream itself, so input
+    /// listener isn't called while dialog is open. If game was started with [`start_with_tick`],
+    /// input stream is non-blocking and it is polled.
+    ///
+    /// Returns `None` if input stream ended (for example, stdin was closed) before *Enter* was
+    /// pressed. Dialog is closed in this case. Returns empty string if game has no board.
+    ///
+    /// [`start_with_tick`]: #method.start_with_tick
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// if let Some(name) = game.prompt("Enter your name:") {
+    ///     game.update_info(&[&format!("Player: {}", name)]);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Input which ends before *Enter* is pressed.
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// # struct App;
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// let input = ScriptedInput::new(&[Key::Char('a'), Key::Char('b')]);
+    /// let app = Rc::new(RefCell::new(App));
+    /// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+    /// game.init(Board::new(30, 8, 1, 1, false, None), None);
+    /// assert_eq!(game.prompt("Name:"), None);
+    ///
+    /// let input = ScriptedInput::new(&[Key::Char('a'), Key::Char('b'), Key::Char('\n')]);
+    /// let mut game = Game::with_terminal(input, RecordingOutput::new(80, 24), Rc::clone(&app));
+    /// game.init(Board::new(30, 8, 1, 1, false, None), None);
+    /// assert_eq!(game.prompt("Name:"), Some(String::from("ab")));
+    /// ```
+    pub fn prompt(&mut self, label: &str) -> Option<String> {
+        if self.state == GameState::Created {
+            panic!("You can't show prompt before initialization.");
+        }
+        if let Some(ref mut board) = self.board {
+            board.show_prompt(label, DialogPosition::Center);
+        }
+        self.write_updates();
+        loop {
+            let key = self.read_key();
+            let text = match (self.board.as_mut(), key) {
+                (Some(board), Some(key)) => board.handle_prompt_key(key),
+                (Some(board), None) => {
+                    // Input stream ended, there will be no Enter.
+                    board.hide_message();
+                    self.write_updates();
+                    return None
+                },
+                (None, _) => return Some(String::new())
+            };
+            self.write_updates();
+            if text.is_some() {
+                return text
+            }
+        }
+    }
+
+    /// Shows quit confirmation dialog and returns `true` if user confirmed quit.
+    ///
+    /// The dialog contains `message` and *Yes*/*No* buttons. *Esc* key closes dialog as *No*.
+    /// Game is not stopped, use [`exit`] or [`stop`] if user confirmed quit.
+    ///
+    /// This method blocks until dialog is closed. It reads game input stream itself, the same
+    /// as [`prompt`]. Message dialog which is already open is hidden while quit confirmation is
+    /// shown.
+    ///
+    /// [`exit`]: #method.exit
+    /// [`stop`]: #method.stop
+    /// [`prompt`]: #method.prompt
+    ///
+    /// # Panics
+    ///
+    /// This method can't be called in `GameState::Created` state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// if game.confirm_quit("|^|Your progress will be lost. Quit?") {
+    ///     game.exit();
+    /// }
+    /// # }
+    /// ```
+    pub fn confirm_quit(&mut self, message: &str) -> bool {
+        if self.state == GameState::Created {
+            panic!("You can't show quit confirmation before initialization.");
+        }
+        let previous = match self.board {
+            Some(ref mut board) => board.take_message(),
+            None => return false
+        };
+        if let Some(ref mut board) = self.board {
+            board.show_message(&[message], &["Yes", "No"], DialogPosition::Center);
+        }
+        self.write_updates();
+        let confirmed = loop {
+            let key = self.read_key();
+            let res = match (self.board.as_mut(), key) {
+                // Input stream ended, quit isn't confirmed.
+                (Some(_), None) | (Some(_), Some(Key::Esc)) => KeyHandleResult::DialogResult(1),
+                (Some(board), Some(key)) => board.handle_key(key),
+                (None, _) => return false
+            };
+            if let KeyHandleResult::DialogResult(index) = res {
+                break index == 0
+            }
+            self.write_updates();
+        };
+        if let Some(ref mut board) = self.board {
+            board.restore_message(previous);
+        }
+        self.write_updates();
+        confirmed
+    }
+
+    // Wait for key press. Other input events are skipped. Return `None` if input stream ended.
+    // Non-blocking input stream has no data until key is pressed, so it is polled instead.
+    fn read_key(&mut self) -> Option<Key> {
+        loop {
+            match self.input.next() {
+                None if self.polled_input => thread::sleep(INPUT_POLL_INTERVAL),
+                None => return None,
+                Some(Ok(Event::Key(key))) => return Some(event::to_key(key)),
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// Sets cell background color.
+    ///
+    /// See `Board::set_cell_background` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// use gameboard::Color;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_cell_background(Position(0, 0), Some(Color::Rgb(128, 0, 0)));
+    /// # }
+    /// ```
+    pub fn set_cell_background(&mut self, position: Position, color: Option<Color>) {
+        if let Some(ref mut board) = self.board {
+            board.set_cell_background(position, color);
+        }
+    }
+
+    /// Highlights cells with background color.
+    ///
+    /// See `Board::highlight_line` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// use termion::color;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Show the winning diagonal.
+    /// game.highlight_line(&[Position(0, 0), Position(1, 1), Position(2, 2)],
+    ///                     color::Rgb(0, 128, 0));
+    /// # }
+    /// ```
+    pub fn highlight_line<C: Into<Color>>(&mut self, cells: &[Position], color: C) {
+        if let Some(ref mut board) = self.board {
+            board.highlight_line(cells, color);
+        }
+    }
+
+    /// Starts changing background color of cells between two colors.
+    ///
+    /// Pulse is advanced every tick. See `Board::pulse_cells` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Position};
+    /// use termion::color;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Flash the cell 3 times.
+    /// game.pulse_cells(&[Position(2, 2)], color::Rgb(255, 0, 0), color::Rgb(0, 0, 0), 1, 3);
+    /// # }
+    /// ```
+    pub fn pulse_cells<C: Into<Color>>(&mut self, cells: &[Position], color_a: C, color_b: C,
+                                       period_ticks: u32, count: u32) {
+        if let Some(ref mut board) = self.board {
+            board.pulse_cells(cells, color_a, color_b, period_ticks, count);
+        }
+    }
+
+    /// Moves cell content from one cell to another with animation.
+    ///
+    /// Animation is advanced every tick. See `Board::animate_move` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if position is out of the board bounds or message dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use std::time::Duration;
+    /// use gameboard::{Board, Cell, Game, InputListener, Terminal, Key, Position};
+    /// use gameboard::test::RecordingOutput;
+    /// use termion::cursor::Goto;
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    ///
+    ///     fn on_tick(&mut self, game: &mut Game<R, W, Self>) {
+    ///         match game.tick_count() {
+    ///             1 => game.animate_move(Position(0, 0), Position(2, 0), Cell::Char('o'), 4),
+    ///             6 => game.stop(),
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let mut game = Game::with_terminal(io::empty(), output.clone(), Rc::clone(&app));
+    /// let mut board = Board::new(3, 1, 2, 1, false, None);
+    /// board.init_from_vec(&[Cell::Char('o'), Cell::Empty, Cell::Empty], None);
+    /// game.init(board, None);
+    /// game.start_with_tick(Duration::from_millis(1));
+    ///
+    /// // Cells start at column 2 and are 2 columns wide, cell is moved 1 column every tick.
+    /// let output = String::from_utf8(output.output()).unwrap();
+    /// assert!(output.contains(&format!("{}oo", Goto(3, 2))));
+    /// assert!(output.contains(&format!("{}oo", Goto(5, 2))));
+    /// let cells: Vec<&Cell> = game.get_board().unwrap().iter_cells().map(|(_, c)| c).collect();
+    /// assert_eq!(cells, vec![&Cell::Empty, &Cell::Empty, &Cell::Char('o')]);
+    /// ```
+    pub fn animate_move(&mut self, from: Position, to: Position, cell: Cell, ticks: u32) {
+        if let Some(ref mut board) = self.board {
+            board.animate_move(from, to, cell, ticks);
+        }
+    }
+
+    /// Removes highlighting added by `highlight_line` from all cells.
+    ///
+    /// Pulse started by `pulse_cells` is stopped as well.
+    pub fn clear_highlight(&mut self) {
+        if let Some(ref mut board) = self.board {
+            board.clear_highlight();
+        }
+    }
+
+    /// Sets cursor background color.
+    ///
+    /// This can be used to show game state, for example, highlight illegal move. See
+    /// `Board::set_cursor_color` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// use termion::color;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_cursor_color(color::Rgb(200, 0, 0));
+    /// # }
+    /// ```
+    pub fn set_cursor_color<C: Into<Color>>(&mut self, color: C) {
+        if let Some(ref mut board) = self.board {
+            board.set_cursor_color(color);
+        }
+    }
+
+    /// Shows or hides cursor.
+    ///
+    /// This can be used to remove cursor highlighting on game over screen or during animation.
+    /// See `Board::set_cursor_visible` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_cursor_visible(false);
+    /// # }
+    /// ```
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        if let Some(ref mut board) = self.board {
+            board.set_cursor_visible(visible);
+        }
+    }
+
+    /// Returns cells selected by cursor in selection order.
+    ///
+    /// The first cell is the selection anchor, the last one is the cursor cell. Returns empty
+    /// vector if nothing is selected or there is no board or cursor. See
+    /// `Cursor::set_selection` for selection rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, CursorBuilder, Game, InputListener, Terminal, Key, Position};
+    /// use gameboard::test::RecordingOutput;
+    /// use gameboard::cursor::Direction;
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// }
+    ///
+    /// fn get_select_direction(key: Key) -> Option<Direction> {
+    ///     match key {
+    ///         Key::Char('A') => Some(Direction::Left),
+    ///         Key::Char('D') => Some(Direction::Right),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// fn play(keys: &'static str) -> Vec<Position> {
+    ///     let app = Rc::new(RefCell::new(App {}));
+    ///     let output = RecordingOutput::new(80, 24);
+    ///     let mut game = Game::with_terminal(keys.as_bytes(), output, Rc::clone(&app));
+    ///     let cursor = CursorBuilder::new().selection(get_select_direction).build();
+    ///     let mut board = Board::new(4, 1, 1, 1, false, None);
+    ///     board.init_from_str("word", Some(cursor));
+    ///     game.init(board, None);
+    ///     game.start();
+    ///     game.selection()
+    /// }
+    ///
+    /// assert!(play("DD") == vec![Position(0, 0), Position(1, 0), Position(2, 0)]);
+    /// // Moving back shortens the path.
+    /// assert!(play("DDA") == vec![Position(0, 0), Position(1, 0)]);
+    /// // Plain move clears the selection.
+    /// assert!(play("DDa").is_empty());
+    /// ```
+    pub fn selection(&self) -> Vec<Position> {
+        match self.board {
+            Some(ref board) => board.selection(),
+            None => Vec::new()
+        }
+    }
+
+    /// Clears cursor selection. Cursor position isn't changed.
+    pub fn clear_selection(&mut self) {
+        if let Some(ref mut board) = self.board {
+            board.clear_selection();
+        }
+    }
+
+    /// Enables mouse support.
+    ///
+    /// Terminal starts reporting mouse events. Clicking board cell with the left mouse button
+    /// moves [`Cursor`] to this cell and calls [`on_click`]. Clicking information area calls
+    /// [`on_info_click`]. Clicking board cell with the right mouse button toggles it (see
+    /// [`set_toggle_key`]). Cell under mouse pointer is highlighted and [`on_hover`] is called
+    /// when pointer moves to another cell. Mouse is disabled when game object is dropped.
+    ///
+    /// [`Cursor`]: ../cursor/struct.Cursor.html
+    /// [`on_click`]: trait.InputListener.html#method.on_click
+    /// [`on_info_click`]: trait.InputListener.html#method.on_info_click
+    /// [`set_toggle_key`]: #method.set_toggle_key
+    /// [`on_hover`]: trait.InputListener.html#method.on_hover
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.enable_mouse();
+    /// # }
+    /// ```
+    pub fn enable_mouse(&mut self) {
+        if !self.mouse_enabled {
+            write!(self.output, "{}", MOUSE_ENABLE_SEQUENCE).unwrap();
+            self.output.flush().unwrap();
+            self.mouse_enabled = true;
+        }
+    }
+
+    /// Disables mouse support.
+    pub fn disable_mouse(&mut self) {
+        if self.mouse_enabled {
+            write!(self.output, "{}", MOUSE_DISABLE_SEQUENCE).unwrap();
+            self.output.flush().unwrap();
+            self.mouse_enabled = false;
+        }
+    }
+
+    /// Sets colors of the message dialogs.
+    ///
+    /// Style is applied to dialogs opened after this call. Board must be initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::{self, Read};
+    /// # use gameboard::{Game, InputListener, Terminal, DialogStyle};
+    /// use gameboard::Color;
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.set_dialog_style(DialogStyle::new(None, Some(Color::Rgb(0, 0, 128)), None));
+    /// # }
+    /// ```
+    pub fn set_dialog_style(&mut self, style: DialogStyle) {
+        if let Some(ref mut board) = self.board {
+            board.set_dialog_style(style);
+        }
+    }
+
+    /// Applies colors of the theme to the board and information areas.
+    ///
+    /// See [`Theme`] for details. Theme is applied to the current board and information areas
+    /// and the screen is redrawn. If game is initialized again, theme must be applied again. Use
+    /// `Board::set_theme` to apply theme to the board before initialization.
+    ///
+    /// [`Theme`]: ../theme/struct.Theme.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Game, InputListener, Terminal, Theme};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// game.apply_theme(&Theme::light());
+    /// # }
+    /// ```
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if let Some(ref mut board) = self.board {
+            board.set_theme(theme);
+        }
+        for info in &mut self.infos {
+            info.set_border_color(theme.border);
+        }
+        if self.state != GameState::Created {
+            self.redraw();
+        }
+    }
+
+    /// Sets color mode of the terminal.
+    ///
+    /// Colors are converted to the nearest colors supported in this mode before output, including
+    /// colors in cell and information area content. Content written with [`write_raw`] isn't
+    /// converted. By default mode is detected when game is created (see `ColorMode::detect`), so
+    /// Rgb colors are shown correctly on terminals without truecolor support. Use this method to
+    /// override detection. Screen is redrawn if game is initialized.
+    ///
+    /// [`write_raw`]: #method.write_raw
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Color, Game, InputListener, Terminal, Key};
+    /// use gameboard::color::ColorMode;
+    /// use gameboard::test::RecordingOutput;
+    /// use termion::color;
+    /// # struct App {}
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let mut game = Game::with_terminal(io::empty(), output.clone(), Rc::clone(&app));
+    /// game.set_color_mode(ColorMode::Ansi256);
+    /// let mut board = Board::new(1, 1, 1, 1, false, None);
+    /// board.set_background(Some(Color::Rgb(0, 0, 200)));
+    /// game.init(board, None);
+    /// let output = String::from_utf8(output.output()).unwrap();
+    /// assert!(output.contains(&color::Bg(color::AnsiValue(20)).to_string()));
+    /// assert!(!output.contains(&color::Bg(color::Rgb(0, 0, 200)).to_string()));
+    /// ```
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+        if self.state != GameState::Created {
+            self.redraw();
+        }
+    }
+
+    /// Returns color mode of the terminal.
+    pub fn get_color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Starts batch of screen updates.
+    ///
+    /// Board and information area changes are not written to the terminal until [`end_batch`] is
+    /// called, even if several keys or ticks are handled in the meantime. Then all changes are
+    /// written at once. Cells changed several times are written only once with the final
+    /// content. This reduces flickering when many moves are applied rapidly, for example in
+    /// computer vs computer demo. Batches can't be nested, calling this method during a batch
+    /// does nothing.
+    ///
+    /// [`end_batch`]: #method.end_batch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Cell, Game, InputListener, Terminal, Key, Position};
+    /// use gameboard::test::RecordingOutput;
+    ///
+    /// struct App {}
+    ///
+    /// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    ///     type Action = ();
+    ///
+    ///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+    ///         if let Key::Char(c) = key {
+    ///             game.set_cell(Position(0, 0), Cell::Char(c));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let output = RecordingOutput::new(80, 24);
+    /// let app = Rc::new(RefCell::new(App {}));
+    /// let mut game = Game::with_terminal(&b"xyz"[..], output.clone(), Rc::clone(&app));
+    /// game.init(Board::new(1, 1, 1, 1, false, None), None);
+    ///
+    /// output.clear();
+    /// game.begin_batch();
+    /// game.start();
+    /// assert!(output.output().is_empty());
+    ///
+    /// game.end_batch();
+    /// let screen = output.screen_snapshot();
+    /// assert_eq!(screen[1].chars().nth(1), Some('z'));
+    /// let output = String::from_utf8(output.output()).unwrap();
+    /// assert!(output.contains('z') && !output.contains('x') && !output.contains('y'));
+    /// ```
+    pub fn begin_batch(&mut self) {
+        self.batch = true;
+    }
+
+    /// Ends batch of screen updates started by [`begin_batch`] and writes all collected changes
+    /// to the terminal. Does nothing if batch isn't started.
+    ///
+    /// [`begin_batch`]: #method.begin_batch
+    pub fn end_batch(&mut self) {
+        if self.batch {
+            self.batch = false;
+            self.write_updates();
+        }
+    }
+
+    /// Returns `true` if batch of screen updates is started.
+    pub fn is_batch(&self) -> bool {
+        self.batch
+    }
+
+    /// Hides message dialog.
+    pub fn hide_message(&mut self) {
+        if let Some(ref mut board) = self.board {
+            board.hide_message();
+        }
+    }
+
+    /// Returns `true` if message dialog is open.
+    ///
+    /// Message dialog is opened by [`show_message`], [`show_dialog`] and [`prompt`].
+    ///
+    /// [`show_message`]: #method.show_message
+    /// [`show_dialog`]: #method.show_dialog
+    /// [`prompt`]: #method.prompt
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{Cell, Game, InputListener, Terminal, Position};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// if !game.is_message_open() {
+    ///     game.set_cell(Position(0, 0), Cell::Char('x'));
+    /// }
+    /// # }
+    /// ```
+    pub fn is_message_open(&self) -> bool {
+        self.board.as_ref().map_or(false, |b| b.is_message_open())
+    }
+
+    /// Shows non-modal message over the board.
+    ///
+    /// Game continues while toast is shown. See `Board::show_toast` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io::Read;
+    /// # use gameboard::{DialogPosition, Game, InputListener, Terminal};
+    /// # fn f<R: Read, W: Terminal, L: InputListener<R, W>>(game: &mut Game<R, W, L>) {
+    /// // Show toast for 3 seconds if tick interval is 100 ms.
+    /// game.show_toast(&["|^|Checkmate in 2"], DialogPosition::Top, 30);
+    /// # }
+    /// ```
+    pub fn show_toast(&mut self, lines: &[&str], position: DialogPosition, duration_ticks: u32) {
+        if let Some(ref mut board) = self.board {
+            board.show_toast(lines, position, duration_ticks);
+        }
+    }
+
+    /// Hides toast shown by [`show_toast`].
+    ///
+    /// [`show_toast`]: #method.show_toast
+    pub fn hide_toast(&mut self) {
+        if let Some(ref mut board) = self.board {
+            board.hide_toast();
+        }
+    }
+}
+
+// Get size (width, height) of the block formed by board and information areas. Each area takes
+// the whole side of the block, so its size is added to the block width or height.
+fn get_layout_size(board: &Board, infos: &[Info]) -> (usize, usize) {
+    let (mut width, mut height) = (board.get_width(), board.get_height());
+    for info in infos {
+        match info.get_layout() {
+            InfoLayout::Left | InfoLayout::Right => width += info.get_size(),
+            InfoLayout::Top | InfoLayout::Bottom => height += info.get_size(),
+        }
+    }
+    (width, height)
+}
+
+// Get top left corner position of the layout with `width` and `height` size on the screen.
+fn get_layout_origin(alignment: ScreenAlign, screen_size: Option<(u16, u16)>, width: usize,
+                     height: usize) -> (usize, usize) {
+    let (screen_w, screen_h) = match screen_size {
+        Some((w, h)) => (w as usize, h as usize),
+        None => return (SCREEN_LEFT, SCREEN_TOP)
+    };
+    let right = SCREEN_LEFT + screen_w.saturating_sub(width);
+    let bottom = SCREEN_TOP + screen_h.saturating_sub(height);
+    match alignment {
+        ScreenAlign::TopLeft => (SCREEN_LEFT, SCREEN_TOP),
+        ScreenAlign::TopRight => (right, SCREEN_TOP),
+        ScreenAlign::BottomLeft => (SCREEN_LEFT, bottom),
+        ScreenAlign::BottomRight => (right, bottom),
+        ScreenAlign::Center => ((SCREEN_LEFT + right) / 2, (SCREEN_TOP + bottom) / 2),
+    }
 }
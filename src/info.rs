@@ -1,10 +1,16 @@
 //! Information area.
 
-use termion::{cursor};
+use std::mem;
+use std::rc::Rc;
 
+use crate::ansi::{color, cursor, style};
+use crate::board::CellUpdates;
+use crate::cell::{Cell, CellPadding};
+use crate::cell_grid::CellGrid;
 use crate::chars;
-use crate::game::Position;
-use crate::str_utils;
+use crate::color::Color;
+use crate::game::{Position, Rect};
+use crate::str_utils::{self, TextAlign};
 
 /// Information area layout.
 #[derive(Copy, Clone)]
@@ -20,6 +26,7 @@ pub enum InfoLayout {
 }
 
 /// Information area structure.
+#[derive(Clone)]
 pub struct Info {
     /// Info top left position.
     position: Position,
@@ -30,6 +37,41 @@ pub struct Info {
     size: usize,
     layout: InfoLayout,
     lines: Vec<String>,
+    wrap: bool,
+    rtl: bool,
+    border_color: Option<Color>,
+    grid: Option<InfoGrid>,
+    /// Index of the first visible text line.
+    scroll: usize,
+    /// Progress bars.
+    bars: Vec<Bar>,
+    /// Indexes of lines changed by `update_line` since the last screen update.
+    changed_lines: Vec<usize>,
+    /// Rows of bars changed since the last screen update.
+    changed_bars: Vec<usize>,
+    /// The whole area must be redrawn on the next screen update. Area isn't redrawn if it is
+    /// not set and no lines are changed.
+    full_update: bool,
+}
+
+/// Progress bar shown in the information area.
+#[derive(Clone)]
+struct Bar {
+    /// Text row.
+    row: usize,
+    /// Filled part of the bar from 0 to 1.
+    fraction: f32,
+    color: Color,
+}
+
+/// Cells shown in the information area.
+#[derive(Clone)]
+struct InfoGrid {
+    grid: CellGrid,
+    columns: usize,
+    rows: usize,
+    cell_width: usize,
+    cell_height: usize,
 }
 
 impl Info {
@@ -45,22 +87,39 @@ impl Info {
     /// `layout` - information area layout
     ///
     /// `lines` - information area content. A list of strings to display. If line number is more
-    /// than information area height, last lines will be ignored. Too long lines will be truncated.
+    /// than information area height, last lines will be hidden until text is scrolled (see
+    /// `Game::scroll_info`). Too long lines will be truncated.
     /// If you want space between lines, add empty string to list.
     ///
+    /// Lines can contain [escape sequences] to set text color and style. Termion provides
+    /// `termion::style` and `termion::color` for this. Escape sequences are not counted in line
+    /// length. Style is reset at the end of each line, so if line is wrapped (see [`set_wrap`]),
+    /// the continuation lines are not styled.
+    ///
+    /// [escape sequences]: https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_(Select_Graphic_Rendition)_parameters
+    /// [`set_wrap`]: #method.set_wrap
+    ///
     /// # Implementation note
     ///
     /// This crate iterates Unicode strings as a set of [grapheme clusters] to handle characters
-    /// like *g̈* correctly. When we slice strings to put them inside cells or dialogs, we expect
-    /// characters to have the same width. This is not always true for some Unicode symbols. Such
-    /// symbols will break layout.
+    /// like *g̈* correctly. Wide characters (CJK, emoji) take 2 terminal columns. If wide
+    /// character doesn't fit into the line, it is cut and its place is filled with space. Width
+    /// of some Unicode symbols depends on terminal. Such symbols can break layout.
     ///
     /// [grapheme clusters]: http://www.unicode.org/reports/tr29/
     ///
+    /// ```
+    /// # use gameboard::{Info, InfoLayout};
+    /// let info = Info::new(10, InfoLayout::Right, &["日本語", "Japanese"]);
+    /// // Text takes 6 columns, the rest of the line is filled with spaces.
+    /// assert!(info.render().contains("日本語    "));
+    /// ```
+    ///
     /// # Examples
     ///
     /// Information area is above the board. It has height 15 and width the same as a board.
     /// ```no_run
+    /// # use gameboard::{Board, Info, InfoLayout};
     /// let board = Board::new(5, 5, 10, 5, true, None);
     /// let info = Info::new(15, InfoLayout::Top, &[
     ///     "This is line 1.",
@@ -69,6 +128,16 @@ impl Info {
     ///     "This is line 4.",
     /// ]);
     /// ```
+    ///
+    /// Colored line.
+    /// ```no_run
+    /// use termion::color;
+    /// # use gameboard::{Info, InfoLayout};
+    ///
+    /// let info = Info::new(3, InfoLayout::Bottom, &[
+    ///     &format!("{}You lose.", color::Fg(color::Red)),
+    /// ]);
+    /// ```
     pub fn new(size: usize, layout: InfoLayout, lines: &[&str]) -> Self {
         let mut v = Vec::with_capacity(lines.len());
         for &l in lines {
@@ -81,10 +150,243 @@ impl Info {
             height: 1,
             size: size + 2, // add borders
             layout,
-            lines: v
+            lines: v,
+            wrap: false,
+            rtl: false,
+            border_color: None,
+            grid: None,
+            scroll: 0,
+            bars: Vec::new(),
+            changed_lines: Vec::new(),
+            changed_bars: Vec::new(),
+            full_update: true,
         }
     }
 
+    /// Enables or disables word wrapping.
+    ///
+    /// Word wrapping is disabled by default and too long lines are truncated. If wrapping is
+    /// enabled, too long lines are split into several lines at word boundaries. Words longer than
+    /// information area width are split as well. Wrapped lines are counted against the
+    /// information area height, last lines which don't fit are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use gameboard::{Board, Game, Info, InfoLayout, InputListener, Terminal, Key};
+    /// use gameboard::test::{RecordingOutput, ScriptedInput};
+    ///
+    /// # struct App;
+    /// # impl<R: Read, W: Terminal> InputListener<R, W> for App {
+    /// #     type Action = ();
+    /// #     fn handle_key(&mut self, _key: Key, _game: &mut Game<R, W, Self>) {}
+    /// # }
+    /// // Returns rows of the information area to the right of a 3x3 board.
+    /// fn show(wrap: bool) -> Vec<String> {
+    ///     let output = RecordingOutput::new(40, 10);
+    ///     let app = Rc::new(RefCell::new(App));
+    ///     let mut game = Game::with_terminal(ScriptedInput::new(&[]), output.clone(), app);
+    ///     let mut info = Info::new(12, InfoLayout::Right, &["one two three four"]);
+    ///     info.set_wrap(wrap);
+    ///     game.init(Board::new(3, 3, 1, 1, false, None), vec![info]);
+    ///     output.screen_snapshot()[1..4].iter()
+    ///         .map(|row| row.chars().skip(5).collect::<String>().trim_end().to_string())
+    ///         .collect()
+    /// }
+    ///
+    /// // The line is split at the word boundary and takes 2 rows.
+    /// assert_eq!(show(true), ["║one two     ║", "║three four  ║", "║            ║"]);
+    /// // The line is truncated at the area width.
+    /// assert_eq!(show(false), ["║one two thre║", "║            ║", "║            ║"]);
+    /// ```
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.full_update = true;
+    }
+
+    /// Enables or disables right-to-left text (Hebrew, Arabic).
+    ///
+    /// Right-to-left text is disabled by default. If it is enabled, lines are right-aligned and
+    /// their characters are written in reverse order, so text stored in logical order is shown
+    /// correctly. Too long lines lose their last characters (leftmost on the screen). Wrapping
+    /// works as usual.
+    ///
+    /// Styled right-to-left text is not supported: escape sequences at the start of the line
+    /// are kept, other escape sequences are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Info, InfoLayout};
+    /// let mut info = Info::new(8, InfoLayout::Right, &["שלום"]);
+    /// info.set_rtl(true);
+    /// assert!(info.render().contains("    םולש"));
+    /// ```
+    pub fn set_rtl(&mut self, rtl: bool) {
+        self.rtl = rtl;
+        self.full_update = true;
+    }
+
+    /// Adds cells to the information area.
+    ///
+    /// Information area shows a small board (for example, the next piece preview in Tetris)
+    /// in the top left corner inside the borders. Cells are drawn without cell borders, cells
+    /// which don't fit the area are not shown. Text lines are written below the cells, so
+    /// `Game::update_info_area` updates text and doesn't change cells. Use
+    /// `Game::update_info_cells` to update cells. `Cell::ResourceId` cells are not supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cells` contain wrong number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Cell, Info, InfoLayout};
+    /// use termion::cursor::Goto;
+    ///
+    /// let mut info = Info::new(6, InfoLayout::Right, &["Next"]);
+    /// info.set_cell_grid(3, 2, 2, 1, &[
+    ///     Cell::Char('#'), Cell::Char('#'), Cell::Char('#'),
+    ///     Cell::Empty, Cell::Char('#'), Cell::Empty,
+    /// ]);
+    /// let text = info.render();
+    /// assert!(text.contains(&format!("{}##", Goto(2, 2))));
+    /// assert!(text.contains(&format!("{}##", Goto(4, 3))));
+    /// assert!(text.contains(&format!("{}Next", Goto(2, 4))));
+    /// ```
+    pub fn set_cell_grid(&mut self, columns: usize, rows: usize, cell_width: usize,
+                         cell_height: usize, cells: &[Cell]) {
+        if cells.len() != columns * rows {
+            panic!("Wrong number of cells. Expected {}, got {}.", columns * rows, cells.len());
+        }
+        let mut grid = CellGrid::new(columns, rows, cell_width, cell_height, Rc::new(None));
+        grid.init_from_vec(cells);
+        self.grid = Some(InfoGrid { grid, columns, rows, cell_width, cell_height });
+        self.full_update = true;
+    }
+
+    /// Changes text of the line with `index`.
+    ///
+    /// Unlike replacing all lines with `Game::update_info_area`, only this line is redrawn on the
+    /// next screen update. This is useful for frequently updated lines (score, timer) next to
+    /// static text. If wrapping is enabled and the new text takes a different number of rows,
+    /// the whole area is redrawn. Use `Game::update_info_line` to update information area which
+    /// is already added to the game.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no line with this index.
+    pub fn update_line(&mut self, index: usize, text: &str) {
+        if index >= self.lines.len() {
+            panic!("Line index is out of range.");
+        }
+        if self.wrap {
+            let text_width = self.width.max(2) - 2;
+            let old_rows = str_utils::wrap_str(&self.lines[index], text_width).len();
+            if str_utils::wrap_str(text, text_width).len() != old_rows {
+                // Following lines are moved.
+                self.full_update = true;
+            }
+        }
+        self.lines[index] = String::from(text);
+        if !self.changed_lines.contains(&index) {
+            self.changed_lines.push(index);
+        }
+    }
+
+    /// Shows horizontal progress bar in the text row.
+    ///
+    /// The bar takes the whole width of the area. `fraction` of the width is filled with block
+    /// characters of `color`, the rest is empty. `fraction` is clamped to `[0, 1]`. This can be
+    /// used to show time left or health. Rows are counted on the screen from the first text row
+    /// (below cells, see [`set_cell_grid`]), text is not scrolled under the bar. The bar is drawn
+    /// over the text line in this row, so leave an empty line for it. Setting the bar again
+    /// updates it, only the bar row is redrawn. Use `Game::set_info_bar` to update the bar of
+    /// information area which is already added to the game.
+    ///
+    /// [`set_cell_grid`]: #method.set_cell_grid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Color, Info, InfoLayout};
+    /// use termion::{color, cursor::Goto};
+    ///
+    /// let mut info = Info::new(4, InfoLayout::Right, &["Time", ""]);
+    /// info.set_bar(1, 0.5, Color::Rgb(0, 200, 0));
+    /// assert!(info.render().contains(&format!("{}{}██", Goto(2, 3),
+    ///                                         color::Fg(color::Rgb(0, 200, 0)))));
+    /// ```
+    pub fn set_bar<C: Into<Color>>(&mut self, row: usize, fraction: f32, color: C) {
+        let bar = Bar { row, fraction: fraction.clamp(0.0, 1.0), color: color.into() };
+        match self.bars.iter_mut().find(|b| b.row == row) {
+            Some(b) => *b = bar,
+            None => self.bars.push(bar),
+        }
+        if !self.changed_bars.contains(&row) {
+            self.changed_bars.push(row);
+        }
+    }
+
+    /// Removes progress bar from the text row. Text line in this row is shown again.
+    pub fn remove_bar(&mut self, row: usize) {
+        self.bars.retain(|b| b.row != row);
+        if !self.changed_bars.contains(&row) {
+            self.changed_bars.push(row);
+        }
+    }
+
+    /// Sets color of the information area borders.
+    ///
+    /// Use `None` to use default terminal foreground color.
+    pub fn set_border_color(&mut self, color: Option<Color>) {
+        self.border_color = color;
+    }
+
+    /// Renders the whole information area.
+    ///
+    /// Returns a string with borders and lines as it would be written to the terminal, including
+    /// escape sequences. This can be used to test information area output without a terminal.
+    ///
+    /// Position and size of the information area are set by the game layout. Until then, it is
+    /// rendered at `(1, 1)` and the dimension taken from the board fits the area lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gameboard::{Info, InfoLayout};
+    /// use termion::cursor::Goto;
+    /// use termion::style::Reset;
+    ///
+    /// let info = Info::new(1, InfoLayout::Top, &["Hi"]);
+    /// assert_eq!(info.render(), format!("{}╔══╗{}║{}║{}╚══╝{}Hi{}",
+    ///                                   Goto(1, 1), Goto(1, 2), Goto(4, 2), Goto(1, 3),
+    ///                                   Goto(2, 2), Reset));
+    /// ```
+    pub fn render(&self) -> String {
+        if self.width < 2 || self.height < 2 {
+            let mut info = self.clone();
+            let max_len = self.lines.iter().map(|l| str_utils::get_str_len(l)).max().unwrap_or(0);
+            let (grid_w, grid_h) = self.get_grid_size();
+            let (w, h) = match self.layout {
+                InfoLayout::Left | InfoLayout::Right =>
+                    (self.size, grid_h + self.lines.len() + 2),
+                InfoLayout::Top | InfoLayout::Bottom => (max_len.max(grid_w) + 2, self.size),
+            };
+            info.set_position_and_size(self.position, w, h);
+            return info.render()
+        }
+        let mut res = self.get_border();
+        if let Some(content) = self.get_content(None) {
+            res.push_str(&content);
+        }
+        res
+    }
+
     pub(crate) fn get_size(&self) -> usize {
         self.size
     }
@@ -97,6 +399,22 @@ impl Info {
         self.position = pos;
         self.width = w;
         self.height = h;
+        self.full_update = true;
+    }
+
+    pub(crate) fn get_rect(&self) -> Rect {
+        let Position(x, y) = self.position;
+        Rect { x, y, width: self.width, height: self.height }
+    }
+
+    // Get (row, column) of the text area at screen position. Rows are counted as displayed, so
+    // wrapped line takes several rows. Return `None` for borders and positions outside.
+    pub(crate) fn get_text_position(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let Position(left, top) = self.position;
+        if x <= left || y <= top || x >= left + self.width - 1 || y >= top + self.height - 1 {
+            return None
+        }
+        Some((y - top - 1, x - left - 1))
     }
 
     pub(crate) fn get_border(&self) -> String {
@@ -105,6 +423,9 @@ impl Info {
         // Add 16 chars to row width for Goto sequences
         let mut res = String::with_capacity((self.width + 16) * self.height);
 
+        if let Some(fg) = self.border_color {
+            res.push_str(&color::Fg(fg).to_string());
+        }
         res.push_str(&format!(
             "{}{}{}{}{}",
             cursor::Goto(x, y),
@@ -132,41 +453,157 @@ impl Info {
             chars::DOUBLE_BORDER_HOR_LINE.to_string().repeat(self.width - 2),
             chars::DOUBLE_BORDER_BOTTOM_RIGHT
         ));
+        if self.border_color.is_some() {
+            res.push_str(style::Reset.as_ref());
+        }
         res
     }
 
+    pub(crate) fn update_cells(&mut self, updates: &CellUpdates) -> bool {
+        match self.grid {
+            Some(ref mut g) => {
+                if updates.iter().any(|(_, Position(x, y))| *x >= g.columns || *y >= g.rows) {
+                    panic!("Cell position is out of the information area cells bounds.");
+                }
+                g.grid.update_cells(updates);
+                self.full_update = true;
+                true
+            },
+            None => false
+        }
+    }
+
+    // Get size of the cells area in characters.
+    fn get_grid_size(&self) -> (usize, usize) {
+        match self.grid {
+            Some(ref g) => (g.columns * g.cell_width, g.rows * g.cell_height),
+            None => (0, 0)
+        }
+    }
+
+    // Get cells which fit the text area.
+    fn get_grid_content(&self, x: u16, y: u16, text_width: usize, text_height: usize) -> String {
+        let g = match self.grid {
+            Some(ref g) => g,
+            None => return String::new()
+        };
+        let mut res = String::new();
+        for (i, cell) in g.grid.iter().enumerate() {
+            let (column, row) = (i % g.columns, i / g.columns);
+            if (column + 1) * g.cell_width > text_width || (row + 1) * g.cell_height > text_height {
+                continue;
+            }
+            let cell_x = x + (column * g.cell_width) as u16;
+            let cell_y = y + (row * g.cell_height) as u16;
+            res.push_str(&cell.get_content(g.cell_width, g.cell_height, cell_x, cell_y,
+                                           CellPadding::default(), Rc::new(None)));
+        }
+        res
+    }
+
+    // Scroll text lines by `dy` lines. Return `false` if text can't be scrolled in this direction.
+    pub(crate) fn scroll(&mut self, dy: isize) -> bool {
+        let max_scroll = self.get_max_scroll();
+        let scroll = (self.scroll.min(max_scroll) as isize).saturating_add(dy).max(0) as usize;
+        let scroll = scroll.min(max_scroll);
+        if scroll == self.scroll {
+            return false
+        }
+        self.scroll = scroll;
+        self.full_update = true;
+        true
+    }
+
+    // Get number of visible text lines.
+    pub(crate) fn get_page_height(&self) -> usize {
+        (self.height.max(2) - 2).saturating_sub(self.get_grid_size().1)
+    }
+
+    fn get_max_scroll(&self) -> usize {
+        let text_width = self.width.max(2) - 2;
+        self.get_text_lines(text_width).len().saturating_sub(self.get_page_height())
+    }
+
+    // Get text lines as displayed with indexes of source lines, wrapped lines are split.
+    fn get_text_lines(&self, text_width: usize) -> Vec<(usize, &str)> {
+        if self.wrap {
+            self.lines.iter().enumerate().flat_map(|(i, l)| {
+                str_utils::wrap_str(l, text_width).into_iter().map(move |row| (i, row))
+            }).collect()
+        } else {
+            self.lines.iter().map(|l| l.as_str()).enumerate().collect()
+        }
+    }
+
     pub(crate) fn update(&mut self, lines: &[&str]) {
         self.lines = Vec::with_capacity(lines.len());
         for &l in lines {
             self.lines.push(String::from(l));
         }
+        self.full_update = true;
+    }
+
+    // Request redrawing of the whole area on the next screen update.
+    pub(crate) fn request_full_redraw(&mut self) {
+        self.full_update = true;
     }
 
-    pub(crate) fn get_updates(&self) -> Option<String> {
-        let line_num = self.lines.len();
-        if line_num == 0 {
+    // Get content changed since the last call. Only lines changed by `update_line` are returned
+    // if nothing else was changed. Return `None` if nothing is changed.
+    pub(crate) fn get_updates(&mut self) -> Option<String> {
+        if !self.full_update && self.changed_lines.is_empty() && self.changed_bars.is_empty() {
+            return None
+        }
+        let changed_lines = mem::take(&mut self.changed_lines);
+        let changed_bars = mem::take(&mut self.changed_bars);
+        let full_update = mem::replace(&mut self.full_update, false);
+        self.get_content(if full_update { None } else { Some((&changed_lines, &changed_bars)) })
+    }
+
+    // Get content of the area inside borders. If `changed` is set, only rows of these lines and
+    // bars are returned.
+    fn get_content(&self, changed: Option<(&[usize], &[usize])>) -> Option<String> {
+        if self.lines.is_empty() && self.grid.is_none() && self.bars.is_empty() {
             return None
         }
 
         let x = self.position.0 as u16 + 1;
-        let mut y = self.position.1 as u16 + 1;
+        let y = self.position.1 as u16 + 1;
         let text_width = self.width - 2;
+        let text_height = self.height - 2;
+        // Text lines are written below the cells.
+        let grid_height = self.get_grid_size().1.min(text_height);
+
+        let lines = self.get_text_lines(text_width);
+        // Content could be changed after scrolling, so scroll position is limited here as well.
+        let first = self.scroll.min(lines.len().saturating_sub(text_height - grid_height));
 
+        let align = if self.rtl { TextAlign::RightToLeft } else { TextAlign::Left };
         let mut res =
             String::with_capacity((self.width + str_utils::GOTO_SEQUENCE_WIDTH) * self.height);
-        for i in 0..self.height - 2 {
-            if i < line_num {
-                let line = &self.lines[i];
-                let s = if str_utils::get_str_len(line) < text_width {
-                    format!("{:width$}", &line, width = text_width)
-                } else {
-                    str_utils::get_str_range(line, 0, text_width).to_string()
-                };
-                res.push_str(&format!("{}{}", cursor::Goto(x, y), s));
+        if changed.is_none() {
+            res.push_str(&self.get_grid_content(x, y, text_width, text_height));
+        }
+        for i in 0..text_height - grid_height {
+            let y = y + (grid_height + i) as u16;
+            let line = lines.get(first + i);
+            if let Some((changed_lines, changed_bars)) = changed {
+                let line_changed = line.map_or(false, |(index, _)| changed_lines.contains(index));
+                if !line_changed && !changed_bars.contains(&i) {
+                    continue
+                }
+            }
+            if let Some(bar) = self.bars.iter().find(|b| b.row == i) {
+                let filled = (bar.fraction * text_width as f32).round() as usize;
+                res.push_str(&format!("{}{}{}{}{}", cursor::Goto(x, y), color::Fg(bar.color),
+                                      chars::BAR_FILLED.to_string().repeat(filled), style::Reset,
+                                      " ".repeat(text_width - filled)));
+            } else if let Some((_, line)) = line {
+                let s = str_utils::align_str(line, text_width, align);
+                res.push_str(&format!("{}{}{}", cursor::Goto(x, y), s, style::Reset));
             } else {
                 res.push_str(&format!("{}{}", cursor::Goto(x, y), " ".repeat(text_width)));
             }
-            y += 1;
         }
         Some(res)
     }
@@ -0,0 +1,220 @@
+//! User input.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "termion")]
+use termion::event;
+
+/// Key pressed by user.
+///
+/// This type doesn't depend on terminal backend. With `termion` feature, keys from
+/// `termion::event::Key` can be converted to it with `From` trait. Keys which can't be mapped are
+/// converted to `Key::Unknown`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Character key. *Enter* and *Tab* are not characters, see `Key::Enter` and `Key::Tab`.
+    Char(char),
+    /// *Alt* + character key.
+    Alt(char),
+    /// *Ctrl* + character key.
+    Ctrl(char),
+    /// Function key. *F1* is `F(1)`.
+    F(u8),
+    /// *Enter* key.
+    Enter,
+    /// *Tab* key.
+    Tab,
+    /// *Shift* + *Tab* key.
+    BackTab,
+    /// *Backspace* key.
+    Backspace,
+    /// *Delete* key.
+    Delete,
+    /// *Insert* key.
+    Insert,
+    /// *Esc* key.
+    Esc,
+    /// Left arrow key.
+    Left,
+    /// Right arrow key.
+    Right,
+    /// Up arrow key.
+    Up,
+    /// Down arrow key.
+    Down,
+    /// *Home* key.
+    Home,
+    /// *End* key.
+    End,
+    /// *Page Up* key.
+    PageUp,
+    /// *Page Down* key.
+    PageDown,
+    /// Null byte.
+    Null,
+    /// Key which can't be mapped.
+    Unknown,
+}
+
+#[cfg(feature = "termion")]
+impl From<event::Key> for Key {
+    /// Converts termion key.
+    ///
+    /// `Char('\n')` is converted to `Key::Enter`, `Char('\t')` is converted to `Key::Tab`.
+    fn from(key: event::Key) -> Self {
+        match key {
+            event::Key::Char('\n') => Key::Enter,
+            event::Key::Char('\t') => Key::Tab,
+            event::Key::Char(c) => Key::Char(c),
+            event::Key::Alt(c) => Key::Alt(c),
+            event::Key::Ctrl(c) => Key::Ctrl(c),
+            event::Key::F(n) => Key::F(n),
+            event::Key::BackTab => Key::BackTab,
+            event::Key::Backspace => Key::Backspace,
+            event::Key::Delete => Key::Delete,
+            event::Key::Insert => Key::Insert,
+            event::Key::Esc => Key::Esc,
+            event::Key::Left => Key::Left,
+            event::Key::Right => Key::Right,
+            event::Key::Up => Key::Up,
+            event::Key::Down => Key::Down,
+            event::Key::Home => Key::Home,
+            event::Key::End => Key::End,
+            event::Key::PageUp => Key::PageUp,
+            event::Key::PageDown => Key::PageDown,
+            event::Key::Null => Key::Null,
+            _ => Key::Unknown
+        }
+    }
+}
+
+/// Map of keys to user actions.
+///
+/// Actions are values of user type (see `InputListener::Action`). When game has bindings (see
+/// `Game::set_bindings`), bound keys are passed to `InputListener::on_action` instead of
+/// `handle_key`. So game controls can be changed without changing key handlers. Keys which are
+/// not bound are passed to `handle_key` as usual.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::{Key, KeyBindings};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Place,
+///     Undo,
+/// }
+///
+/// let mut bindings = KeyBindings::new();
+/// bindings.bind(Key::Char(' '), Action::Place);
+/// bindings.bind(Key::Enter, Action::Place);
+/// bindings.bind(Key::Char('u'), Action::Undo);
+///
+/// assert_eq!(bindings.get_action(Key::Enter), Some(Action::Place));
+/// assert_eq!(bindings.get_action(Key::Char('x')), None);
+/// ```
+#[derive(Clone)]
+pub struct KeyBindings<A> {
+    actions: HashMap<Key, A>,
+}
+
+impl<A: Copy> KeyBindings<A> {
+    /// Creates empty bindings.
+    pub fn new() -> Self {
+        KeyBindings { actions: HashMap::new() }
+    }
+
+    /// Binds `key` to `action`. Previous binding of this key is replaced. Several keys can be
+    /// bound to the same action.
+    pub fn bind(&mut self, key: Key, action: A) {
+        self.actions.insert(key, action);
+    }
+
+    /// Removes binding of `key`.
+    pub fn unbind(&mut self, key: Key) {
+        self.actions.remove(&key);
+    }
+
+    /// Returns action bound to `key`.
+    pub fn get_action(&self, key: Key) -> Option<A> {
+        self.actions.get(&key).cloned()
+    }
+}
+
+impl<A: Copy> Default for KeyBindings<A> {
+    fn default() -> Self {
+        KeyBindings::new()
+    }
+}
+
+// Get mouse pointer position from mouse motion event without pressed buttons. Input event parser
+// doesn't support such events, so we parse them from SGR sequence: ESC [ < Cb ; Cx ; Cy M.
+pub(crate) fn parse_mouse_motion(seq: &[u8]) -> Option<(u16, u16)> {
+    let seq = std::str::from_utf8(seq).ok()?;
+    let seq = seq.strip_prefix("\x1b[<")?.strip_suffix('M')?;
+    let mut nums = seq.split(';').map(|n| n.parse::<u16>());
+    let (cb, x, y) = (nums.next()?.ok()?, nums.next()?.ok()?, nums.next()?.ok()?);
+    // Motion flag is 32. Lower bits 3 mean no button is pressed.
+    if cb & 32 != 0 {
+        Some((x, y))
+    } else {
+        None
+    }
+}
+
+// Encode key as the byte sequence which is parsed to the same key. Keys which can't be parsed
+// (for example, `Key::Unknown` or *F13*) are encoded as empty sequence.
+pub(crate) fn encode_key(key: Key, buf: &mut Vec<u8>) {
+    let seq: &[u8] = match key {
+        Key::Char(c) => {
+            let mut tmp = [0; 4];
+            buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+            return
+        },
+        Key::Alt(c) => {
+            let mut tmp = [0; 4];
+            buf.push(0x1b);
+            buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+            return
+        },
+        Key::Ctrl(c) => {
+            match c.to_ascii_lowercase() {
+                c @ 'a'..='z' => buf.push(c as u8 - b'a' + 0x01),
+                c @ '4'..='7' => buf.push(c as u8 - b'4' + 0x1c),
+                _ => {}
+            }
+            return
+        },
+        Key::Enter => b"\n",
+        Key::Tab => b"\t",
+        Key::BackTab => b"\x1b[Z",
+        Key::Backspace => b"\x7f",
+        Key::Delete => b"\x1b[3~",
+        Key::Insert => b"\x1b[2~",
+        Key::Esc => b"\x1b",
+        Key::Left => b"\x1b[D",
+        Key::Right => b"\x1b[C",
+        Key::Up => b"\x1b[A",
+        Key::Down => b"\x1b[B",
+        Key::Home => b"\x1b[H",
+        Key::End => b"\x1b[F",
+        Key::PageUp => b"\x1b[5~",
+        Key::PageDown => b"\x1b[6~",
+        Key::Null => b"\0",
+        Key::F(1) => b"\x1bOP",
+        Key::F(2) => b"\x1bOQ",
+        Key::F(3) => b"\x1bOR",
+        Key::F(4) => b"\x1bOS",
+        Key::F(5) => b"\x1b[15~",
+        Key::F(6) => b"\x1b[17~",
+        Key::F(7) => b"\x1b[18~",
+        Key::F(8) => b"\x1b[19~",
+        Key::F(9) => b"\x1b[20~",
+        Key::F(10) => b"\x1b[21~",
+        Key::F(11) => b"\x1b[23~",
+        Key::F(12) => b"\x1b[24~",
+        Key::F(_) | Key::Unknown => b"",
+    };
+    buf.extend_from_slice(seq);
+}
@@ -1,24 +1,61 @@
 //! Gameboard is a small library to create text UI for board games (like tic-tac-toe,
 //! scrabble etc.). It allows you to easily draw and update board in the terminal.
 //!
-//! Board must be rectangular and must contain rectangular cells. Also information board is supported.
+//! Board must be rectangular and must contain rectangular cells. Also information areas are
+//! supported. They are placed around the board and show text lines and optionally a small board
+//! of cells (see [`Info`]).
 //!
-//! Library uses [termion] crate for terminal input/output.
+//! [`Info`]: info/struct.Info.html
+//!
+//! Library uses [termion] crate for terminal input/output by default.
 //!
 //! [termion]: https://github.com/redox-os/termion
 //!
+//! # Features
+//!
+//! * `termion` (default) - termion terminal backend ([`Game::new`]) and conversions from termion
+//!   colors and keys. Disable default features to build the library without termion, for
+//!   example, with `crossterm` backend only.
+//! * `crossterm` - crossterm terminal backend (see [`crossterm_backend`]).
+//! * `serde` - `Serialize` and `Deserialize` implementations for [`BoardState`], [`Cell`] and
+//!   [`Position`].
+//!
+//! [`Game::new`]: game/struct.Game.html#method.new
+//! [`crossterm_backend`]: crossterm_backend/index.html
+//! [`BoardState`]: board/struct.BoardState.html
+//! [`Cell`]: cell/enum.Cell.html
+//! [`Position`]: game/struct.Position.html
+//!
 
-pub use board::{Board, ResourceTable, CellUpdates};
-pub use cell::Cell;
-pub use game::{Game, GameState, InputListener, Position};
+pub use board::{Board, BoardBuilder, BoardError, BoardState, BorderStyle, Edge, RenderMode,
+                ResourceTable, CellUpdates};
+pub use cell::{Cell, CellPadding};
+pub use color::{Color, ColorMode};
+pub use game::{Focus, Game, GameError, GameState, InputListener, LayoutError, Position, Rect,
+               ScreenAlign};
 pub use info::{Info, InfoLayout};
-pub use cursor::Cursor;
+pub use cursor::{Cursor, CursorBuilder};
+pub use dialog::{DialogPosition, DialogStyle};
+pub use terminal::Terminal;
+pub use input::{Key, KeyBindings};
+pub use theme::Theme;
 
 pub mod board;
 pub mod game;
 pub mod info;
 pub mod cell;
+pub mod color;
 pub mod cursor;
+pub mod dialog;
+pub mod terminal;
+pub mod input;
+pub mod theme;
+pub mod text;
+pub mod test;
+#[cfg(feature = "crossterm")]
+pub mod crossterm_backend;
+mod ansi;
 mod chars;
 mod cell_grid;
+mod event;
 mod str_utils;
@@ -1,16 +1,232 @@
-use unicode_segmentation::UnicodeSegmentation;
+use unicode_segmentation::{UnicodeSegmentation, GraphemeIndices};
+use unicode_width::UnicodeWidthStr;
 
 pub(crate) const GOTO_SEQUENCE_WIDTH: usize = 16;
 
+const CSI_SGR_START: char = '\x1b';
+const CSI_SGR_END: char = 'm';
+
+const TEXT_ALIGN_CENTER: &str = "|^|";
+const TEXT_ALIGN_RIGHT: &str = "|>|";
+
+/// Horizontal text alignment.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum TextAlign {
+    Left = 0,
+    Center,
+    Right,
+    /// Right-to-left text, right-aligned.
+    RightToLeft,
+}
+
+/// Iterator over visible grapheme clusters of the string. Escape sequences are skipped.
+pub(crate) struct VisibleGraphemes<'a> {
+    iter: GraphemeIndices<'a>,
+    is_csi: bool,
+}
+
+impl<'a> Iterator for VisibleGraphemes<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        for (i, ch) in &mut self.iter {
+            if ch.as_bytes()[0] as char == CSI_SGR_START {
+                self.is_csi = true;
+            } else if self.is_csi && ch.as_bytes()[0] as char == CSI_SGR_END {
+                self.is_csi = false;
+            } else if !self.is_csi {
+                return Some((i, ch))
+            }
+        }
+        None
+    }
+}
+
+pub(crate) fn visible_graphemes(text: &str) -> VisibleGraphemes<'_> {
+    VisibleGraphemes {
+        iter: UnicodeSegmentation::grapheme_indices(text, true),
+        is_csi: false,
+    }
+}
+
+// Get substring from `start` to `end` terminal columns. If `start` is 0, escape sequences at
+// the beginning of the string are included. Wide characters which don't fit into the range are
+// not included, so substring can be narrower than the range. Return empty string if range is
+// empty or `start` is out of the string.
 pub(crate) fn get_str_range(text: &str, start: usize, end: usize) -> &str {
-    let mut iter = UnicodeSegmentation::grapheme_indices(text, true);
-    let (s, _) = iter.nth(start).expect("Invalid string range index.");
-    match iter.nth(end - start - 1) {
-        Some((e, _)) => &text[s as usize..e as usize],
-        None => &text[s as usize..]
+    if end <= start {
+        return ""
+    }
+    let mut col = 0;
+    let mut s = None;
+    let mut e = text.len();
+    for (i, g) in visible_graphemes(text) {
+        let w = get_grapheme_width(g);
+        if s.is_none() && col >= start {
+            s = Some(i);
+        }
+        if s.is_some() && col + w > end {
+            e = i;
+            break;
+        }
+        col += w;
+    }
+    match s {
+        Some(_) if start == 0 => &text[..e],
+        Some(s) => &text[s..e],
+        None => ""
     }
 }
 
+// Get width of the string in terminal columns. Escape sequences are not counted. Wide
+// characters (CJK, emoji) take 2 columns.
 pub(crate) fn get_str_len(text: &str) -> usize {
-    UnicodeSegmentation::graphemes(text, true).count()
+    visible_graphemes(text).map(|(_, g)| get_grapheme_width(g)).sum()
+}
+
+// Get number of visible grapheme clusters in the string.
+pub(crate) fn get_grapheme_count(text: &str) -> usize {
+    visible_graphemes(text).count()
+}
+
+// Get width of grapheme cluster in terminal columns. Emoji sequences are shown as one symbol,
+// so width can't be more than 2.
+pub(crate) fn get_grapheme_width(grapheme: &str) -> usize {
+    UnicodeWidthStr::width(grapheme).min(2)
+}
+
+// Get text alignment from alignment prefix (|^| or |>|) and return text without prefix.
+pub(crate) fn split_align_prefix(text: &str) -> (TextAlign, &str) {
+    if let Some(s) = text.strip_prefix(TEXT_ALIGN_CENTER) {
+        (TextAlign::Center, s)
+    } else if let Some(s) = text.strip_prefix(TEXT_ALIGN_RIGHT) {
+        (TextAlign::Right, s)
+    } else {
+        (TextAlign::Left, text)
+    }
+}
+
+// Get number of spaces to add before and after the text of length `len` to align it within
+// `width`.
+pub(crate) fn get_padding(len: usize, width: usize, align: TextAlign) -> (usize, usize) {
+    let pad = width.saturating_sub(len);
+    match align {
+        TextAlign::Left => (0, pad),
+        TextAlign::Center => (pad / 2, pad - pad / 2),
+        TextAlign::Right | TextAlign::RightToLeft => (pad, 0),
+    }
+}
+
+// Align text within `width`. Too long text is truncated. Right-to-left text is reversed.
+pub(crate) fn align_str(text: &str, width: usize, align: TextAlign) -> String {
+    let text = if get_str_len(text) > width { get_str_range(text, 0, width) } else { text };
+    let reversed;
+    let text = if align == TextAlign::RightToLeft {
+        reversed = reverse_str(text);
+        &reversed
+    } else {
+        text
+    };
+    // Truncated text can be narrower than `width` if wide character doesn't fit.
+    let (before, after) = get_padding(get_str_len(text), width, align);
+    format!("{}{}{}", " ".repeat(before), text, " ".repeat(after))
+}
+
+// Split text into lines not longer than `width` at word boundaries. Too long words are split.
+pub(crate) fn wrap_str(text: &str, width: usize) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut rest = text;
+    while width > 0 && get_str_len(rest) > width {
+        // (line end, next line start)
+        let mut word_break = None;
+        let mut width_end = rest.len();
+        let mut col = 0;
+        for (i, g) in visible_graphemes(rest) {
+            let is_space = g.trim().is_empty();
+            let w = get_grapheme_width(g);
+            // The first character is always taken, even if it is wider than the line.
+            if col + w > width && col > 0 {
+                width_end = i;
+                if is_space {
+                    word_break = Some((i, i + g.len()));
+                }
+                break;
+            }
+            if is_space && i > 0 {
+                word_break = Some((i, i + g.len()));
+            }
+            col += w;
+        }
+        let (line_end, next_start) = word_break.unwrap_or((width_end, width_end));
+        lines.push(rest[..line_end].trim_end());
+        rest = rest[next_start..].trim_start();
+    }
+    lines.push(rest);
+    lines
+}
+
+// Get width of the widest row of the text written into `width` columns by characters. Wide
+// character which doesn't fit into the row is moved to the next row, the same as in cells.
+pub(crate) fn get_wrapped_width(text: &str, width: usize) -> usize {
+    let mut max = 0;
+    let mut col = 0;
+    for (_, g) in visible_graphemes(text) {
+        let w = get_grapheme_width(g);
+        if col > 0 && col + w > width {
+            col = 0;
+        }
+        col += w;
+        max = max.max(col);
+    }
+    max
+}
+
+// Reverse order of visible grapheme clusters, so right-to-left text can be written left-to-right.
+// Escape sequences before the first visible character are kept at the start, other escape
+// sequences are removed.
+pub(crate) fn reverse_str(text: &str) -> String {
+    let lead = &text[..get_grapheme_offset(text, 0)];
+    let graphemes: Vec<&str> = visible_graphemes(text).map(|(_, g)| g).collect();
+    let mut res = String::with_capacity(text.len());
+    res.push_str(lead);
+    res.extend(graphemes.iter().rev().cloned());
+    res
+}
+
+// Convert right-to-left text written into `width` columns by characters to text which looks the
+// same when written left-to-right. Each row is reversed and all rows except the last one are
+// padded to `width` at the left. The last row gets alignment prefix of `align`, right-to-left
+// text is right-aligned unless it is centered. Rows are split the same way as in cells. Escape
+// sequences are handled the same way as in `reverse_str`.
+pub(crate) fn rtl_to_ltr(text: &str, width: usize, align: TextAlign) -> String {
+    let mut rows: Vec<(Vec<&str>, usize)> = vec![(Vec::new(), 0)];
+    for (_, g) in visible_graphemes(text) {
+        let w = get_grapheme_width(g);
+        let (_, col) = rows[rows.len() - 1];
+        if col > 0 && col + w > width {
+            rows.push((Vec::new(), 0));
+        }
+        let row = rows.last_mut().unwrap();
+        row.0.push(g);
+        row.1 += w;
+    }
+    let mut res = String::with_capacity(text.len() + width * rows.len());
+    res.push_str(if align == TextAlign::Center { TEXT_ALIGN_CENTER } else { TEXT_ALIGN_RIGHT });
+    res.push_str(&text[..get_grapheme_offset(text, 0)]);
+    let last = rows.len() - 1;
+    for (i, (row, col)) in rows.iter().enumerate() {
+        if i < last {
+            res.push_str(&" ".repeat(width.saturating_sub(*col)));
+        }
+        res.extend(row.iter().rev().cloned());
+    }
+    res
+}
+
+// Get byte offset of visible grapheme with `index` or string length if there is no such grapheme.
+pub(crate) fn get_grapheme_offset(text: &str, index: usize) -> usize {
+    match visible_graphemes(text).nth(index) {
+        Some((i, _)) => i,
+        None => text.len()
+    }
 }
@@ -0,0 +1,144 @@
+//! Terminal output backend.
+//!
+//! Game writes all output using [`Terminal`] trait. Implementations for termion raw terminal and
+//! alternate screen are provided with `termion` feature. You can implement this trait to use
+//! another terminal library or to record game output.
+//!
+//! [`Terminal`]: trait.Terminal.html
+
+use std::io::{self, Write};
+use std::panic;
+
+#[cfg(feature = "termion")]
+use termion::raw::RawTerminal;
+#[cfg(feature = "termion")]
+use termion::screen::AlternateScreen;
+
+use crate::ansi::{cursor, screen};
+
+/// Disable mouse button and motion events reporting.
+pub(crate) const MOUSE_DISABLE_SEQUENCE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1003l\x1b[?1000l";
+
+/// Terminal output backend.
+///
+/// Output is written using `Write` methods. Game writes ANSI escape sequences to move cursor and
+/// set colors, so terminal must support them. Game switches terminal to raw mode with
+/// [`enable_raw_mode`] when game object is created and restores it with [`disable_raw_mode`]
+/// when game object is dropped.
+///
+/// [`enable_raw_mode`]: #method.enable_raw_mode
+/// [`disable_raw_mode`]: #method.disable_raw_mode
+///
+/// # Examples
+///
+/// Terminal which records output in memory.
+/// ```
+/// use std::io::{self, Write};
+/// use gameboard::Terminal;
+///
+/// struct Recorder {
+///     output: Vec<u8>,
+/// }
+///
+/// impl Write for Recorder {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.output.write(buf)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl Terminal for Recorder {
+///     fn size(&self) -> io::Result<(u16, u16)> {
+///         Ok((80, 24))
+///     }
+/// }
+/// ```
+pub trait Terminal: Write {
+    /// Hides terminal cursor. Default implementation writes `ESC [ ? 25 l` sequence.
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        write!(self, "{}", cursor::Hide)
+    }
+
+    /// Shows terminal cursor. Default implementation writes `ESC [ ? 25 h` sequence.
+    fn show_cursor(&mut self) -> io::Result<()> {
+        write!(self, "{}", cursor::Show)
+    }
+
+    /// Returns terminal size in characters (width, height).
+    fn size(&self) -> io::Result<(u16, u16)>;
+
+    /// Switches terminal to raw mode: input isn't echoed and is available without waiting for
+    /// *Enter*. Default implementation does nothing, it is enough for terminals which are always
+    /// in raw mode or don't read input (for example, output recorder).
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Restores terminal mode changed by [`enable_raw_mode`]. Default implementation does
+    /// nothing.
+    ///
+    /// [`enable_raw_mode`]: #method.enable_raw_mode
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "termion")]
+impl<W: Write> Terminal for RawTerminal<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        termion::terminal_size()
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        self.activate_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        self.suspend_raw_mode()
+    }
+}
+
+#[cfg(feature = "termion")]
+impl<W: Terminal> Terminal for AlternateScreen<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        termion::terminal_size()
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        (**self).enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        (**self).disable_raw_mode()
+    }
+}
+
+/// Installs panic hook which restores the terminal before panic message is printed.
+///
+/// Game is usually drawn on the alternate screen, so panic message printed there is lost when
+/// the program exits. The hook disables mouse reporting, shows the cursor and switches back to
+/// the main screen (all write errors are ignored), then calls the previously installed hook.
+/// Raw mode is restored when `RawTerminal` is dropped during unwinding, so this doesn't help if
+/// panics abort the process. Call it once before creating the game.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gameboard::terminal;
+///
+/// terminal::install_panic_hook();
+/// // Create and start the game.
+/// ```
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{}{}{}", MOUSE_DISABLE_SEQUENCE, cursor::Show,
+                       screen::ToMainScreen);
+        let _ = stdout.flush();
+        previous(info);
+    }));
+}
@@ -0,0 +1,278 @@
+//! Helpers for scripted games and tests.
+//!
+//! Game reads input from any `Read` stream and writes output to any [`Terminal`], so it can be
+//! run without a real terminal. [`ScriptedInput`] feeds predefined keys to the game, and
+//! `Game::start` returns when all keys are handled. [`RecordingOutput`] records game output and
+//! shows what is on the screen.
+//!
+//! [`Terminal`]: ../terminal/trait.Terminal.html
+//! [`ScriptedInput`]: struct.ScriptedInput.html
+//! [`RecordingOutput`]: struct.RecordingOutput.html
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::input::{encode_key, Key};
+use crate::terminal::Terminal;
+
+/// Input stream which yields predefined keys.
+///
+/// Keys are encoded as the byte sequences terminal sends, including escape sequences for arrow
+/// and function keys. Stream ends when all keys are read. Keys which can't be encoded (for
+/// example, `Key::Unknown`) are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Read, Write};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use gameboard::{Board, Cursor, Game, InputListener, Key, Position, Terminal};
+/// use gameboard::test::ScriptedInput;
+/// use termion::color;
+/// # struct Recorder {}
+/// # impl Write for Recorder {
+/// #     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { Ok(buf.len()) }
+/// #     fn flush(&mut self) -> io::Result<()> { Ok(()) }
+/// # }
+/// # impl Terminal for Recorder {
+/// #     fn size(&self) -> io::Result<(u16, u16)> { Ok((80, 24)) }
+/// # }
+///
+/// struct App {
+///     position: Position,
+///     keys: Vec<Key>,
+/// }
+///
+/// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+///     type Action = ();
+///
+///     fn handle_key(&mut self, key: Key, _game: &mut Game<R, W, Self>) {
+///         self.keys.push(key);
+///     }
+///
+///     fn cursor_moved(&mut self, position: Position, _game: &mut Game<R, W, Self>) {
+///         self.position = position;
+///     }
+/// }
+///
+/// let input = ScriptedInput::new(&[Key::Right, Key::Down, Key::Esc, Key::Char('x')]);
+/// let app = Rc::new(RefCell::new(App { position: Position(0, 0), keys: Vec::new() }));
+/// let mut game = Game::with_terminal(input, Recorder {}, Rc::clone(&app));
+/// let cursor = Cursor::new(color::Rgb(0, 0, 200), Position(0, 0), false, None);
+/// let mut board = Board::new(3, 3, 1, 1, false, None);
+/// board.init_from_str("         ", Some(cursor));
+/// game.init(board, None);
+/// game.start();
+///
+/// assert!(app.borrow().position == Position(1, 1));
+/// assert!(app.borrow().keys == vec![Key::Esc, Key::Char('x')]);
+/// ```
+pub struct ScriptedInput {
+    /// Encoded keys not read yet.
+    keys: VecDeque<Vec<u8>>,
+}
+
+impl ScriptedInput {
+    /// Creates input stream which yields `keys`.
+    pub fn new(keys: &[Key]) -> Self {
+        let mut input = ScriptedInput { keys: VecDeque::with_capacity(keys.len()) };
+        for &key in keys {
+            input.push(key);
+        }
+        input
+    }
+
+    /// Adds key to the end of the stream.
+    pub fn push(&mut self, key: Key) {
+        let mut seq = Vec::new();
+        encode_key(key, &mut seq);
+        if !seq.is_empty() {
+            self.keys.push_back(seq);
+        }
+    }
+
+    /// Returns `true` if all keys are read.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl Read for ScriptedInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Bytes of one key only are returned, so single Esc isn't merged with the next key.
+        let seq = match self.keys.front_mut() {
+            Some(seq) => seq,
+            None => return Ok(0)
+        };
+        let n = buf.len().min(seq.len());
+        buf[..n].copy_from_slice(&seq[..n]);
+        seq.drain(..n);
+        if seq.is_empty() {
+            self.keys.pop_front();
+        }
+        Ok(n)
+    }
+}
+
+/// Terminal which records output in memory.
+///
+/// All written bytes are kept. [`screen_snapshot`] replays them on a grid of characters of the
+/// terminal size, so rendered screen can be checked without a real terminal. Clones share the
+/// same buffer: pass one clone to the game and keep another one to inspect output.
+///
+/// [`screen_snapshot`]: #method.screen_snapshot
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Read;
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use gameboard::{Board, Cell, Game, InputListener, Terminal, Key, Position};
+/// use gameboard::test::{RecordingOutput, ScriptedInput};
+///
+/// struct App {}
+///
+/// impl<R: Read, W: Terminal> InputListener<R, W> for App {
+///     type Action = ();
+///
+///     fn handle_key(&mut self, key: Key, game: &mut Game<R, W, Self>) {
+///         if key == Key::Char('x') {
+///             game.set_cell(Position(1, 1), Cell::Char('X'));
+///         }
+///     }
+/// }
+///
+/// let output = RecordingOutput::new(7, 7);
+/// let app = Rc::new(RefCell::new(App {}));
+/// let input = ScriptedInput::new(&[Key::Char('x')]);
+/// let mut game = Game::with_terminal(input, output.clone(), Rc::clone(&app));
+/// let mut board = Board::new(3, 3, 1, 1, true, None);
+/// board.init_from_str("         ", None);
+/// game.init(board, None);
+/// game.start();
+///
+/// assert_eq!(output.screen_snapshot(), vec![
+///     "╔═╤═╤═╗",
+///     "║ │ │ ║",
+///     "╟─┼─┼─╢",
+///     "║ │X│ ║",
+///     "╟─┼─┼─╢",
+///     "║ │ │ ║",
+///     "╚═╧═╧═╝",
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct RecordingOutput {
+    output: Rc<RefCell<Vec<u8>>>,
+    width: u16,
+    height: u16,
+}
+
+impl RecordingOutput {
+    /// Creates recording terminal of size `width` x `height` characters.
+    pub fn new(width: u16, height: u16) -> Self {
+        RecordingOutput {
+            output: Rc::new(RefCell::new(Vec::new())),
+            width,
+            height,
+        }
+    }
+
+    /// Returns all bytes written so far.
+    pub fn output(&self) -> Vec<u8> {
+        self.output.borrow().clone()
+    }
+
+    /// Removes all written bytes.
+    pub fn clear(&self) {
+        self.output.borrow_mut().clear();
+    }
+
+    /// Returns screen content as lines of text.
+    ///
+    /// Output is replayed on an empty screen. Cursor movement (`termion::cursor::Goto`), screen
+    /// clearing (`termion::clear::All`), carriage return and line feed are applied, all other
+    /// escape sequences (colors, styles etc.) are ignored. Every line is exactly terminal width
+    /// characters wide, wide characters take two columns.
+    pub fn screen_snapshot(&self) -> Vec<String> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        // Wide characters are followed by `None` which takes the second column.
+        let mut screen = vec![vec![Some(' '); width]; height];
+        let (mut x, mut y) = (0, 0);
+        let output = self.output.borrow();
+        let text = String::from_utf8_lossy(&output);
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    if chars.peek() != Some(&'[') {
+                        // Two characters escape sequence.
+                        chars.next();
+                        continue
+                    }
+                    chars.next();
+                    let mut params = String::new();
+                    let mut command = None;
+                    for c in &mut chars {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            command = Some(c);
+                            break
+                        }
+                        params.push(c);
+                    }
+                    match command {
+                        Some('H') => {
+                            let mut nums = params.split(';')
+                                .map(|n| n.parse::<usize>().unwrap_or(1));
+                            y = nums.next().unwrap_or(1).max(1) - 1;
+                            x = nums.next().unwrap_or(1).max(1) - 1;
+                        },
+                        Some('J') if params == "2" => {
+                            screen = vec![vec![Some(' '); width]; height];
+                        },
+                        _ => {}
+                    }
+                },
+                '\r' => x = 0,
+                '\n' => y += 1,
+                c => {
+                    let w = c.width().unwrap_or(0);
+                    if w == 0 {
+                        continue
+                    }
+                    if y < height && x + w <= width {
+                        screen[y][x] = Some(c);
+                        if w == 2 {
+                            screen[y][x + 1] = None;
+                        }
+                    }
+                    x += w;
+                }
+            }
+        }
+        screen.into_iter().map(|line| line.into_iter().flatten().collect()).collect()
+    }
+}
+
+impl Write for RecordingOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Terminal for RecordingOutput {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok((self.width, self.height))
+    }
+}
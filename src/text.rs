@@ -0,0 +1,48 @@
+//! Text measuring functions.
+//!
+//! Use them to measure strings before passing them to [`Info`] or `Game::show_message`, if you
+//! need to align text yourself. Width is measured in terminal columns. Characters are counted as
+//! grapheme clusters, so combining marks don't add width. Wide characters (CJK, emoji) take 2
+//! columns. Escape sequences are not counted. Alignment prefix is treated as regular text.
+//!
+//! [`Info`]: ../info/struct.Info.html
+
+use crate::str_utils;
+
+/// Returns width of the string in terminal columns.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::text;
+/// use termion::color;
+///
+/// assert_eq!(text::display_width("Score: 10"), 9);
+/// assert_eq!(text::display_width(&format!("{}Red", color::Fg(color::Red))), 3);
+/// assert_eq!(text::display_width("日本語"), 6);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    str_utils::get_str_len(text)
+}
+
+/// Returns part of the string from `start` to `end` column (`end` is excluded).
+///
+/// Wide characters which don't fit into the range are not included, so the result can be
+/// narrower than the range. Escape sequences inside the range are kept. If `start` is 0, escape
+/// sequences at the beginning of the string are included. If `end` is out of the string, the
+/// rest of the string is returned. Empty string is returned if `start` is out of the string or
+/// `end` is not greater than `start`.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::text;
+///
+/// assert_eq!(text::slice("Player 1", 0, 6), "Player");
+/// assert_eq!(text::slice("Player 1", 7, 20), "1");
+/// assert_eq!(text::slice("Player 1", 10, 20), "");
+/// assert_eq!(text::slice("日本語", 1, 5), "本");
+/// ```
+pub fn slice(text: &str, start: usize, end: usize) -> &str {
+    str_utils::get_str_range(text, start, end)
+}
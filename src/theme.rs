@@ -0,0 +1,72 @@
+//! Color themes.
+
+use crate::color::Color;
+use crate::dialog::DialogStyle;
+
+/// Set of colors used by the game.
+///
+/// Theme can be applied to the whole game with `Game::apply_theme` or to the board with
+/// `Board::set_theme`. Start from one of the presets and change the colors you need.
+///
+/// # Examples
+///
+/// ```
+/// use gameboard::{Color, Theme};
+///
+/// let theme = Theme {
+///     cursor: Color::Rgb(200, 0, 0),
+///     ..Theme::dark()
+/// };
+/// ```
+#[derive(Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// Cursor background color.
+    pub cursor: Color,
+    /// Color of the board and information area borders. `None` is default terminal color.
+    pub border: Option<Color>,
+    /// Background color of the board. `None` is default terminal color.
+    pub background: Option<Color>,
+    /// Background color of the cell under mouse pointer. `None` disables highlighting.
+    pub hover: Option<Color>,
+    /// Message dialog colors.
+    pub dialog: DialogStyle,
+}
+
+impl Theme {
+    /// Light text on dark background.
+    pub fn dark() -> Self {
+        Theme {
+            cursor: Color::Rgb(0, 0, 200),
+            border: Some(Color::Rgb(150, 150, 150)),
+            background: Some(Color::Rgb(20, 20, 30)),
+            hover: Some(Color::Rgb(60, 60, 60)),
+            dialog: DialogStyle::new(Some(Color::Rgb(220, 220, 0)), Some(Color::Rgb(0, 0, 96)),
+                                     Some(Color::Rgb(230, 230, 230))),
+        }
+    }
+
+    /// Dark text on light background.
+    ///
+    /// Board text uses default terminal color, so cells should set their text color.
+    pub fn light() -> Self {
+        Theme {
+            cursor: Color::Rgb(120, 170, 255),
+            border: Some(Color::Rgb(90, 90, 90)),
+            background: Some(Color::Rgb(235, 235, 225)),
+            hover: Some(Color::Rgb(205, 205, 195)),
+            dialog: DialogStyle::new(Some(Color::Rgb(60, 60, 60)), Some(Color::Rgb(250, 250, 240)),
+                                     Some(Color::Rgb(20, 20, 20))),
+        }
+    }
+
+    /// Default terminal colors with gray cursor.
+    pub fn mono() -> Self {
+        Theme {
+            cursor: Color::Rgb(128, 128, 128),
+            border: None,
+            background: None,
+            hover: Some(Color::Rgb(64, 64, 64)),
+            dialog: DialogStyle::default(),
+        }
+    }
+}